@@ -1,19 +1,49 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20261018_000001_create_reports;
+mod m20261018_000002_create_user_follows;
+mod m20261018_000003_add_proof_visibility;
+mod m20261018_000004_add_belief_weights;
+mod m20261018_000005_create_proof_photo_hashes;
+mod m20261018_000006_create_proof_shares;
+mod m20261018_000007_create_messages;
+mod m20261018_000008_add_member_last_read;
+mod m20261018_000009_extend_messages;
+mod m20261018_000010_create_search_index;
+mod m20261018_000011_create_lobby_bans;
+mod m20261018_000012_add_lobby_access;
+mod m20261018_000013_add_proof_photos_hashed_at;
+mod m20261018_000014_key_search_index_by_rowid;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261018_000001_create_reports::Migration),
+            Box::new(m20261018_000002_create_user_follows::Migration),
+            Box::new(m20261018_000003_add_proof_visibility::Migration),
+            Box::new(m20261018_000004_add_belief_weights::Migration),
+            Box::new(m20261018_000005_create_proof_photo_hashes::Migration),
+            Box::new(m20261018_000006_create_proof_shares::Migration),
+            Box::new(m20261018_000007_create_messages::Migration),
+            Box::new(m20261018_000008_add_member_last_read::Migration),
+            Box::new(m20261018_000009_extend_messages::Migration),
+            Box::new(m20261018_000010_create_search_index::Migration),
+            Box::new(m20261018_000011_create_lobby_bans::Migration),
+            Box::new(m20261018_000012_add_lobby_access::Migration),
+            Box::new(m20261018_000013_add_proof_photos_hashed_at::Migration),
+            Box::new(m20261018_000014_key_search_index_by_rowid::Migration),
+        ]
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use sea_orm::Database;
+    use sea_orm::{ConnectionTrait, Database, DatabaseConnection, DbBackend, Statement};
     use sea_orm_migration::prelude::*;
 
     use crate::m20220101_000001_create_table::Migration;
@@ -65,4 +95,136 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn test_all_migrations_up_and_down() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+
+        crate::Migrator::up(&db, None)
+            .await
+            .expect("Migrator UP failed");
+
+        let schema_manager = SchemaManager::new(&db);
+        for table in [
+            "reports",
+            "user_follows",
+            "moderation_flags",
+            "proof_photo_hashes",
+            "proof_photo_matches",
+            "proof_shares",
+            "messages",
+            "message_reactions",
+            "message_mentions",
+            "messages_fts",
+            "quest_proofs_fts",
+            "lobbies_fts",
+            "quests_fts",
+            "lobby_bans",
+            "lobby_invites",
+            "lobby_join_requests",
+        ] {
+            assert!(
+                schema_manager.has_table(table).await.unwrap(),
+                "Table '{}' was not created",
+                table
+            );
+        }
+        assert!(
+            schema_manager
+                .has_column("users", "is_moderator")
+                .await
+                .unwrap()
+        );
+        assert!(
+            schema_manager
+                .has_column("quest_proofs", "visibility")
+                .await
+                .unwrap()
+        );
+        assert!(
+            schema_manager
+                .has_column("lobby_members", "last_read_message_id")
+                .await
+                .unwrap()
+        );
+        assert!(
+            schema_manager
+                .has_column("lobbies", "visibility")
+                .await
+                .unwrap()
+        );
+        assert!(
+            schema_manager
+                .has_column("quest_proofs", "photos_hashed_at")
+                .await
+                .unwrap()
+        );
+
+        crate::Migrator::down(&db, None)
+            .await
+            .expect("Migrator DOWN failed");
+
+        for table in [
+            "reports",
+            "user_follows",
+            "moderation_flags",
+            "proof_photo_hashes",
+            "proof_photo_matches",
+            "proof_shares",
+            "messages",
+            "message_reactions",
+            "message_mentions",
+            "messages_fts",
+            "quests_fts",
+            "lobby_bans",
+            "lobby_invites",
+            "lobby_join_requests",
+        ] {
+            assert!(!schema_manager.has_table(table).await.unwrap());
+        }
+    }
+
+    async fn search(db: &DatabaseConnection, table: &str, term: &str) -> Vec<String> {
+        db.query_all_raw(Statement::from_sql_and_values(
+            DbBackend::Sqlite,
+            format!("SELECT ulid FROM {table} WHERE {table} MATCH ?"),
+            [term.into()],
+        ))
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| row.try_get("", "ulid").unwrap())
+        .collect()
+    }
+
+    #[tokio::test]
+    async fn test_search_index_follows_edits_and_deletes() {
+        let db = Database::connect("sqlite::memory:").await.unwrap();
+
+        // A quest from before the rowid keyed index
+        crate::Migrator::up(&db, Some(14)).await.unwrap();
+        db.execute_unprepared(
+            "INSERT INTO quests (ulid, title, complexity, xp_reward, validation_type, target_value) \
+             VALUES ('Q1', 'Walk through Šabac', 'E', 10, 'COMMUNITY', 1)",
+        )
+        .await
+        .unwrap();
+        crate::Migrator::up(&db, None).await.unwrap();
+        assert_eq!(search(&db, "quests_fts", "sabac").await, vec!["Q1"]);
+
+        db.execute_unprepared("UPDATE quests SET title = 'Run in Novi Sad' WHERE ulid = 'Q1'")
+            .await
+            .unwrap();
+        assert!(search(&db, "quests_fts", "sabac").await.is_empty());
+        assert_eq!(search(&db, "quests_fts", "novi").await, vec!["Q1"]);
+
+        db.execute_unprepared("DELETE FROM quests WHERE ulid = 'Q1'")
+            .await
+            .unwrap();
+        assert!(search(&db, "quests_fts", "novi").await.is_empty());
+
+        db.execute_unprepared("INSERT INTO quests_fts (quests_fts) VALUES ('integrity-check')")
+            .await
+            .unwrap();
+    }
 }
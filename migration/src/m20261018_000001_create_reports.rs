@@ -0,0 +1,126 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .add_column(
+                        ColumnDef::new(Users::IsModerator)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Reports::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Reports::Ulid).string_len(26).primary_key())
+                    .col(
+                        ColumnDef::new(Reports::ReporterId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(Reports::TargetType)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Reports::TargetId).string_len(26).not_null())
+                    .col(ColumnDef::new(Reports::Reason).string_len(16).not_null())
+                    .col(ColumnDef::new(Reports::Details).text())
+                    .col(
+                        ColumnDef::new(Reports::Status)
+                            .string_len(10)
+                            .not_null()
+                            .default("open"),
+                    )
+                    .col(ColumnDef::new(Reports::CreatedAt).date_time().not_null())
+                    .col(ColumnDef::new(Reports::ResolvedAt).date_time())
+                    .col(ColumnDef::new(Reports::ResolvedBy).string_len(26))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-report-reporter")
+                            .from(Reports::Table, Reports::ReporterId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // One report per reporter and target, duplicates are rejected by the database
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reports-reporter-target")
+                    .table(Reports::Table)
+                    .col(Reports::ReporterId)
+                    .col(Reports::TargetType)
+                    .col(Reports::TargetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Used by the auto-hide subqueries in the feed and lobby messages
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-reports-target-status")
+                    .table(Reports::Table)
+                    .col(Reports::TargetType)
+                    .col(Reports::TargetId)
+                    .col(Reports::Status)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Reports::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Users::Table)
+                    .drop_column(Users::IsModerator)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+    IsModerator,
+}
+
+#[derive(DeriveIden)]
+enum Reports {
+    Table,
+    Ulid,
+    ReporterId,
+    TargetType,
+    TargetId,
+    Reason,
+    Details,
+    Status,
+    CreatedAt,
+    ResolvedAt,
+    ResolvedBy,
+}
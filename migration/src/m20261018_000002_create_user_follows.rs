@@ -0,0 +1,84 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(UserFollows::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(UserFollows::FollowerId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFollows::FollowingId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(UserFollows::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(UserFollows::FollowerId)
+                            .col(UserFollows::FollowingId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-follow-follower")
+                            .from(UserFollows::Table, UserFollows::FollowerId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-follow-following")
+                            .from(UserFollows::Table, UserFollows::FollowingId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // The primary key covers "who do I follow", this one covers "who follows me"
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-user-follows-following")
+                    .table(UserFollows::Table)
+                    .col(UserFollows::FollowingId)
+                    .col(UserFollows::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(UserFollows::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum UserFollows {
+    Table,
+    FollowerId,
+    FollowingId,
+    CreatedAt,
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Existing proofs were visible to everyone, so they stay public
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .add_column(
+                        ColumnDef::new(QuestProofs::Visibility)
+                            .string_len(10)
+                            .not_null()
+                            .default("public"),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .drop_column(QuestProofs::Visibility)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuestProofs {
+    Table,
+    Visibility,
+}
@@ -0,0 +1,147 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Weights are stored in thousandths of a belief so sums stay exact integers
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofBeliefs::Table)
+                    .add_column(
+                        ColumnDef::new(QuestProofBeliefs::Weight)
+                            .integer()
+                            .not_null()
+                            .default(1000),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .add_column(
+                        ColumnDef::new(QuestProofs::BeliefWeight)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Beliefs given before weighting existed keep counting as full beliefs
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(QuestProofs::Table)
+                    .value(
+                        QuestProofs::BeliefWeight,
+                        Expr::col(QuestProofs::BeliefsCount).mul(1000),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModerationFlags::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModerationFlags::Ulid)
+                            .string_len(26)
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModerationFlags::TargetType)
+                            .string_len(10)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModerationFlags::TargetId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModerationFlags::Reason)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ModerationFlags::Details).text())
+                    .col(
+                        ColumnDef::new(ModerationFlags::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ModerationFlags::ResolvedAt).date_time())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-moderation-flags-target")
+                    .table(ModerationFlags::Table)
+                    .col(ModerationFlags::TargetType)
+                    .col(ModerationFlags::TargetId)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ModerationFlags::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .drop_column(QuestProofs::BeliefWeight)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofBeliefs::Table)
+                    .drop_column(QuestProofBeliefs::Weight)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuestProofBeliefs {
+    Table,
+    Weight,
+}
+
+#[derive(DeriveIden)]
+enum QuestProofs {
+    Table,
+    BeliefsCount,
+    BeliefWeight,
+}
+
+#[derive(DeriveIden)]
+enum ModerationFlags {
+    Table,
+    Ulid,
+    TargetType,
+    TargetId,
+    Reason,
+    Details,
+    CreatedAt,
+    ResolvedAt,
+}
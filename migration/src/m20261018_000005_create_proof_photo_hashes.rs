@@ -0,0 +1,215 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProofPhotoHashes::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::Ulid)
+                            .string_len(26)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::ProofId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::UserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::PhotoKey)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::Hash)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ProofPhotoHashes::Band0).integer().not_null())
+                    .col(ColumnDef::new(ProofPhotoHashes::Band1).integer().not_null())
+                    .col(ColumnDef::new(ProofPhotoHashes::Band2).integer().not_null())
+                    .col(ColumnDef::new(ProofPhotoHashes::Band3).integer().not_null())
+                    .col(
+                        ColumnDef::new(ProofPhotoHashes::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-photo-hash-proof")
+                            .from(ProofPhotoHashes::Table, ProofPhotoHashes::ProofId)
+                            .to(QuestProofs::Table, QuestProofs::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-photo-hashes-proof-key")
+                    .table(ProofPhotoHashes::Table)
+                    .col(ProofPhotoHashes::ProofId)
+                    .col(ProofPhotoHashes::PhotoKey)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Candidates are looked up by any equal band, see file_storage::perceptual_hash
+        for (name, band) in [
+            ("idx-photo-hashes-band0", ProofPhotoHashes::Band0),
+            ("idx-photo-hashes-band1", ProofPhotoHashes::Band1),
+            ("idx-photo-hashes-band2", ProofPhotoHashes::Band2),
+            ("idx-photo-hashes-band3", ProofPhotoHashes::Band3),
+        ] {
+            manager
+                .create_index(
+                    Index::create()
+                        .name(name)
+                        .table(ProofPhotoHashes::Table)
+                        .col(band)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProofPhotoMatches::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::Ulid)
+                            .string_len(26)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::ProofId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::PhotoKey)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::UserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::MatchedProofId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::MatchedPhotoKey)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::MatchedUserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::Distance)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofPhotoMatches::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-photo-match-proof")
+                            .from(ProofPhotoMatches::Table, ProofPhotoMatches::ProofId)
+                            .to(QuestProofs::Table, QuestProofs::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-photo-match-matched-proof")
+                            .from(ProofPhotoMatches::Table, ProofPhotoMatches::MatchedProofId)
+                            .to(QuestProofs::Table, QuestProofs::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-photo-matches-created")
+                    .table(ProofPhotoMatches::Table)
+                    .col(ProofPhotoMatches::CreatedAt)
+                    .col(ProofPhotoMatches::Ulid)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProofPhotoMatches::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ProofPhotoHashes::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuestProofs {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum ProofPhotoHashes {
+    Table,
+    Ulid,
+    ProofId,
+    UserId,
+    PhotoKey,
+    Hash,
+    Band0,
+    Band1,
+    Band2,
+    Band3,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProofPhotoMatches {
+    Table,
+    Ulid,
+    ProofId,
+    PhotoKey,
+    UserId,
+    MatchedProofId,
+    MatchedPhotoKey,
+    MatchedUserId,
+    Distance,
+    CreatedAt,
+}
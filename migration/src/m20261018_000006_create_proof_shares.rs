@@ -0,0 +1,63 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One share token per proof; revoking deletes the row and takes the page down
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProofShares::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProofShares::Token)
+                            .string_len(32)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofShares::ProofId)
+                            .string_len(26)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProofShares::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-proof-share-proof")
+                            .from(ProofShares::Table, ProofShares::ProofId)
+                            .to(QuestProofs::Table, QuestProofs::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProofShares::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuestProofs {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum ProofShares {
+    Table,
+    Token,
+    ProofId,
+    CreatedAt,
+}
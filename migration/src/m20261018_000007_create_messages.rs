@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Messages::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Messages::Ulid)
+                            .string_len(26)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Messages::LobbyId).string_len(26).not_null())
+                    .col(ColumnDef::new(Messages::AuthorId).string_len(26).not_null())
+                    .col(ColumnDef::new(Messages::Content).text().not_null())
+                    .col(ColumnDef::new(Messages::CreatedAt).date_time().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-messages-lobby_id")
+                            .from(Messages::Table, Messages::LobbyId)
+                            .to(Lobbies::Table, Lobbies::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-messages-author_id")
+                            .from(Messages::Table, Messages::AuthorId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Lobby history is always read newest first, page by page
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-messages-lobby-created")
+                    .table(Messages::Table)
+                    .col(Messages::LobbyId)
+                    .col(Messages::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Messages::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Lobbies {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Ulid,
+    LobbyId,
+    AuthorId,
+    Content,
+    CreatedAt,
+}
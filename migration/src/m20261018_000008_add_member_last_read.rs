@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // NULL means nothing read yet
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LobbyMembers::Table)
+                    .add_column(
+                        ColumnDef::new(LobbyMembers::LastReadMessageId)
+                            .string_len(26)
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Unread counts scan only the messages after the read marker of each lobby
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-messages-lobby-ulid")
+                    .table(Messages::Table)
+                    .col(Messages::LobbyId)
+                    .col(Messages::Ulid)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-messages-lobby-ulid")
+                    .table(Messages::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(LobbyMembers::Table)
+                    .drop_column(LobbyMembers::LastReadMessageId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LobbyMembers {
+    Table,
+    LastReadMessageId,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Ulid,
+    LobbyId,
+}
@@ -0,0 +1,198 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // SQLite adds one column per ALTER
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .add_column(ColumnDef::new(Messages::EditedAt).date_time().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // No foreign key, deleted messages stay as rows so a reply never loses its parent
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .add_column(ColumnDef::new(Messages::ReplyToId).string_len(26).null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Messages::Table)
+                    .add_column(ColumnDef::new(Messages::DeletedAt).date_time().null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // The primary key lets a user put each emoji on a message once
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageReactions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageReactions::MessageId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageReactions::UserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageReactions::Emoji)
+                            .string_len(32)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageReactions::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(MessageReactions::MessageId)
+                            .col(MessageReactions::UserId)
+                            .col(MessageReactions::Emoji),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reaction-message")
+                            .from(MessageReactions::Table, MessageReactions::MessageId)
+                            .to(Messages::Table, Messages::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-reaction-user")
+                            .from(MessageReactions::Table, MessageReactions::UserId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageMentions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageMentions::MessageId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageMentions::UserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageMentions::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(MessageMentions::MessageId)
+                            .col(MessageMentions::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-mention-message")
+                            .from(MessageMentions::Table, MessageMentions::MessageId)
+                            .to(Messages::Table, Messages::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-mention-user")
+                            .from(MessageMentions::Table, MessageMentions::UserId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // "Where was I mentioned", newest first
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-message-mentions-user")
+                    .table(MessageMentions::Table)
+                    .col(MessageMentions::UserId)
+                    .col(MessageMentions::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageMentions::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(MessageReactions::Table).to_owned())
+            .await?;
+
+        for column in [Messages::DeletedAt, Messages::ReplyToId, Messages::EditedAt] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(Messages::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum Messages {
+    Table,
+    Ulid,
+    EditedAt,
+    ReplyToId,
+    DeletedAt,
+}
+
+#[derive(DeriveIden)]
+enum MessageReactions {
+    Table,
+    MessageId,
+    UserId,
+    Emoji,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum MessageMentions {
+    Table,
+    MessageId,
+    UserId,
+    CreatedAt,
+}
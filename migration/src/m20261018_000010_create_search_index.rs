@@ -0,0 +1,107 @@
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// One FTS5 table per searchable table. The source row's ULID is stored unindexed next to the
+// text, results join back on it. `remove_diacritics` lets "sabac" find "Šabac".
+pub(crate) const UP: &[&str] = &[
+    "CREATE VIRTUAL TABLE messages_fts USING fts5(ulid UNINDEXED, content, tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE quest_proofs_fts USING fts5(ulid UNINDEXED, proof_text, tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE lobbies_fts USING fts5(ulid UNINDEXED, name, topic, tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE quests_fts USING fts5(ulid UNINDEXED, title, tokenize = 'unicode61 remove_diacritics 2')",
+    // Rows that existed before the index
+    "INSERT INTO messages_fts (ulid, content) SELECT ulid, content FROM messages WHERE deleted_at IS NULL",
+    "INSERT INTO quest_proofs_fts (ulid, proof_text) SELECT ulid, proof_text FROM quest_proofs WHERE proof_text IS NOT NULL",
+    "INSERT INTO lobbies_fts (ulid, name, topic) SELECT ulid, name, topic FROM lobbies",
+    "INSERT INTO quests_fts (ulid, title) SELECT ulid, title FROM quests",
+    // Deleted messages leave the index, their row stays behind only as a placeholder
+    "CREATE TRIGGER messages_fts_insert AFTER INSERT ON messages BEGIN
+        INSERT INTO messages_fts (ulid, content) VALUES (new.ulid, new.content);
+    END",
+    "CREATE TRIGGER messages_fts_update AFTER UPDATE OF content, deleted_at ON messages BEGIN
+        DELETE FROM messages_fts WHERE ulid = old.ulid;
+        INSERT INTO messages_fts (ulid, content) SELECT new.ulid, new.content WHERE new.deleted_at IS NULL;
+    END",
+    "CREATE TRIGGER messages_fts_delete AFTER DELETE ON messages BEGIN
+        DELETE FROM messages_fts WHERE ulid = old.ulid;
+    END",
+    "CREATE TRIGGER quest_proofs_fts_insert AFTER INSERT ON quest_proofs WHEN new.proof_text IS NOT NULL BEGIN
+        INSERT INTO quest_proofs_fts (ulid, proof_text) VALUES (new.ulid, new.proof_text);
+    END",
+    "CREATE TRIGGER quest_proofs_fts_update AFTER UPDATE OF proof_text ON quest_proofs BEGIN
+        DELETE FROM quest_proofs_fts WHERE ulid = old.ulid;
+        INSERT INTO quest_proofs_fts (ulid, proof_text) SELECT new.ulid, new.proof_text WHERE new.proof_text IS NOT NULL;
+    END",
+    "CREATE TRIGGER quest_proofs_fts_delete AFTER DELETE ON quest_proofs BEGIN
+        DELETE FROM quest_proofs_fts WHERE ulid = old.ulid;
+    END",
+    "CREATE TRIGGER lobbies_fts_insert AFTER INSERT ON lobbies BEGIN
+        INSERT INTO lobbies_fts (ulid, name, topic) VALUES (new.ulid, new.name, new.topic);
+    END",
+    "CREATE TRIGGER lobbies_fts_update AFTER UPDATE OF name, topic ON lobbies BEGIN
+        DELETE FROM lobbies_fts WHERE ulid = old.ulid;
+        INSERT INTO lobbies_fts (ulid, name, topic) VALUES (new.ulid, new.name, new.topic);
+    END",
+    "CREATE TRIGGER lobbies_fts_delete AFTER DELETE ON lobbies BEGIN
+        DELETE FROM lobbies_fts WHERE ulid = old.ulid;
+    END",
+    "CREATE TRIGGER quests_fts_insert AFTER INSERT ON quests BEGIN
+        INSERT INTO quests_fts (ulid, title) VALUES (new.ulid, new.title);
+    END",
+    "CREATE TRIGGER quests_fts_update AFTER UPDATE OF title ON quests BEGIN
+        DELETE FROM quests_fts WHERE ulid = old.ulid;
+        INSERT INTO quests_fts (ulid, title) VALUES (new.ulid, new.title);
+    END",
+    "CREATE TRIGGER quests_fts_delete AFTER DELETE ON quests BEGIN
+        DELETE FROM quests_fts WHERE ulid = old.ulid;
+    END",
+];
+
+pub(crate) const DOWN: &[&str] = &[
+    "DROP TRIGGER IF EXISTS messages_fts_insert",
+    "DROP TRIGGER IF EXISTS messages_fts_update",
+    "DROP TRIGGER IF EXISTS messages_fts_delete",
+    "DROP TRIGGER IF EXISTS quest_proofs_fts_insert",
+    "DROP TRIGGER IF EXISTS quest_proofs_fts_update",
+    "DROP TRIGGER IF EXISTS quest_proofs_fts_delete",
+    "DROP TRIGGER IF EXISTS lobbies_fts_insert",
+    "DROP TRIGGER IF EXISTS lobbies_fts_update",
+    "DROP TRIGGER IF EXISTS lobbies_fts_delete",
+    "DROP TRIGGER IF EXISTS quests_fts_insert",
+    "DROP TRIGGER IF EXISTS quests_fts_update",
+    "DROP TRIGGER IF EXISTS quests_fts_delete",
+    "DROP TABLE IF EXISTS messages_fts",
+    "DROP TABLE IF EXISTS quest_proofs_fts",
+    "DROP TABLE IF EXISTS lobbies_fts",
+    "DROP TABLE IF EXISTS quests_fts",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // FTS5 is SQLite only. Postgres would get a generated `tsvector` column with a GIN index
+    // per table in its own migration instead.
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        for sql in UP {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        for sql in DOWN {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+}
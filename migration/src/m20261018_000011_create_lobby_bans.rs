@@ -0,0 +1,79 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // One row per banned user, banning again replaces it. An expired ban is ignored and
+        // overwritten by the next one.
+        manager
+            .create_table(
+                Table::create()
+                    .table(LobbyBans::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(LobbyBans::LobbyId).string_len(26).not_null())
+                    .col(ColumnDef::new(LobbyBans::UserId).string_len(26).not_null())
+                    .col(
+                        ColumnDef::new(LobbyBans::BannedBy)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LobbyBans::Reason).string_len(200).null())
+                    // NULL bans for good
+                    .col(ColumnDef::new(LobbyBans::ExpiresAt).date_time().null())
+                    .col(ColumnDef::new(LobbyBans::CreatedAt).date_time().not_null())
+                    .primary_key(
+                        Index::create()
+                            .col(LobbyBans::LobbyId)
+                            .col(LobbyBans::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-lobby-ban-lobby")
+                            .from(LobbyBans::Table, LobbyBans::LobbyId)
+                            .to(Lobbies::Table, Lobbies::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-lobby-ban-user")
+                            .from(LobbyBans::Table, LobbyBans::UserId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LobbyBans::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum Lobbies {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum LobbyBans {
+    Table,
+    LobbyId,
+    UserId,
+    BannedBy,
+    Reason,
+    ExpiresAt,
+    CreatedAt,
+}
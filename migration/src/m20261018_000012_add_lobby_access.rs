@@ -0,0 +1,182 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Every existing lobby was open to anyone, so they stay public
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lobbies::Table)
+                    .add_column(
+                        ColumnDef::new(Lobbies::Visibility)
+                            .string_len(10)
+                            .not_null()
+                            .default("public"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(LobbyInvites::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LobbyInvites::Code)
+                            .string_len(16)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(LobbyInvites::LobbyId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LobbyInvites::CreatedBy)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    // NULL for no limit
+                    .col(ColumnDef::new(LobbyInvites::MaxUses).integer().null())
+                    .col(
+                        ColumnDef::new(LobbyInvites::Uses)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(LobbyInvites::ExpiresAt).date_time().null())
+                    .col(
+                        ColumnDef::new(LobbyInvites::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-lobby-invite-lobby")
+                            .from(LobbyInvites::Table, LobbyInvites::LobbyId)
+                            .to(Lobbies::Table, Lobbies::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-lobby-invites-lobby")
+                    .table(LobbyInvites::Table)
+                    .col(LobbyInvites::LobbyId)
+                    .to_owned(),
+            )
+            .await?;
+
+        // One pending request per user and lobby, approving or rejecting deletes it
+        manager
+            .create_table(
+                Table::create()
+                    .table(LobbyJoinRequests::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LobbyJoinRequests::LobbyId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LobbyJoinRequests::UserId)
+                            .string_len(26)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LobbyJoinRequests::Message)
+                            .string_len(200)
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(LobbyJoinRequests::CreatedAt)
+                            .date_time()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(LobbyJoinRequests::LobbyId)
+                            .col(LobbyJoinRequests::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-join-request-lobby")
+                            .from(LobbyJoinRequests::Table, LobbyJoinRequests::LobbyId)
+                            .to(Lobbies::Table, Lobbies::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-join-request-user")
+                            .from(LobbyJoinRequests::Table, LobbyJoinRequests::UserId)
+                            .to(Users::Table, Users::Ulid)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LobbyJoinRequests::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(LobbyInvites::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Lobbies::Table)
+                    .drop_column(Lobbies::Visibility)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Ulid,
+}
+
+#[derive(DeriveIden)]
+enum Lobbies {
+    Table,
+    Ulid,
+    Visibility,
+}
+
+#[derive(DeriveIden)]
+enum LobbyInvites {
+    Table,
+    Code,
+    LobbyId,
+    CreatedBy,
+    MaxUses,
+    Uses,
+    ExpiresAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum LobbyJoinRequests {
+    Table,
+    LobbyId,
+    UserId,
+    Message,
+    CreatedAt,
+}
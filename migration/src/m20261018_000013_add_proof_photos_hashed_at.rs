@@ -0,0 +1,88 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // NULL on a confirmed proof means its photos still have to be hashed
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .add_column(
+                        ColumnDef::new(QuestProofs::PhotosHashedAt)
+                            .date_time()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Proofs the handler already got to are done, the rest is left for the sweep
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(QuestProofs::Table)
+                    .value(
+                        QuestProofs::PhotosHashedAt,
+                        Expr::col(QuestProofs::UpdatedAt),
+                    )
+                    .and_where(
+                        Expr::col(QuestProofs::Ulid).in_subquery(
+                            Query::select()
+                                .column(ProofPhotoHashes::ProofId)
+                                .from(ProofPhotoHashes::Table)
+                                .to_owned(),
+                        ),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-quest-proofs-photos-hashed-at")
+                    .table(QuestProofs::Table)
+                    .col(QuestProofs::PhotosHashedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-quest-proofs-photos-hashed-at")
+                    .table(QuestProofs::Table)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(QuestProofs::Table)
+                    .drop_column(QuestProofs::PhotosHashedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum QuestProofs {
+    Table,
+    Ulid,
+    UpdatedAt,
+    PhotosHashedAt,
+}
+
+#[derive(DeriveIden)]
+enum ProofPhotoHashes {
+    Table,
+    ProofId,
+}
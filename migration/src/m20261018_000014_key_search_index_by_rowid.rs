@@ -0,0 +1,92 @@
+use sea_orm_migration::{prelude::*, sea_orm::DatabaseBackend};
+
+use crate::m20261018_000010_create_search_index as ulid_keyed;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+// External-content FTS5 tables: the text is read from the source table and every entry uses
+// the source row's rowid, so edits and deletes drop the old entry by rowid instead of
+// scanning the whole index for its ULID. Deleted messages and proofs without text stay out
+// of the index, which is why the triggers only send 'delete' for rows that were indexed and
+// 'rebuild' must never be run on these tables. A VACUUM can renumber the rowids, the index
+// has to be rebuilt through this migration's down and up after one.
+const UP: &[&str] = &[
+    "CREATE VIRTUAL TABLE messages_fts USING fts5(ulid UNINDEXED, content, content = 'messages', content_rowid = 'rowid', tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE quest_proofs_fts USING fts5(ulid UNINDEXED, proof_text, content = 'quest_proofs', content_rowid = 'rowid', tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE lobbies_fts USING fts5(ulid UNINDEXED, name, topic, content = 'lobbies', content_rowid = 'rowid', tokenize = 'unicode61 remove_diacritics 2')",
+    "CREATE VIRTUAL TABLE quests_fts USING fts5(ulid UNINDEXED, title, content = 'quests', content_rowid = 'rowid', tokenize = 'unicode61 remove_diacritics 2')",
+    "INSERT INTO messages_fts (rowid, ulid, content) SELECT rowid, ulid, content FROM messages WHERE deleted_at IS NULL",
+    "INSERT INTO quest_proofs_fts (rowid, ulid, proof_text) SELECT rowid, ulid, proof_text FROM quest_proofs WHERE proof_text IS NOT NULL",
+    "INSERT INTO lobbies_fts (rowid, ulid, name, topic) SELECT rowid, ulid, name, topic FROM lobbies",
+    "INSERT INTO quests_fts (rowid, ulid, title) SELECT rowid, ulid, title FROM quests",
+    "CREATE TRIGGER messages_fts_insert AFTER INSERT ON messages WHEN new.deleted_at IS NULL BEGIN
+        INSERT INTO messages_fts (rowid, ulid, content) VALUES (new.rowid, new.ulid, new.content);
+    END",
+    "CREATE TRIGGER messages_fts_update AFTER UPDATE OF content, deleted_at ON messages BEGIN
+        INSERT INTO messages_fts (messages_fts, rowid, ulid, content) SELECT 'delete', old.rowid, old.ulid, old.content WHERE old.deleted_at IS NULL;
+        INSERT INTO messages_fts (rowid, ulid, content) SELECT new.rowid, new.ulid, new.content WHERE new.deleted_at IS NULL;
+    END",
+    "CREATE TRIGGER messages_fts_delete AFTER DELETE ON messages WHEN old.deleted_at IS NULL BEGIN
+        INSERT INTO messages_fts (messages_fts, rowid, ulid, content) VALUES ('delete', old.rowid, old.ulid, old.content);
+    END",
+    "CREATE TRIGGER quest_proofs_fts_insert AFTER INSERT ON quest_proofs WHEN new.proof_text IS NOT NULL BEGIN
+        INSERT INTO quest_proofs_fts (rowid, ulid, proof_text) VALUES (new.rowid, new.ulid, new.proof_text);
+    END",
+    "CREATE TRIGGER quest_proofs_fts_update AFTER UPDATE OF proof_text ON quest_proofs BEGIN
+        INSERT INTO quest_proofs_fts (quest_proofs_fts, rowid, ulid, proof_text) SELECT 'delete', old.rowid, old.ulid, old.proof_text WHERE old.proof_text IS NOT NULL;
+        INSERT INTO quest_proofs_fts (rowid, ulid, proof_text) SELECT new.rowid, new.ulid, new.proof_text WHERE new.proof_text IS NOT NULL;
+    END",
+    "CREATE TRIGGER quest_proofs_fts_delete AFTER DELETE ON quest_proofs WHEN old.proof_text IS NOT NULL BEGIN
+        INSERT INTO quest_proofs_fts (quest_proofs_fts, rowid, ulid, proof_text) VALUES ('delete', old.rowid, old.ulid, old.proof_text);
+    END",
+    "CREATE TRIGGER lobbies_fts_insert AFTER INSERT ON lobbies BEGIN
+        INSERT INTO lobbies_fts (rowid, ulid, name, topic) VALUES (new.rowid, new.ulid, new.name, new.topic);
+    END",
+    "CREATE TRIGGER lobbies_fts_update AFTER UPDATE OF name, topic ON lobbies BEGIN
+        INSERT INTO lobbies_fts (lobbies_fts, rowid, ulid, name, topic) VALUES ('delete', old.rowid, old.ulid, old.name, old.topic);
+        INSERT INTO lobbies_fts (rowid, ulid, name, topic) VALUES (new.rowid, new.ulid, new.name, new.topic);
+    END",
+    "CREATE TRIGGER lobbies_fts_delete AFTER DELETE ON lobbies BEGIN
+        INSERT INTO lobbies_fts (lobbies_fts, rowid, ulid, name, topic) VALUES ('delete', old.rowid, old.ulid, old.name, old.topic);
+    END",
+    "CREATE TRIGGER quests_fts_insert AFTER INSERT ON quests BEGIN
+        INSERT INTO quests_fts (rowid, ulid, title) VALUES (new.rowid, new.ulid, new.title);
+    END",
+    "CREATE TRIGGER quests_fts_update AFTER UPDATE OF title ON quests BEGIN
+        INSERT INTO quests_fts (quests_fts, rowid, ulid, title) VALUES ('delete', old.rowid, old.ulid, old.title);
+        INSERT INTO quests_fts (rowid, ulid, title) VALUES (new.rowid, new.ulid, new.title);
+    END",
+    "CREATE TRIGGER quests_fts_delete AFTER DELETE ON quests BEGIN
+        INSERT INTO quests_fts (quests_fts, rowid, ulid, title) VALUES ('delete', old.rowid, old.ulid, old.title);
+    END",
+];
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    // SQLite only, like the index it replaces
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        for sql in ulid_keyed::DOWN.iter().chain(UP) {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+
+    // The triggers and tables carry the same names as before, dropping them works the same
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if manager.get_database_backend() != DatabaseBackend::Sqlite {
+            return Ok(());
+        }
+
+        let db = manager.get_connection();
+        for sql in ulid_keyed::DOWN.iter().chain(ulid_keyed::UP) {
+            db.execute_unprepared(sql).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,10 @@
+use std::str::FromStr;
+
+// Value of an environment variable, None when it is unset or doesn't parse
+pub fn env_opt<T: FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok().and_then(|v| v.parse().ok())
+}
+
+pub fn env_or<T: FromStr>(name: &str, default: T) -> T {
+    env_opt(name).unwrap_or(default)
+}
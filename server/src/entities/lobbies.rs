@@ -1,7 +1,7 @@
 use migration::IntoCondition;
 use sea_orm::{ActiveValue::Set, ExprTrait, entity::prelude::*};
 use serde::{Deserialize, Serialize};
-use shared::models::lobby_dto::LobbyDto;
+use shared::models::lobby_dto::{LobbyDto, LobbyVisibility};
 use specta::Type;
 use ulid::Ulid;
 
@@ -20,6 +20,8 @@ pub struct Model {
 
     pub owner_id: String,
 
+    pub visibility: LobbyVisibility,
+
     pub created_at: DateTimeUtc,
 }
 
@@ -38,6 +40,7 @@ impl ActiveModel {
             topic: Set(topic),
             owner_id: Set(owner_id),
             description: Set(description),
+            visibility: Set(LobbyVisibility::Public),
             created_at: Set(chrono::Utc::now()),
         }
     }
@@ -51,6 +54,7 @@ impl From<Model> for LobbyDto {
             topic: model.topic,
             description: model.description,
             owner_id: model.owner_id,
+            visibility: model.visibility,
             created_at: model.created_at,
         }
     }
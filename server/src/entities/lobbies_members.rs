@@ -22,6 +22,9 @@ pub struct Model {
     pub joined_at: DateTimeUtc,
 
     pub role: Role,
+
+    // Newest message the member has read, unread counts start after it
+    pub last_read_message_id: Option<String>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
@@ -33,6 +36,7 @@ impl ActiveModel {
             user_id: Set(user_id),
             joined_at: Set(chrono::Utc::now()),
             role: Set(Role::Member),
+            last_read_message_id: Set(None),
         }
     }
 }
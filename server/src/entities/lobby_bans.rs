@@ -0,0 +1,64 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use shared::models::lobby_dto::LobbyBanDto;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "lobby_bans")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub lobby_id: String,
+
+    #[sea_orm(primary_key)]
+    pub user_id: String,
+
+    pub banned_by: String,
+
+    pub reason: Option<String>,
+
+    // None bans for good
+    pub expires_at: Option<DateTimeUtc>,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_ban(
+        lobby_id: String,
+        user_id: String,
+        banned_by: String,
+        reason: Option<String>,
+        expires_at: Option<DateTimeUtc>,
+    ) -> Self {
+        Self {
+            lobby_id: Set(lobby_id),
+            user_id: Set(user_id),
+            banned_by: Set(banned_by),
+            reason: Set(reason),
+            expires_at: Set(expires_at),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
+
+impl Model {
+    pub fn is_active(&self) -> bool {
+        self.expires_at
+            .is_none_or(|expires_at| expires_at > chrono::Utc::now())
+    }
+}
+
+impl From<Model> for LobbyBanDto {
+    fn from(model: Model) -> Self {
+        Self {
+            lobby_id: model.lobby_id,
+            user_id: model.user_id,
+            banned_by: model.banned_by,
+            reason: model.reason,
+            expires_at: model.expires_at,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use shared::models::lobby_dto::LobbyInviteDto;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "lobby_invites")]
+pub struct Model {
+    // Short and random, it's what the invite link carries
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub code: String,
+
+    pub lobby_id: String,
+
+    pub created_by: String,
+
+    // None for no limit
+    pub max_uses: Option<u32>,
+
+    pub uses: u32,
+
+    pub expires_at: Option<DateTimeUtc>,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_invite(
+        code: String,
+        lobby_id: String,
+        created_by: String,
+        max_uses: Option<u32>,
+        expires_at: Option<DateTimeUtc>,
+    ) -> Self {
+        Self {
+            code: Set(code),
+            lobby_id: Set(lobby_id),
+            created_by: Set(created_by),
+            max_uses: Set(max_uses),
+            uses: Set(0),
+            expires_at: Set(expires_at),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
+
+impl Model {
+    pub fn is_usable(&self) -> bool {
+        let expired = self
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= chrono::Utc::now());
+        let used_up = self.max_uses.is_some_and(|max_uses| self.uses >= max_uses);
+        !expired && !used_up
+    }
+}
+
+impl From<Model> for LobbyInviteDto {
+    fn from(model: Model) -> Self {
+        Self {
+            code: model.code,
+            lobby_id: model.lobby_id,
+            created_by: model.created_by,
+            max_uses: model.max_uses,
+            uses: model.uses,
+            expires_at: model.expires_at,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "lobby_join_requests")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub lobby_id: String,
+
+    #[sea_orm(primary_key)]
+    pub user_id: String,
+
+    #[sea_orm(belongs_to, from = "user_id", to = "ulid")]
+    #[serde(skip)]
+    pub user: Option<super::users::Entity>,
+
+    pub message: Option<String>,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_request(lobby_id: String, user_id: String, message: Option<String>) -> Self {
+        Self {
+            lobby_id: Set(lobby_id),
+            user_id: Set(user_id),
+            message: Set(message),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "message_reactions")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub message_id: String,
+
+    #[sea_orm(primary_key)]
+    pub user_id: String,
+
+    #[sea_orm(primary_key)]
+    pub emoji: String,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_reaction(message_id: String, user_id: String, emoji: String) -> Self {
+        Self {
+            message_id: Set(message_id),
+            user_id: Set(user_id),
+            emoji: Set(emoji),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
@@ -1,8 +1,7 @@
 use sea_orm::{ActiveValue::Set, entity::prelude::*};
 use serde::{Deserialize, Serialize};
-use shared::models::message_dto::MessageDto;
+use shared::{models::message_dto::MessageDto, utils::monotonic_ulid::next_ulid};
 use specta::Type;
-use ulid::Ulid;
 
 #[sea_orm::model]
 #[derive(Clone, Debug, DeriveEntityModel, Serialize, Deserialize, Type)]
@@ -27,18 +26,55 @@ pub struct Model {
     pub content: String,
 
     pub created_at: DateTimeUtc,
+
+    pub edited_at: Option<DateTimeUtc>,
+
+    pub reply_to_id: Option<String>,
+
+    // Deleted messages keep their row so replies still point somewhere
+    pub deleted_at: Option<DateTimeUtc>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
 
+impl Model {
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    // Mentions and reactions live in their own tables and are filled in by `MessageService`
+    pub fn into_dto(self, author_name: String) -> MessageDto {
+        MessageDto {
+            deleted: self.is_deleted(),
+            ulid: self.ulid,
+            author_id: self.author_id,
+            author_name,
+            content: self.content,
+            created_at: self.created_at,
+            edited_at: self.edited_at,
+            reply_to_id: self.reply_to_id,
+            mentions: Vec::new(),
+            reactions: Vec::new(),
+        }
+    }
+}
+
 impl ActiveModel {
-    pub fn new_message(lobby_id: String, author_id: String, content: String) -> Self {
+    pub fn new_message(
+        lobby_id: String,
+        author_id: String,
+        content: String,
+        reply_to_id: Option<String>,
+    ) -> Self {
         Self {
-            ulid: Set(Ulid::new().to_string()),
+            ulid: Set(next_ulid().to_string()),
             lobby_id: Set(lobby_id),
             author_id: Set(author_id),
             content: Set(content),
             created_at: Set(chrono::Utc::now()),
+            edited_at: Set(None),
+            reply_to_id: Set(reply_to_id),
+            deleted_at: Set(None),
         }
     }
 }
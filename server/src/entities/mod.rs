@@ -4,14 +4,25 @@ pub mod arena_votes;
 pub mod journal_entries;
 pub mod lobbies;
 pub mod lobbies_members;
+pub mod lobby_bans;
+pub mod lobby_invites;
+pub mod lobby_join_requests;
+pub mod message_mentions;
+pub mod message_reactions;
 pub mod messages;
+pub mod moderation_flags;
+pub mod proof_photo_hashes;
+pub mod proof_photo_matches;
+pub mod proof_shares;
 pub mod quest_proof_beliefs;
 pub mod quest_proofs;
 pub mod quests;
 pub mod refresh_tokens;
+pub mod reports;
 pub mod shop_items;
 pub mod skill_prerequisites;
 pub mod skills;
+pub mod user_follows;
 pub mod user_inventory;
 pub mod user_quest_status;
 pub mod user_skills;
@@ -20,11 +31,22 @@ pub mod users;
 pub mod prelude {
     pub use super::lobbies::Entity as Lobby;
     pub use super::lobbies_members::Entity as LobbyMembers;
+    pub use super::lobby_bans::Entity as LobbyBan;
+    pub use super::lobby_invites::Entity as LobbyInvite;
+    pub use super::lobby_join_requests::Entity as LobbyJoinRequest;
+    pub use super::message_mentions::Entity as MessageMention;
+    pub use super::message_reactions::Entity as MessageReaction;
     pub use super::messages::Entity as Message;
+    pub use super::moderation_flags::Entity as ModerationFlag;
+    pub use super::proof_photo_hashes::Entity as ProofPhotoHash;
+    pub use super::proof_photo_matches::Entity as ProofPhotoMatch;
+    pub use super::proof_shares::Entity as ProofShare;
     pub use super::quest_proof_beliefs::Entity as QuestProofBeliefs;
     pub use super::quest_proofs::Entity as QuestProof;
     pub use super::quests::Entity as Quest;
     pub use super::refresh_tokens::Entity as RefreshToken;
+    pub use super::reports::Entity as Report;
+    pub use super::user_follows::Entity as UserFollow;
     pub use super::user_quest_status::Entity as UserQuestStatus;
     pub use super::users::Entity as User;
 }
@@ -0,0 +1,61 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use shared::models::report_dto::ReportTargetType;
+use ulid::Ulid;
+
+// Raised by the server itself rather than by users, so there is no reporter
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "moderation_flags")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub ulid: String,
+
+    pub target_type: ReportTargetType,
+    pub target_id: String,
+    pub reason: FlagReason,
+
+    #[sea_orm(column_type = "Text")]
+    pub details: Option<String>,
+
+    pub created_at: DateTimeUtc,
+    pub resolved_at: Option<DateTimeUtc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(32))")]
+pub enum FlagReason {
+    // Completion pushed over the threshold mostly by users the author believes in back
+    #[sea_orm(string_value = "mutual_belief_cluster")]
+    MutualBeliefCluster,
+    // Photo is a near duplicate of one from the author's earlier proofs
+    #[sea_orm(string_value = "reused_photo")]
+    ReusedPhoto,
+    // Photo is a near duplicate of one from another user's proof
+    #[sea_orm(string_value = "copied_photo")]
+    CopiedPhoto,
+    // Text matched a flag rule of the moderation wordlists
+    #[sea_orm(string_value = "offensive_text")]
+    OffensiveText,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_flag(
+        target_type: ReportTargetType,
+        target_id: String,
+        reason: FlagReason,
+        details: Option<String>,
+    ) -> Self {
+        Self {
+            ulid: Set(Ulid::new().to_string()),
+            target_type: Set(target_type),
+            target_id: Set(target_id),
+            reason: Set(reason),
+            details: Set(details),
+            created_at: Set(chrono::Utc::now()),
+            resolved_at: Set(None),
+        }
+    }
+}
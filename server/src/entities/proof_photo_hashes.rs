@@ -0,0 +1,54 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use crate::file_storage::perceptual_hash;
+
+// One perceptual hash per proof photo. The bands are the hash split in four so near
+// duplicates can be found through an index instead of scanning every hash.
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "proof_photo_hashes")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub ulid: String,
+
+    pub proof_id: String,
+    pub user_id: String,
+    pub photo_key: String,
+
+    // The u64 hash stored bit for bit, sqlite has no unsigned integers
+    pub hash: i64,
+    pub band0: i32,
+    pub band1: i32,
+    pub band2: i32,
+    pub band3: i32,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Model {
+    pub fn hash(&self) -> u64 {
+        self.hash as u64
+    }
+}
+
+impl ActiveModel {
+    pub fn new_hash(proof_id: String, user_id: String, photo_key: String, hash: u64) -> Self {
+        let [band0, band1, band2, band3] = perceptual_hash::bands(hash);
+        Self {
+            ulid: Set(Ulid::new().to_string()),
+            proof_id: Set(proof_id),
+            user_id: Set(user_id),
+            photo_key: Set(photo_key),
+            hash: Set(hash as i64),
+            band0: Set(band0.into()),
+            band1: Set(band1.into()),
+            band2: Set(band2.into()),
+            band3: Set(band3.into()),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+
+use super::proof_photo_hashes;
+
+// A newly uploaded photo (proof_id, photo_key) that is a near duplicate of an earlier one
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "proof_photo_matches")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub ulid: String,
+
+    pub proof_id: String,
+    pub photo_key: String,
+    pub user_id: String,
+
+    pub matched_proof_id: String,
+    pub matched_photo_key: String,
+    pub matched_user_id: String,
+
+    pub distance: i32,
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_match(
+        photo: &proof_photo_hashes::Model,
+        matched: &proof_photo_hashes::Model,
+        distance: u32,
+    ) -> Self {
+        Self {
+            ulid: Set(Ulid::new().to_string()),
+            proof_id: Set(photo.proof_id.clone()),
+            photo_key: Set(photo.photo_key.clone()),
+            user_id: Set(photo.user_id.clone()),
+            matched_proof_id: Set(matched.proof_id.clone()),
+            matched_photo_key: Set(matched.photo_key.clone()),
+            matched_user_id: Set(matched.user_id.clone()),
+            distance: Set(distance as i32),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
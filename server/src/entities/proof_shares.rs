@@ -0,0 +1,28 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "proof_shares")]
+pub struct Model {
+    // Random, unguessable; the only thing the public page URL contains
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub token: String,
+
+    #[sea_orm(unique)]
+    pub proof_id: String,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_share(token: String, proof_id: String) -> Self {
+        Self {
+            token: Set(token),
+            proof_id: Set(proof_id),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
@@ -20,6 +20,8 @@ pub struct Model {
     #[serde(skip)]
     pub proof: Option<super::quest_proofs::Entity>,
 
+    // Thousandths of a belief, see QuestProofBeliefsService::belief_weight
+    pub weight: u32,
     pub created_at: DateTimeUtc,
 }
 
@@ -1,5 +1,6 @@
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use shared::models::quest_proof_dto::ProofVisibility;
 use specta::Type;
 
 #[sea_orm::model]
@@ -24,6 +25,11 @@ pub struct Model {
 
     pub status: ProofStatus,
     pub beliefs_count: u32,
+    // Sum of belief weights in thousandths, used for the completion threshold
+    pub belief_weight: u32,
+    pub visibility: ProofVisibility,
+    // Set once the photos went through duplicate detection, see PhotoHashService::sweep
+    pub photos_hashed_at: Option<DateTimeUtc>,
     pub created_at: DateTimeUtc,
     pub updated_at: DateTimeUtc,
 }
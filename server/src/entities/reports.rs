@@ -0,0 +1,71 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+use shared::models::report_dto::{ReportDto, ReportReason, ReportStatus, ReportTargetType};
+use ulid::Ulid;
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "reports")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub ulid: String,
+
+    pub reporter_id: String,
+    #[sea_orm(belongs_to, from = "reporter_id", to = "ulid")]
+    #[serde(skip)]
+    pub reporter: HasOne<super::users::Entity>,
+
+    pub target_type: ReportTargetType,
+    pub target_id: String,
+    pub reason: ReportReason,
+
+    #[sea_orm(column_type = "Text")]
+    pub details: Option<String>,
+
+    pub status: ReportStatus,
+    pub created_at: DateTimeUtc,
+    pub resolved_at: Option<DateTimeUtc>,
+    pub resolved_by: Option<String>,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_report(
+        reporter_id: String,
+        target_type: ReportTargetType,
+        target_id: String,
+        reason: ReportReason,
+        details: Option<String>,
+    ) -> Self {
+        Self {
+            ulid: Set(Ulid::new().to_string()),
+            reporter_id: Set(reporter_id),
+            target_type: Set(target_type),
+            target_id: Set(target_id),
+            reason: Set(reason),
+            details: Set(details),
+            status: Set(ReportStatus::Open),
+            created_at: Set(chrono::Utc::now()),
+            resolved_at: Set(None),
+            resolved_by: Set(None),
+        }
+    }
+}
+
+impl From<Model> for ReportDto {
+    fn from(model: Model) -> Self {
+        Self {
+            ulid: model.ulid,
+            reporter_id: model.reporter_id,
+            target_type: model.target_type,
+            target_id: model.target_id,
+            reason: model.reason,
+            details: model.details,
+            status: model.status,
+            created_at: model.created_at,
+            resolved_at: model.resolved_at,
+            resolved_by: model.resolved_by,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use sea_orm::{ActiveValue::Set, entity::prelude::*};
+use serde::{Deserialize, Serialize};
+
+#[sea_orm::model]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
+#[sea_orm(table_name = "user_follows")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub follower_id: String,
+
+    #[sea_orm(primary_key)]
+    pub following_id: String,
+
+    pub created_at: DateTimeUtc,
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl ActiveModel {
+    pub fn new_follow(follower_id: String, following_id: String) -> Self {
+        Self {
+            follower_id: Set(follower_id),
+            following_id: Set(following_id),
+            created_at: Set(chrono::Utc::now()),
+        }
+    }
+}
@@ -26,6 +26,8 @@ pub struct Model {
     pub avatar_url: Option<String>,
     pub bio: Option<String>,
 
+    pub is_moderator: bool,
+
     #[sea_orm(has_many)]
     #[serde(skip)]
     pub refresh_token: HasMany<super::refresh_tokens::Entity>,
@@ -47,6 +49,8 @@ impl ActiveModel {
 
             avatar_url: Set(None),
             bio: Set(None),
+
+            is_moderator: Set(false),
         }
     }
 }
@@ -0,0 +1,82 @@
+pub mod photo_hash_handler;
+
+use sea_orm::prelude::Date;
+use tokio::sync::broadcast;
+
+const EVENT_BUS_CAPACITY: usize = 1024;
+
+// How a proof got its quest completed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionSource {
+    // The quest validates itself, confirming the upload is enough
+    Automatic,
+    // Weighted beliefs reached the quest's threshold
+    CommunityBelief,
+    // A moderator approved the proof
+    Moderation,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuestCompletion {
+    pub user_id: String,
+    pub quest_id: String,
+    pub proof_id: String,
+    // Day of the status row that changed
+    pub assigned_at: Date,
+    pub xp_reward: u32,
+    pub source: CompletionSource,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DomainEvent {
+    QuestCompleted(QuestCompletion),
+    // A belief-based completion lost its beliefs or its proof was rejected
+    QuestCompletionReverted(QuestCompletion),
+    // The proof's media is in storage and can be processed
+    ProofUploaded {
+        proof_id: String,
+    },
+    // Someone wrote `@username` for a lobby member, for notifications
+    UserMentioned {
+        user_id: String,
+        lobby_id: String,
+        message_id: String,
+    },
+}
+
+// Events are published only after the transaction that caused them commits, so subscribers
+// never see changes that were rolled back. Each subscriber gets every event; one that falls
+// more than EVENT_BUS_CAPACITY events behind skips the oldest ones.
+#[derive(Debug, Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DomainEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUS_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DomainEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(&self, event: DomainEvent) {
+        tracing::debug!(?event, "Publishing domain event");
+        // Having no subscribers is fine, e.g. in tests
+        let _ = self.sender.send(event);
+    }
+
+    pub fn publish_all(&self, events: impl IntoIterator<Item = DomainEvent>) {
+        for event in events {
+            self.publish(event);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use sea_orm::DatabaseConnection;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::events::{DomainEvent, EventBus};
+use crate::file_storage::s3_client::S3Manager;
+use crate::service::photo_hash_service::PhotoHashService;
+
+// Hashes the photos of every confirmed upload and flags near duplicates. Events only wake the
+// handler up, the work comes from the proofs still missing their hash mark.
+pub struct PhotoHashHandler;
+
+impl PhotoHashHandler {
+    pub fn spawn(db: DatabaseConnection, s3: S3Manager, bus: &EventBus) {
+        let mut events = bus.subscribe();
+
+        tokio::spawn(async move {
+            // Proofs confirmed while the server was down
+            Self::sweep(&db, &s3).await;
+
+            loop {
+                match events.recv().await {
+                    Ok(DomainEvent::ProofUploaded { .. }) => Self::sweep(&db, &s3).await,
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(
+                            skipped,
+                            "Photo hash handler lagged behind, sweeping for missed proofs"
+                        );
+                        Self::sweep(&db, &s3).await;
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    async fn sweep(db: &DatabaseConnection, s3: &S3Manager) {
+        if let Err(e) = PhotoHashService::sweep(db, s3).await {
+            tracing::error!(error = %e, "Photo hash handler failed");
+        }
+    }
+}
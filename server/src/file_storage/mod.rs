@@ -1 +1,3 @@
+pub mod perceptual_hash;
 pub mod s3_client;
+pub mod upload_policy;
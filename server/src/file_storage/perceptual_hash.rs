@@ -0,0 +1,67 @@
+use image::{DynamicImage, imageops::FilterType};
+
+// The image is shrunk to SAMPLE_SIZE², transformed with a DCT, and the lowest
+// HASH_SIZE² frequencies become the 64 hash bits.
+const SAMPLE_SIZE: usize = 32;
+const HASH_SIZE: usize = 8;
+
+// The hash is split into HASH_BANDS bands of 16 bits for the database lookup. Two hashes at
+// most HASH_BANDS - 1 bits apart always share a whole band, so the band index finds every
+// match within MATCH_DISTANCE.
+pub const HASH_BANDS: usize = 4;
+pub const MATCH_DISTANCE: u32 = 3;
+
+// DCT based perceptual hash (pHash). Survives re-encoding, resizing and small colour changes,
+// which a byte hash of the upload would not.
+pub fn perceptual_hash(bytes: &[u8]) -> Result<u64, image::ImageError> {
+    Ok(hash_image(&image::load_from_memory(bytes)?))
+}
+
+pub fn hash_image(image: &DynamicImage) -> u64 {
+    let pixels = image
+        .resize_exact(SAMPLE_SIZE as u32, SAMPLE_SIZE as u32, FilterType::Triangle)
+        .into_luma8();
+
+    let sample: Vec<f64> = pixels.pixels().map(|p| f64::from(p.0[0])).collect();
+
+    // Only the low frequency corner of the 2D DCT-II is needed
+    let cosines: Vec<f64> = (0..HASH_SIZE * SAMPLE_SIZE)
+        .map(|i| {
+            let (freq, pos) = (i / SAMPLE_SIZE, i % SAMPLE_SIZE);
+            ((2 * pos + 1) as f64 * freq as f64 * std::f64::consts::PI / (2 * SAMPLE_SIZE) as f64)
+                .cos()
+        })
+        .collect();
+
+    let mut coefficients = Vec::with_capacity(HASH_SIZE * HASH_SIZE);
+    for u in 0..HASH_SIZE {
+        for v in 0..HASH_SIZE {
+            let mut sum = 0.0;
+            for y in 0..SAMPLE_SIZE {
+                let row = cosines[u * SAMPLE_SIZE + y];
+                for x in 0..SAMPLE_SIZE {
+                    sum += sample[y * SAMPLE_SIZE + x] * row * cosines[v * SAMPLE_SIZE + x];
+                }
+            }
+            coefficients.push(sum);
+        }
+    }
+
+    // The DC term is the average brightness and would skew the median
+    let mut sorted: Vec<f64> = coefficients[1..].to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let median = sorted[sorted.len() / 2];
+
+    coefficients.iter().enumerate().fold(
+        0u64,
+        |hash, (i, c)| if *c > median { hash | (1 << i) } else { hash },
+    )
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+pub fn bands(hash: u64) -> [u16; HASH_BANDS] {
+    std::array::from_fn(|i| (hash >> (i * 16)) as u16)
+}
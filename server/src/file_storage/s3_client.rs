@@ -1,12 +1,41 @@
 use aws_config::Region;
+use aws_credential_types::{
+    Credentials,
+    provider::{ProvideCredentials, SharedCredentialsProvider},
+};
 use aws_sdk_s3::Client;
 use aws_sdk_s3::presigning::PresigningConfig;
-use std::time::Duration;
+use chrono::Utc;
+use shared::models::PresignedUpload;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::file_storage::upload_policy::{PostPolicy, UploadKind, UploadLimits, sign_post_policy};
+
+// Links embedded in public pages get scraped and cached by link previewers, keep them brief
+pub const SHORT_LIVED_URL_EXPIRES_IN: u64 = 15 * 60;
+
+// Above this many entries expired URLs are swept out on the next insert
+const URL_CACHE_SWEEP_THRESHOLD: usize = 10_000;
+
+struct CachedUrl {
+    url: String,
+    signed_at: Instant,
+}
+
+// Presigned download URLs keyed by object key and expiry
+type UrlCache = Arc<Mutex<HashMap<(String, u64), CachedUrl>>>;
 
 #[derive(Clone)]
 pub struct S3Manager {
     client: Client,
     bucket: String,
+    endpoint: String,
+    region: String,
+    credentials: Option<SharedCredentialsProvider>,
+    upload_limits: UploadLimits,
+    url_cache: UrlCache,
 }
 
 impl S3Manager {
@@ -14,8 +43,8 @@ impl S3Manager {
         let sdk_config = aws_config::load_from_env().await;
 
         let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
-            .endpoint_url(endpoint)
-            .region(Region::new(region))
+            .endpoint_url(endpoint.clone())
+            .region(Region::new(region.clone()))
             .force_path_style(true)
             .build();
 
@@ -24,26 +53,66 @@ impl S3Manager {
         Self {
             client,
             bucket: bucket_name,
+            endpoint,
+            region,
+            credentials: sdk_config.credentials_provider(),
+            upload_limits: UploadLimits::default(),
+            url_cache: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 
-    pub async fn get_upload_url(
+    pub fn with_upload_limits(mut self, upload_limits: UploadLimits) -> Self {
+        self.upload_limits = upload_limits;
+        self
+    }
+
+    // Fixed keys instead of the environment's, lets tests sign URLs without a real storage
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        let config = self
+            .client
+            .config()
+            .to_builder()
+            .credentials_provider(credentials.clone())
+            .build();
+        self.client = Client::from_conf(config);
+        self.credentials = Some(SharedCredentialsProvider::new(credentials));
+        self
+    }
+
+    pub fn upload_limits(&self) -> &UploadLimits {
+        &self.upload_limits
+    }
+
+    // Presigned POST instead of PUT: unlike a PUT signature, the policy lets the storage
+    // enforce the exact Content-Type and the size limit for `kind`.
+    pub async fn get_upload_form(
         &self,
         key: &str,
-        content_type: &str,
+        kind: UploadKind,
         expires_in: u64,
-    ) -> Result<String, Box<dyn std::error::Error>> {
-        let expires_in = Duration::from_secs(expires_in);
-        let presigned_request = self
-            .client
-            .put_object()
-            .bucket(&self.bucket)
-            .key(key)
-            .content_type(content_type)
-            .presigned(PresigningConfig::expires_in(expires_in)?)
+    ) -> Result<PresignedUpload, Box<dyn std::error::Error>> {
+        let credentials = self
+            .credentials
+            .as_ref()
+            .ok_or("No storage credentials configured")?
+            .provide_credentials()
             .await?;
 
-        Ok(presigned_request.uri().to_string())
+        let max_bytes = self.upload_limits.max_bytes(kind);
+        let policy = PostPolicy {
+            bucket: &self.bucket,
+            region: &self.region,
+            key,
+            content_type: kind.content_type(),
+            max_bytes,
+            expires_in,
+        };
+
+        Ok(PresignedUpload {
+            url: format!("{}/{}", self.endpoint.trim_end_matches('/'), self.bucket),
+            fields: sign_post_policy(&policy, &credentials, Utc::now()),
+            max_bytes,
+        })
     }
 
     pub async fn get_download_url(
@@ -63,6 +132,18 @@ impl S3Manager {
         Ok(presigned_request.uri().to_string())
     }
 
+    pub async fn get_file(&self, key: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+
+        Ok(object.body.collect().await?.into_bytes().to_vec())
+    }
+
     pub async fn delete_file(&self, key: &str) -> Result<(), Box<dyn std::error::Error>> {
         self.client
             .delete_object()
@@ -89,15 +170,91 @@ impl S3Manager {
         )
     }
 
-    pub async fn resolve_urls(&self, keys_json: Option<&serde_json::Value>) -> Vec<String> {
-        let mut urls = Vec::new();
-        if let Some(keys) = keys_json.and_then(|v| v.as_array()) {
-            for key in keys.iter().filter_map(|k| k.as_str()) {
-                if let Ok(url) = self.get_download_url(key, 3600).await {
-                    urls.push(url);
+    // Presigns every key at once, reusing cached URLs that still have at least half of their
+    // lifetime left so clients never receive a link that is about to expire.
+    pub async fn get_download_urls(
+        &self,
+        keys: &[String],
+        expires_in: u64,
+    ) -> HashMap<String, String> {
+        let now = Instant::now();
+        let fresh_for = Duration::from_secs(expires_in / 2);
+
+        let mut urls = HashMap::with_capacity(keys.len());
+        let mut missing = Vec::new();
+        {
+            let cache = self.url_cache.lock().unwrap();
+            for key in keys {
+                match cache.get(&(key.clone(), expires_in)) {
+                    Some(cached) if now.duration_since(cached.signed_at) < fresh_for => {
+                        urls.insert(key.clone(), cached.url.clone());
+                    }
+                    _ => missing.push(key.clone()),
                 }
             }
         }
+
+        if missing.is_empty() {
+            return urls;
+        }
+
+        let mut signed = Vec::with_capacity(missing.len());
+        for key in missing {
+            match self.get_download_url(&key, expires_in).await {
+                Ok(url) => signed.push((key, url)),
+                Err(e) => tracing::warn!(key = %key, "Failed to presign download url: {e}"),
+            }
+        }
+
+        let mut cache = self.url_cache.lock().unwrap();
+        if cache.len() > URL_CACHE_SWEEP_THRESHOLD {
+            cache.retain(|(_, expiry), cached| {
+                now.duration_since(cached.signed_at) < Duration::from_secs(*expiry / 2)
+            });
+        }
+        for (key, url) in signed {
+            cache.insert(
+                (key.clone(), expires_in),
+                CachedUrl {
+                    url: url.clone(),
+                    signed_at: now,
+                },
+            );
+            urls.insert(key, url);
+        }
+
         urls
     }
+
+    // Signed URL for content shown outside the app, e.g. on public share pages
+    pub async fn get_short_lived_url(&self, key: &str) -> Option<String> {
+        self.get_download_urls(&[key.to_owned()], SHORT_LIVED_URL_EXPIRES_IN)
+            .await
+            .remove(key)
+    }
+
+    pub fn media_keys(keys_json: Option<&serde_json::Value>) -> Vec<String> {
+        keys_json
+            .and_then(|v| v.as_array())
+            .map(|keys| {
+                keys.iter()
+                    .filter_map(|k| k.as_str().map(str::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    // Avatars and other single keys stored as is in a column
+    pub async fn resolve_url(&self, key: Option<&str>) -> Option<String> {
+        let key = key?;
+        self.get_download_urls(&[key.to_owned()], 3600)
+            .await
+            .remove(key)
+    }
+
+    pub async fn resolve_urls(&self, keys_json: Option<&serde_json::Value>) -> Vec<String> {
+        let keys = Self::media_keys(keys_json);
+        let mut urls = self.get_download_urls(&keys, 3600).await;
+        keys.iter().filter_map(|key| urls.remove(key)).collect()
+    }
 }
@@ -0,0 +1,147 @@
+use aws_credential_types::Credentials;
+use base64::{Engine, engine::general_purpose::STANDARD};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+
+use crate::config::env_or;
+
+const DEFAULT_PHOTO_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_VOICE_MAX_BYTES: u64 = 5 * 1024 * 1024;
+const DEFAULT_AVATAR_MAX_BYTES: u64 = 2 * 1024 * 1024;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadKind {
+    Photo,
+    Voice,
+    Avatar,
+}
+
+impl UploadKind {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Photo | Self::Avatar => "image/jpeg",
+            Self::Voice => "audio/ogg",
+        }
+    }
+}
+
+// Per-media upload size caps, enforced by the storage through the POST policy
+#[derive(Debug, Clone)]
+pub struct UploadLimits {
+    pub photo_max_bytes: u64,
+    pub voice_max_bytes: u64,
+    pub avatar_max_bytes: u64,
+}
+
+impl Default for UploadLimits {
+    fn default() -> Self {
+        Self {
+            photo_max_bytes: DEFAULT_PHOTO_MAX_BYTES,
+            voice_max_bytes: DEFAULT_VOICE_MAX_BYTES,
+            avatar_max_bytes: DEFAULT_AVATAR_MAX_BYTES,
+        }
+    }
+}
+
+impl UploadLimits {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        Self {
+            photo_max_bytes: env_or("UPLOAD_PHOTO_MAX_BYTES", defaults.photo_max_bytes),
+            voice_max_bytes: env_or("UPLOAD_VOICE_MAX_BYTES", defaults.voice_max_bytes),
+            avatar_max_bytes: env_or("UPLOAD_AVATAR_MAX_BYTES", defaults.avatar_max_bytes),
+        }
+    }
+
+    pub fn max_bytes(&self, kind: UploadKind) -> u64 {
+        match kind {
+            UploadKind::Photo => self.photo_max_bytes,
+            UploadKind::Voice => self.voice_max_bytes,
+            UploadKind::Avatar => self.avatar_max_bytes,
+        }
+    }
+}
+
+pub struct PostPolicy<'a> {
+    pub bucket: &'a str,
+    pub region: &'a str,
+    pub key: &'a str,
+    pub content_type: &'a str,
+    pub max_bytes: u64,
+    pub expires_in: u64,
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+// SigV4 signing key derivation, see "Derive a signing key" in the AWS docs
+pub fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac(format!("AWS4{secret}").as_bytes(), date.as_bytes());
+    let k_region = hmac(&k_date, region.as_bytes());
+    let k_service = hmac(&k_region, service.as_bytes());
+    hmac(&k_service, b"aws4_request")
+}
+
+// Builds the form fields of a SigV4-signed POST policy. The policy pins the exact key and
+// Content-Type and bounds the body size, so the storage itself rejects anything else.
+pub fn sign_post_policy(
+    policy: &PostPolicy<'_>,
+    credentials: &Credentials,
+    now: DateTime<Utc>,
+) -> BTreeMap<String, String> {
+    let date = now.format("%Y%m%d").to_string();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let credential = format!(
+        "{}/{}/{}/s3/aws4_request",
+        credentials.access_key_id(),
+        date,
+        policy.region
+    );
+    let expiration = (now + Duration::seconds(policy.expires_in as i64))
+        .format("%Y-%m-%dT%H:%M:%S.000Z")
+        .to_string();
+
+    let mut conditions = vec![
+        json!({ "bucket": policy.bucket }),
+        json!(["eq", "$key", policy.key]),
+        json!(["eq", "$Content-Type", policy.content_type]),
+        json!(["content-length-range", 1, policy.max_bytes]),
+        json!({ "x-amz-algorithm": "AWS4-HMAC-SHA256" }),
+        json!({ "x-amz-credential": credential }),
+        json!({ "x-amz-date": amz_date }),
+    ];
+    if let Some(token) = credentials.session_token() {
+        conditions.push(json!({ "x-amz-security-token": token }));
+    }
+
+    let document = json!({ "expiration": expiration, "conditions": conditions });
+    let encoded_policy = STANDARD.encode(document.to_string());
+
+    let key = signing_key(credentials.secret_access_key(), &date, policy.region, "s3");
+    let signature = to_hex(&hmac(&key, encoded_policy.as_bytes()));
+
+    let mut fields = BTreeMap::new();
+    fields.insert("key".to_owned(), policy.key.to_owned());
+    fields.insert("Content-Type".to_owned(), policy.content_type.to_owned());
+    fields.insert("x-amz-algorithm".to_owned(), "AWS4-HMAC-SHA256".to_owned());
+    fields.insert("x-amz-credential".to_owned(), credential);
+    fields.insert("x-amz-date".to_owned(), amz_date);
+    if let Some(token) = credentials.session_token() {
+        fields.insert("x-amz-security-token".to_owned(), token.to_owned());
+    }
+    fields.insert("policy".to_owned(), encoded_policy);
+    fields.insert("x-amz-signature".to_owned(), signature);
+    fields
+}
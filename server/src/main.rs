@@ -2,7 +2,7 @@ use aws_config::Region;
 use axum::Router;
 use migration::{Migrator, MigratorTrait};
 use sea_orm::{ConnectOptions, Database, DatabaseConnection};
-use shared::models::message_dto::LobbyEvent;
+use shared::{models::message_dto::LobbyEventFrame, moderation::TextFilter};
 use std::{collections::HashMap, env, net::SocketAddr, sync::Arc};
 use tokio::{
     net::TcpListener,
@@ -14,15 +14,31 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use crate::{
     entities::quests::seed_quests,
-    file_storage::s3_client::S3Manager,
+    events::{EventBus, photo_hash_handler::PhotoHashHandler},
+    file_storage::{s3_client::S3Manager, upload_policy::UploadLimits},
     routes::{
-        lobby_routes::lobby_router, quest_proof_routes::quest_proof_router,
-        refresh_token_routes::refresh_token_router, user_quest_status_routes::user_quest_router,
-        user_routes::public_user_router,
+        follow_routes::follow_router,
+        lobby_access_routes::lobby_access_router,
+        lobby_routes::lobby_router,
+        message_routes::{lobby_socket_router, message_router},
+        quest_proof_routes::quest_proof_router,
+        refresh_token_routes::refresh_token_router,
+        report_routes::report_router,
+        search_routes::search_router,
+        share_routes::{public_share_router, share_router},
+        user_quest_status_routes::user_quest_router,
+        user_routes::{public_user_router, user_router},
+    },
+    service::{
+        feed_ranking::FeedRankingConfig, moderation_service::ModerationConfig,
+        presence::PresenceRegistry, quest_proof_beliefs_service::BeliefConfig,
+        rate_limiter::RateLimiter, report_service::ReportConfig,
     },
 };
 
+pub mod config;
 pub mod entities;
+pub mod events;
 pub mod file_storage;
 pub mod middleware;
 pub mod routes;
@@ -32,8 +48,14 @@ pub mod service;
 pub struct AppState {
     pub connection: DatabaseConnection,
     pub s3_manager: S3Manager,
-    pub lobby_channels: Arc<Mutex<HashMap<String, broadcast::Sender<LobbyEvent>>>>, // TODO:
-                                                                                    // Implement chat
+    pub report_config: ReportConfig,
+    pub feed_ranking: FeedRankingConfig,
+    pub belief_config: BeliefConfig,
+    pub text_filter: TextFilter,
+    pub rate_limiter: RateLimiter,
+    pub presence: PresenceRegistry,
+    pub events: EventBus,
+    pub lobby_channels: Arc<Mutex<HashMap<String, broadcast::Sender<LobbyEventFrame>>>>,
 }
 
 #[tokio::main]
@@ -69,26 +91,46 @@ async fn main() -> Result<(), ()> {
 
     let region = Region::new(env::var("AWS_REGION").unwrap());
     let endpoint_url = env::var("AWS_ENDPOINT").unwrap();
-    let s3_manager =
-        S3Manager::new("speak-please".to_string(), endpoint_url, region.to_string()).await;
+    let s3_manager = S3Manager::new("speak-please".to_string(), endpoint_url, region.to_string())
+        .await
+        .with_upload_limits(UploadLimits::from_env());
+
+    let events = EventBus::new();
+    PhotoHashHandler::spawn(connection.clone(), s3_manager.clone(), &events);
 
     let lobby_channels = Arc::new(Mutex::new(HashMap::new()));
     let state = AppState {
         connection,
         s3_manager,
+        report_config: ReportConfig::from_env(),
+        feed_ranking: FeedRankingConfig::from_env(),
+        belief_config: BeliefConfig::from_env(),
+        text_filter: ModerationConfig::from_env().build_filter(),
+        rate_limiter: RateLimiter::new(),
+        presence: PresenceRegistry::new(),
+        events,
         lobby_channels,
     };
 
     // Public routes don't needs access keys
     let public_routes = Router::new()
         .merge(refresh_token_router())
-        .merge(public_user_router());
+        .merge(public_user_router())
+        .merge(public_share_router())
+        .merge(lobby_socket_router());
 
     // Private/Protected routes do needs access keys
     let protected_routes: Router<AppState> = Router::new()
         .merge(user_quest_router())
         .merge(quest_proof_router())
         .merge(lobby_router())
+        .merge(lobby_access_router())
+        .merge(message_router())
+        .merge(report_router())
+        .merge(search_router())
+        .merge(follow_router())
+        .merge(share_router())
+        .merge(user_router())
         .layer(axum::middleware::from_fn(
             middleware::jwt_verify_middleware::check_access_token, // Middleware for access key
                                                                    // checking
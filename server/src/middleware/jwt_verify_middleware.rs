@@ -1,24 +1,107 @@
-use axum::{extract::Request, http::StatusCode, middleware::Next, response::Response};
+use axum::{
+    extract::{FromRequestParts, Request},
+    http::{
+        HeaderMap, StatusCode,
+        header::{AUTHORIZATION, SEC_WEBSOCKET_PROTOCOL},
+        request::Parts,
+    },
+    middleware::Next,
+    response::Response,
+};
 use axum_extra::{
     TypedHeader,
     headers::{Authorization, authorization::Bearer},
 };
-use shared::utils::jwt::verify_access_token;
+use shared::{
+    errors::{AppError, AppResult, jwt_errors::JwtError},
+    utils::jwt::verify_access_token,
+};
+
+// Identity of the caller, inserted into request extensions by `check_access_token`
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub user_id: String,
+}
+
+impl AuthUser {
+    // Endpoints that still carry a user id in the path may only act for the caller
+    pub fn ensure_is(&self, user_id: &str) -> AppResult<()> {
+        if self.user_id == user_id {
+            Ok(())
+        } else {
+            Err(AppError::Custom(
+                "Cannot act on behalf of another user".into(),
+            ))
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for AuthUser
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AuthUser>()
+            .cloned()
+            .ok_or(AppError::Jwt(JwtError::InvalidToken))
+    }
+}
 
 pub async fn check_access_token(
     TypedHeader(auth): TypedHeader<Authorization<Bearer>>,
-    request: Request,
+    mut request: Request,
     next: Next,
 ) -> Result<Response, StatusCode> {
     let token = auth.token();
     let verify_result = verify_access_token(token);
 
     match verify_result {
-        Ok(_) => Ok(next.run(request).await),
+        Ok(claims) => {
+            request.extensions_mut().insert(AuthUser {
+                user_id: claims.user_id,
+            });
+            Ok(next.run(request).await)
+        }
         Err(_) => Err(StatusCode::UNAUTHORIZED),
     }
 }
 
+// First subprotocol a browser offers on the lobby socket, the token is the second one
+pub const SOCKET_AUTH_PROTOCOL: &str = "bearer";
+
+// WebSocket routes sit outside `check_access_token`, since browsers can only send the token
+// as a subprotocol. Native clients can still use the Authorization header.
+pub fn authenticate_socket(headers: &HeaderMap) -> Result<AuthUser, AppError> {
+    let from_header = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+
+    let from_protocol = headers
+        .get(SEC_WEBSOCKET_PROTOCOL)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            let mut protocols = value.split(',').map(str::trim);
+            match protocols.next() {
+                Some(SOCKET_AUTH_PROTOCOL) => protocols.next(),
+                _ => None,
+            }
+        });
+
+    let token = from_header
+        .or(from_protocol)
+        .ok_or(AppError::Jwt(JwtError::InvalidToken))?;
+    let claims = verify_access_token(token)?;
+
+    Ok(AuthUser {
+        user_id: claims.user_id,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,6 +115,7 @@ mod tests {
 
         Router::new()
             .route("/protected", test_route)
+            .route("/whoami", get(|user: AuthUser| async move { user.user_id }))
             .layer(middleware::from_fn(check_access_token))
     }
 
@@ -81,4 +165,53 @@ mod tests {
 
         response.assert_status(StatusCode::UNAUTHORIZED);
     }
+
+    #[tokio::test]
+    async fn test_auth_user_is_extracted_from_token() {
+        let app = create_app().await;
+        let server = TestServer::new(app).unwrap();
+
+        let token = create_access_token("user_123".to_string(), "test@example.com".to_string())
+            .expect("Cannot create a valid token");
+
+        let response = server
+            .get("/whoami")
+            .add_header(
+                axum::http::header::AUTHORIZATION,
+                format!("Bearer {}", token),
+            )
+            .await;
+
+        response.assert_status(StatusCode::OK);
+        assert_eq!(response.text(), "user_123");
+    }
+
+    #[test]
+    fn test_socket_token_from_subprotocol_or_header() {
+        let token = create_access_token("user_123".to_string(), "test@example.com".to_string())
+            .expect("Cannot create a valid token");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            format!("{SOCKET_AUTH_PROTOCOL}, {token}").parse().unwrap(),
+        );
+        assert_eq!(authenticate_socket(&headers).unwrap().user_id, "user_123");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, format!("Bearer {token}").parse().unwrap());
+        assert_eq!(authenticate_socket(&headers).unwrap().user_id, "user_123");
+    }
+
+    #[test]
+    fn test_socket_without_valid_token_is_rejected() {
+        assert!(authenticate_socket(&HeaderMap::new()).is_err());
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            "chat, invalid-token-string".parse().unwrap(),
+        );
+        assert!(authenticate_socket(&headers).is_err());
+    }
 }
@@ -0,0 +1,116 @@
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{get, post},
+};
+use shared::{
+    endpoints::{API, UserUlid, follow_endpoints::FollowEndpoints},
+    errors::{AppError, AppResult},
+    models::{
+        Page, PageQuery,
+        follow_dto::{FollowCountsResponse, FollowUserDto},
+    },
+    utils::ulid_validation::validate_ulid,
+};
+use validator::Validate;
+
+use crate::{
+    AppState, middleware::jwt_verify_middleware::AuthUser, service::follow_service::FollowService,
+};
+
+pub fn follow_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            FollowEndpoints::Follow(UserUlid::default()).template(),
+            post(follow_user).delete(unfollow_user),
+        )
+        .route(
+            FollowEndpoints::GetCounts(UserUlid::default()).template(),
+            get(get_follow_counts),
+        )
+        .route(
+            FollowEndpoints::GetFollowers(UserUlid::default()).template(),
+            get(get_followers),
+        )
+        .route(
+            FollowEndpoints::GetFollowing(UserUlid::default()).template(),
+            get(get_following),
+        )
+}
+
+async fn follow_user(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(target_id): Path<String>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&target_id)?;
+
+    FollowService::follow(&state.connection, &user.user_id, &target_id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+async fn unfollow_user(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(target_id): Path<String>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&target_id)?;
+
+    FollowService::unfollow(&state.connection, &user.user_id, &target_id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+async fn get_follow_counts(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(user_id): Path<String>,
+) -> AppResult<Json<FollowCountsResponse>> {
+    validate_ulid(&user_id)?;
+
+    let counts = FollowService::get_counts(&state.connection, &user_id, &user.user_id).await?;
+
+    Ok(Json(counts))
+}
+
+async fn get_followers(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Query(pagination): Query<PageQuery>,
+) -> AppResult<Json<Page<FollowUserDto>>> {
+    validate_ulid(&user_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let followers = FollowService::get_followers(
+        &state.connection,
+        &state.s3_manager,
+        &user_id,
+        pagination.limit.unwrap_or(20) as u64,
+        pagination.cursor.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(followers))
+}
+
+async fn get_following(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    Query(pagination): Query<PageQuery>,
+) -> AppResult<Json<Page<FollowUserDto>>> {
+    validate_ulid(&user_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let following = FollowService::get_following(
+        &state.connection,
+        &state.s3_manager,
+        &user_id,
+        pagination.limit.unwrap_or(20) as u64,
+        pagination.cursor.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(following))
+}
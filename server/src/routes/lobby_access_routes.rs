@@ -0,0 +1,223 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{delete, get, post},
+};
+use shared::{
+    endpoints::{API, LobbyUlid, UserUlid, lobby_endpoints::LobbyEndpoints},
+    errors::{AppError, AppResult},
+    models::{
+        lobby_dto::{
+            CreateInviteRequest, CreateJoinRequest, InvitePreview, JoinRequestDto, LobbyDto,
+            LobbyInviteDto, LobbyMemberDto,
+        },
+        message_dto::LobbyEvent,
+    },
+    utils::ulid_validation::validate_ulid,
+};
+use validator::Validate;
+
+use crate::AppState;
+use crate::middleware::jwt_verify_middleware::AuthUser;
+use crate::routes::message_routes::broadcast_event;
+use crate::service::lobby_access_service::LobbyAccessService;
+use crate::service::lobby_member_service::LobbyMemberService;
+
+pub fn lobby_access_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            LobbyEndpoints::CreateInvite(LobbyUlid::default()).template(),
+            post(create_invite).get(get_invites),
+        )
+        .route(
+            LobbyEndpoints::RevokeInvite(LobbyUlid::default(), String::new()).template(),
+            delete(revoke_invite),
+        )
+        .route(
+            LobbyEndpoints::GetInvite(String::new()).template(),
+            get(get_invite),
+        )
+        .route(
+            LobbyEndpoints::AcceptInvite(String::new()).template(),
+            post(accept_invite),
+        )
+        .route(
+            LobbyEndpoints::RequestToJoin(LobbyUlid::default()).template(),
+            post(request_to_join).get(get_join_requests),
+        )
+        .route(
+            LobbyEndpoints::ApproveJoinRequest(LobbyUlid::default(), UserUlid::default())
+                .template(),
+            post(approve_join_request),
+        )
+        .route(
+            LobbyEndpoints::DeleteJoinRequest(LobbyUlid::default(), UserUlid::default()).template(),
+            delete(delete_join_request),
+        )
+}
+
+async fn create_invite(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Json(payload): Json<CreateInviteRequest>,
+) -> AppResult<Json<LobbyInviteDto>> {
+    validate_ulid(&lobby_id)?;
+    payload.validate().map_err(AppError::Validation)?;
+
+    let invite = LobbyAccessService::create_invite(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        payload
+            .expires_in_hours
+            .map(|hours| chrono::Duration::hours(hours.into())),
+        payload.max_uses,
+    )
+    .await?;
+
+    Ok(Json(LobbyInviteDto::from(invite)))
+}
+
+async fn get_invites(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+) -> AppResult<Json<Vec<LobbyInviteDto>>> {
+    validate_ulid(&lobby_id)?;
+
+    let invites =
+        LobbyAccessService::get_invites(&state.connection, &lobby_id, &user.user_id).await?;
+
+    Ok(Json(
+        invites.into_iter().map(LobbyInviteDto::from).collect(),
+    ))
+}
+
+async fn revoke_invite(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, code)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+
+    LobbyAccessService::revoke_invite(&state.connection, &lobby_id, &user.user_id, &code).await?;
+
+    Ok(StatusCode::OK)
+}
+
+async fn get_invite(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> AppResult<Json<InvitePreview>> {
+    let (invite, lobby) = LobbyAccessService::find_invite(&state.connection, &code).await?;
+    let count = LobbyMemberService::get_lobby_members_count(&state.connection, &lobby.ulid).await?;
+
+    Ok(Json(InvitePreview {
+        code: invite.code,
+        lobby: LobbyDto::from(lobby),
+        members_count: count as u32,
+    }))
+}
+
+async fn accept_invite(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(code): Path<String>,
+) -> AppResult<Json<LobbyMemberDto>> {
+    let membership =
+        LobbyAccessService::accept_invite(&state.connection, &code, &user.user_id).await?;
+
+    broadcast_event(
+        &state,
+        &membership.lobby_id,
+        LobbyEvent::MemberJoined {
+            user_id: membership.user_id.clone(),
+        },
+    )
+    .await;
+
+    Ok(Json(LobbyMemberDto::from(membership)))
+}
+
+async fn request_to_join(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Json(payload): Json<CreateJoinRequest>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    payload.validate().map_err(AppError::Validation)?;
+
+    LobbyAccessService::request_to_join(
+        &state.connection,
+        &state.text_filter,
+        &lobby_id,
+        &user.user_id,
+        payload.message,
+    )
+    .await?;
+
+    Ok(StatusCode::CREATED)
+}
+
+async fn get_join_requests(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+) -> AppResult<Json<Vec<JoinRequestDto>>> {
+    validate_ulid(&lobby_id)?;
+
+    let requests = LobbyAccessService::get_join_requests(
+        &state.connection,
+        &state.s3_manager,
+        &lobby_id,
+        &user.user_id,
+    )
+    .await?;
+
+    Ok(Json(requests))
+}
+
+async fn approve_join_request(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, user_id)): Path<(String, String)>,
+) -> AppResult<Json<LobbyMemberDto>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&user_id)?;
+
+    let membership = LobbyAccessService::approve_join_request(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &user_id,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MemberJoined {
+            user_id: membership.user_id.clone(),
+        },
+    )
+    .await;
+
+    Ok(Json(LobbyMemberDto::from(membership)))
+}
+
+async fn delete_join_request(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, user_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&user_id)?;
+
+    LobbyAccessService::delete_join_request(&state.connection, &lobby_id, &user.user_id, &user_id)
+        .await?;
+
+    Ok(StatusCode::OK)
+}
@@ -1,22 +1,35 @@
 use axum::{
     Json, Router,
-    extract::{Path, State},
-    routing::{get, post},
+    extract::{Path, Query, State},
+    http::StatusCode,
+    routing::{delete, get, post, put},
 };
 use shared::{
-    endpoints::{API, LobbyUlid, UserUlid, lobby_endpoints::LobbyEndpoints},
+    endpoints::{
+        API, LobbyUlid, UserUlid,
+        lobby_endpoints::{DiscoverQuery, LobbyDetailsQuery, LobbyEndpoints},
+    },
     errors::{AppError, AppResult},
-    models::lobby_dto::{
-        CreateLobbyRequest, LobbyDetailsResponse, LobbyDto, LobbyFeedItem, LobbyFeedResponse,
-        LobbyMemberDto,
+    models::{
+        Page,
+        lobby_dto::{
+            BanMemberRequest, CreateLobbyRequest, DiscoveredLobby, LobbyBanDto,
+            LobbyDetailsResponse, LobbyDto, LobbyFeedItem, LobbyFeedResponse, LobbyMemberDto,
+            SetRoleRequest, SetVisibilityRequest, TopicCount, TransferOwnershipRequest,
+        },
+        message_dto::{LobbyEvent, LobbyUserDto, MemberBan},
     },
     utils::ulid_validation::validate_ulid,
 };
 use validator::Validate;
 
 use crate::AppState;
+use crate::middleware::jwt_verify_middleware::AuthUser;
+use crate::routes::message_routes::broadcast_event;
+use crate::service::lobby_discovery_service::LobbyDiscoveryService;
 use crate::service::lobby_member_service::LobbyMemberService;
 use crate::service::lobby_service::LobbyService;
+use crate::service::message_service::MessageService;
 
 pub fn lobby_router() -> Router<AppState> {
     Router::new()
@@ -25,6 +38,8 @@ pub fn lobby_router() -> Router<AppState> {
             LobbyEndpoints::GetAll(UserUlid::default()).template(),
             get(get_lobby_feed),
         )
+        .route(LobbyEndpoints::Discover.template(), get(discover_lobbies))
+        .route(LobbyEndpoints::GetTopics.template(), get(get_lobby_topics))
         .route(
             LobbyEndpoints::GetDetails(LobbyUlid::default()).template(),
             get(get_lobby_details),
@@ -37,21 +52,52 @@ pub fn lobby_router() -> Router<AppState> {
             LobbyEndpoints::GetMembersCount(LobbyUlid::default()).template(),
             get(get_lobby_members_count),
         )
+        .route(
+            LobbyEndpoints::GetOnlineMembers(LobbyUlid::default()).template(),
+            get(get_online_members),
+        )
+        .route(
+            LobbyEndpoints::Leave(LobbyUlid::default()).template(),
+            post(leave_lobby),
+        )
+        .route(
+            LobbyEndpoints::KickMember(LobbyUlid::default(), UserUlid::default()).template(),
+            delete(kick_member),
+        )
+        .route(
+            LobbyEndpoints::SetRole(LobbyUlid::default(), UserUlid::default()).template(),
+            put(set_member_role),
+        )
+        .route(
+            LobbyEndpoints::Ban(LobbyUlid::default(), UserUlid::default()).template(),
+            put(ban_member).delete(unban_member),
+        )
+        .route(
+            LobbyEndpoints::TransferOwnership(LobbyUlid::default()).template(),
+            post(transfer_ownership),
+        )
+        .route(
+            LobbyEndpoints::SetVisibility(LobbyUlid::default()).template(),
+            put(set_visibility),
+        )
 }
 
 async fn create_lobby(
     State(state): State<AppState>,
+    user: AuthUser,
     Json(payload): Json<CreateLobbyRequest>,
 ) -> AppResult<Json<LobbyDto>> {
     payload.validate().map_err(AppError::Validation)?;
-    validate_ulid(&payload.owner_id)?;
 
+    // Lobbies always belong to whoever creates them
     let lobby_model = LobbyService::create_lobby(
         &state.connection,
-        payload.owner_id,
+        &state.text_filter,
+        user.user_id,
         payload.name,
         payload.topic,
         payload.description,
+        payload.visibility,
     )
     .await?;
 
@@ -68,14 +114,25 @@ async fn get_all_lobbies(State(state): State<AppState>) -> AppResult<Json<Vec<Lo
 
 async fn get_lobby_feed(
     State(state): State<AppState>,
+    user: AuthUser,
     Path(user_id): Path<String>,
 ) -> AppResult<Json<LobbyFeedResponse>> {
+    validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
+
     let data =
         LobbyService::get_lobbies_with_membership_status(&state.connection, &user_id).await?;
+    let unread = MessageService::unread_counts(
+        &state.connection,
+        &user_id,
+        state.report_config.hide_threshold,
+    )
+    .await?;
 
     let lobby_items = data
         .into_iter()
         .map(|(model, is_member)| LobbyFeedItem {
+            unread_count: unread.get(&model.ulid).copied().unwrap_or(0) as u32,
             lobby: LobbyDto::from(model),
             is_member,
         })
@@ -84,13 +141,54 @@ async fn get_lobby_feed(
     Ok(Json(LobbyFeedResponse { items: lobby_items }))
 }
 
+async fn discover_lobbies(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<DiscoverQuery>,
+) -> AppResult<Json<Page<DiscoveredLobby>>> {
+    query.validate().map_err(AppError::Validation)?;
+
+    let page = LobbyDiscoveryService::discover(
+        &state.connection,
+        &user.user_id,
+        &query,
+        query.limit.unwrap_or(20) as u64,
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(page))
+}
+
+async fn get_lobby_topics(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<Vec<TopicCount>>> {
+    let topics = LobbyDiscoveryService::get_topics(
+        &state.connection,
+        &user.user_id,
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(topics))
+}
+
 async fn get_lobby_details(
     State(state): State<AppState>,
+    user: AuthUser,
     Path(lobby_id): Path<String>,
+    Query(query): Query<LobbyDetailsQuery>,
 ) -> AppResult<Json<LobbyDetailsResponse>> {
     validate_ulid(&lobby_id)?;
 
-    let lobby = LobbyService::find_by_id(&state.connection, &lobby_id).await?;
+    let lobby = LobbyService::find_visible(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        query.invite.as_deref(),
+    )
+    .await?;
     let count = LobbyMemberService::get_lobby_members_count(&state.connection, &lobby_id).await?;
 
     Ok(Json(LobbyDetailsResponse {
@@ -101,12 +199,24 @@ async fn get_lobby_details(
 
 async fn join_lobby(
     State(state): State<AppState>,
+    user: AuthUser,
     Path((lobby_id, user_id)): Path<(String, String)>,
 ) -> AppResult<Json<LobbyMemberDto>> {
     validate_ulid(&lobby_id)?;
     validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
 
-    let membership = LobbyMemberService::join_lobby(&state.connection, lobby_id, user_id).await?;
+    let membership =
+        LobbyMemberService::join_lobby(&state.connection, lobby_id.clone(), user_id).await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MemberJoined {
+            user_id: membership.user_id.clone(),
+        },
+    )
+    .await;
 
     Ok(Json(LobbyMemberDto::from(membership)))
 }
@@ -121,3 +231,196 @@ async fn get_lobby_members_count(
 
     Ok(Json(count as u32))
 }
+
+async fn get_online_members(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+) -> AppResult<Json<Vec<LobbyUserDto>>> {
+    validate_ulid(&lobby_id)?;
+
+    let members = LobbyMemberService::get_online_members(
+        &state.connection,
+        &state.s3_manager,
+        &state.presence,
+        &lobby_id,
+        &user.user_id,
+    )
+    .await?;
+
+    Ok(Json(members))
+}
+
+async fn leave_lobby(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+
+    LobbyMemberService::leave_lobby(&state.connection, &lobby_id, &user.user_id).await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MemberLeft {
+            user_id: user.user_id,
+        },
+    )
+    .await;
+
+    Ok(StatusCode::OK)
+}
+
+async fn kick_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, member_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&member_id)?;
+
+    LobbyMemberService::kick_member(&state.connection, &lobby_id, &user.user_id, &member_id)
+        .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MemberKicked {
+            user_id: member_id,
+            by: user.user_id,
+        },
+    )
+    .await;
+
+    Ok(StatusCode::OK)
+}
+
+async fn set_member_role(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, member_id)): Path<(String, String)>,
+    Json(payload): Json<SetRoleRequest>,
+) -> AppResult<Json<LobbyMemberDto>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&member_id)?;
+
+    let membership = LobbyMemberService::set_role(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &member_id,
+        payload.role,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::RoleChanged {
+            user_id: member_id,
+            role: membership.role.clone(),
+        },
+    )
+    .await;
+
+    Ok(Json(LobbyMemberDto::from(membership)))
+}
+
+async fn ban_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, target_id)): Path<(String, String)>,
+    Json(payload): Json<BanMemberRequest>,
+) -> AppResult<Json<LobbyBanDto>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&target_id)?;
+    payload.validate().map_err(AppError::Validation)?;
+
+    let ban = LobbyMemberService::ban_member(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &target_id,
+        payload
+            .duration_hours
+            .map(|hours| chrono::Duration::hours(hours.into())),
+        payload.reason,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MemberBanned(MemberBan {
+            user_id: target_id,
+            by: user.user_id,
+            expires_at: ban.expires_at,
+        }),
+    )
+    .await;
+
+    Ok(Json(LobbyBanDto::from(ban)))
+}
+
+async fn unban_member(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, target_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&target_id)?;
+
+    LobbyMemberService::unban_member(&state.connection, &lobby_id, &user.user_id, &target_id)
+        .await?;
+
+    Ok(StatusCode::OK)
+}
+
+async fn transfer_ownership(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Json(payload): Json<TransferOwnershipRequest>,
+) -> AppResult<Json<LobbyDto>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&payload.user_id)?;
+
+    let lobby = LobbyMemberService::transfer_ownership(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &payload.user_id,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::OwnerChanged {
+            owner_id: payload.user_id,
+        },
+    )
+    .await;
+
+    Ok(Json(LobbyDto::from(lobby)))
+}
+
+async fn set_visibility(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Json(payload): Json<SetVisibilityRequest>,
+) -> AppResult<Json<LobbyDto>> {
+    validate_ulid(&lobby_id)?;
+
+    let lobby = LobbyService::set_visibility(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        payload.visibility,
+    )
+    .await?;
+
+    Ok(Json(LobbyDto::from(lobby)))
+}
@@ -1,59 +1,572 @@
 use axum::{
+    Json, Router,
     extract::{
-        Path, State, WebSocketUpgrade,
+        Path, Query, State, WebSocketUpgrade,
         ws::{Message, WebSocket},
     },
-    response::IntoResponse,
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    routing::{get, patch, post, put},
 };
-use futures_util::{SinkExt, StreamExt};
-use tokio::sync::broadcast;
+use futures_util::{SinkExt, StreamExt, stream::SplitSink};
+use serde::Serialize;
+use shared::{
+    endpoints::{
+        API, LobbyUlid,
+        message_endpoints::{
+            EditMessageRequest, MarkReadRequest, MessageEndpoints, SendMessageRequest, SocketQuery,
+            SyncQuery,
+        },
+    },
+    errors::{AppError, AppResult},
+    models::{
+        Page, PageQuery,
+        message_dto::{
+            CommandReply, LobbyCommand, LobbyEvent, LobbyEventFrame, MessageDto, ReactionChange,
+        },
+    },
+    utils::ulid_validation::validate_ulid,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+use validator::Validate;
 
-use crate::AppState;
+use crate::{
+    AppState,
+    middleware::jwt_verify_middleware::{AuthUser, SOCKET_AUTH_PROTOCOL, authenticate_socket},
+    service::{
+        lobby_member_service::LobbyMemberService, message_service::MessageService,
+        user_service::UserService,
+    },
+};
 
-pub async fn lobby_chat_ws(
+// A socket that falls this many events behind gets a Resync notice instead of the events
+const LOBBY_CHANNEL_CAPACITY: usize = 100;
+// Messages replayed on reconnect before the client is told to sync the rest itself
+const REPLAY_LIMIT: u64 = 100;
+
+pub fn message_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            MessageEndpoints::GetLobbyMessages(LobbyUlid::default()).template(),
+            get(get_lobby_messages).post(send_message),
+        )
+        .route(
+            MessageEndpoints::EditMessage(LobbyUlid::default(), String::new()).template(),
+            patch(edit_message).delete(delete_message),
+        )
+        .route(
+            MessageEndpoints::AddReaction(LobbyUlid::default(), String::new(), String::new())
+                .template(),
+            put(add_reaction).delete(remove_reaction),
+        )
+        .route(
+            MessageEndpoints::SyncMessages(LobbyUlid::default()).template(),
+            get(sync_messages),
+        )
+        .route(
+            MessageEndpoints::MarkRead(LobbyUlid::default()).template(),
+            post(mark_read),
+        )
+}
+
+async fn get_lobby_messages(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Query(pagination): Query<PageQuery>,
+) -> AppResult<Json<Page<MessageDto>>> {
+    validate_ulid(&lobby_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let messages = MessageService::get_lobby_messages(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        pagination.limit.unwrap_or(50) as u64,
+        pagination.cursor.as_deref(),
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(messages))
+}
+
+async fn sync_messages(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Query(query): Query<SyncQuery>,
+) -> AppResult<Json<Page<MessageDto>>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&query.since)?;
+    query.validate().map_err(AppError::Validation)?;
+
+    let messages = MessageService::get_messages_since(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &query.since,
+        query.limit.unwrap_or(50) as u64,
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(messages))
+}
+
+async fn send_message(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(lobby_id): Path<String>,
+    Json(payload): Json<SendMessageRequest>,
+) -> AppResult<Json<MessageDto>> {
+    validate_ulid(&lobby_id)?;
+    payload.validate().map_err(AppError::Validation)?;
+
+    if let Some(reply_to) = &payload.reply_to {
+        validate_ulid(reply_to)?;
+    }
+
+    let message = MessageService::send_message(
+        &state.connection,
+        &state.events,
+        &state.text_filter,
+        lobby_id.clone(),
+        user.user_id,
+        payload.content,
+        payload.reply_to,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MessageCreated(message.clone()),
+    )
+    .await;
+
+    Ok(Json(message))
+}
+
+async fn edit_message(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, message_id)): Path<(String, String)>,
+    Json(payload): Json<EditMessageRequest>,
+) -> AppResult<Json<MessageDto>> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&message_id)?;
+    payload.validate().map_err(AppError::Validation)?;
+
+    let message = MessageService::edit_message(
+        &state.connection,
+        &state.events,
+        &state.text_filter,
+        &lobby_id,
+        &message_id,
+        &user.user_id,
+        payload.content,
+    )
+    .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MessageEdited(message.clone()),
+    )
+    .await;
+
+    Ok(Json(message))
+}
+
+async fn delete_message(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, message_id)): Path<(String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&message_id)?;
+
+    MessageService::delete_message(&state.connection, &lobby_id, &message_id, &user.user_id)
+        .await?;
+
+    broadcast_event(
+        &state,
+        &lobby_id,
+        LobbyEvent::MessageDeleted { id: message_id },
+    )
+    .await;
+
+    Ok(StatusCode::OK)
+}
+
+async fn add_reaction(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, message_id, emoji)): Path<(String, String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&message_id)?;
+
+    let added = MessageService::add_reaction(
+        &state.connection,
+        &lobby_id,
+        &message_id,
+        &user.user_id,
+        &emoji,
+    )
+    .await?;
+
+    if added {
+        let change = ReactionChange {
+            message_id,
+            user_id: user.user_id,
+            emoji,
+        };
+        broadcast_event(&state, &lobby_id, LobbyEvent::ReactionAdded(change)).await;
+    }
+
+    Ok(StatusCode::OK)
+}
+
+async fn remove_reaction(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path((lobby_id, message_id, emoji)): Path<(String, String, String)>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&message_id)?;
+
+    let removed = MessageService::remove_reaction(
+        &state.connection,
+        &lobby_id,
+        &message_id,
+        &user.user_id,
+        &emoji,
+    )
+    .await?;
+
+    if removed {
+        let change = ReactionChange {
+            message_id,
+            user_id: user.user_id,
+            emoji,
+        };
+        broadcast_event(&state, &lobby_id, LobbyEvent::ReactionRemoved(change)).await;
+    }
+
+    Ok(StatusCode::OK)
+}
+
+async fn mark_read(
+    State(state): State<AppState>,
+    user: AuthUser,
     Path(lobby_id): Path<String>,
+    Json(payload): Json<MarkReadRequest>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&lobby_id)?;
+    validate_ulid(&payload.message_id)?;
+
+    let moved = MessageService::mark_read(
+        &state.connection,
+        &lobby_id,
+        &user.user_id,
+        &payload.message_id,
+    )
+    .await?;
+
+    if moved && !payload.silent {
+        broadcast_event(
+            &state,
+            &lobby_id,
+            LobbyEvent::ReadReceipt {
+                user_id: user.user_id,
+                message_id: payload.message_id,
+            },
+        )
+        .await;
+    }
+
+    Ok(StatusCode::OK)
+}
+
+// Nobody is listening when the lobby has no open sockets, the event is simply dropped
+pub(crate) async fn broadcast_event(state: &AppState, lobby_id: &str, event: LobbyEvent) {
+    let channels = state.lobby_channels.lock().await;
+    if let Some(tx) = channels.get(lobby_id) {
+        let _ = tx.send(event.into());
+    }
+}
+
+// Mounted outside `check_access_token`, the handshake authenticates on its own
+pub fn lobby_socket_router() -> Router<AppState> {
+    Router::new().route(
+        MessageEndpoints::LobbySocket(LobbyUlid::default()).template(),
+        get(lobby_chat_ws),
+    )
+}
+
+pub async fn lobby_chat_ws(
     State(state): State<AppState>,
+    Path(lobby_id): Path<String>,
+    Query(query): Query<SocketQuery>,
+    headers: HeaderMap,
     ws: WebSocketUpgrade,
-) -> impl IntoResponse {
-    ws.on_upgrade(move |socket| handle_lobby_socket(socket, lobby_id, state))
+) -> AppResult<Response> {
+    validate_ulid(&lobby_id)?;
+    if let Some(since) = &query.since {
+        validate_ulid(since)?;
+    }
+    let user = authenticate_socket(&headers)?;
+
+    if !LobbyMemberService::is_member(&state.connection, &lobby_id, &user.user_id).await {
+        return Err(AppError::NotFound);
+    }
+
+    Ok(ws
+        .protocols([SOCKET_AUTH_PROTOCOL])
+        .on_upgrade(move |socket| {
+            handle_lobby_socket(socket, state, lobby_id, user.user_id, query.since)
+        }))
 }
 
-async fn handle_lobby_socket(socket: WebSocket, lobby_id: String, state: AppState) {
+async fn handle_lobby_socket(
+    socket: WebSocket,
+    state: AppState,
+    lobby_id: String,
+    user_id: String,
+    since: Option<String>,
+) {
     let (mut sender, mut receiver) = socket.split();
 
-    let mut rx = {
+    let tx = {
         let mut channels = state.lobby_channels.lock().await;
-        let tx = channels.entry(lobby_id.clone()).or_insert_with(|| {
-            let (tx, _) = broadcast::channel(100);
-            tx
-        });
-        tx.subscribe()
+        channels
+            .entry(lobby_id.clone())
+            .or_insert_with(|| broadcast::channel(LOBBY_CHANNEL_CAPACITY).0)
+            .clone()
     };
+    // Subscribed before the replay, so nothing sent in between is lost
+    let mut rx = tx.subscribe();
+    let mut last_acked: Option<String> = None;
+    // Newest message the client has from the replay. Live frames are deduped against this fixed
+    // mark only, broadcasts aren't ordered by ULID and a moving mark would drop some of them.
+    let mut replayed_up_to = since.clone();
+
+    if state.presence.connect(&lobby_id, &user_id)
+        && let Ok(Some(user)) = UserService::find_by_id(&state.connection, &user_id).await
+    {
+        let user = UserService::to_lobby_user(&state.s3_manager, user).await;
+        let _ = tx.send(LobbyEvent::UserJoined { user }.into());
+    }
 
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
-            let msg = serde_json::to_string(&event).unwrap();
-            if sender.send(Message::Text(msg.into())).await.is_err() {
-                break;
+    let mut open = true;
+    if let Some(since) = &since {
+        match replay_missed(&state, &mut sender, &lobby_id, &user_id, since).await {
+            Ok(last) => replayed_up_to = last.or(replayed_up_to),
+            Err(_) => open = false,
+        }
+    }
+
+    let mut last_message_id = replayed_up_to.clone();
+    while open {
+        tokio::select! {
+            event = rx.recv() => {
+                let frame = match event {
+                    Ok(frame) => frame,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(lobby_id = %lobby_id, skipped, "Lobby socket lagged");
+                        LobbyEvent::Resync {
+                            since: last_message_id.clone(),
+                        }
+                        .into()
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                if let LobbyEvent::MessageCreated(message) = &frame.event {
+                    let seen = replayed_up_to
+                        .as_deref()
+                        .is_some_and(|mark| message.ulid.as_str() <= mark);
+                    // Already sent by the replay
+                    if seen {
+                        continue;
+                    }
+                    last_message_id = Some(message.ulid.clone());
+                }
+                let removed = frame.event.removed_member() == Some(user_id.as_str());
+                open = send_json(&mut sender, &frame).await.is_ok() && !removed;
             }
+            frame = receiver.next() => match frame {
+                Some(Ok(Message::Text(text))) => {
+                    let reply =
+                        handle_command(&state, &tx, &lobby_id, &user_id, &mut last_acked, &text)
+                            .await;
+                    if let Some(reply) = reply {
+                        open = send_json(&mut sender, &reply).await.is_ok();
+                    }
+                }
+                Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                // Pings are answered by axum itself
+                Some(Ok(_)) => {}
+            },
         }
-    });
-
-    let lobby_id_clone = lobby_id.clone();
-    let state_clone = state.clone();
-    let mut recv_task =
-        tokio::spawn(
-            async move { while let Some(Ok(Message::Text(text))) = receiver.next().await {} },
-        );
-
-    tokio::select! {
-        _ = (&mut send_task) => recv_task.abort(),
-        _ = (&mut recv_task) => send_task.abort(),
-    };
+    }
+
+    tracing::debug!(
+        lobby_id = %lobby_id,
+        user_id = %user_id,
+        last_acked = ?last_acked,
+        "Lobby socket closed"
+    );
 
+    if state.presence.disconnect(&lobby_id, &user_id) {
+        let _ = tx.send(LobbyEvent::UserLeft { user_id }.into());
+    }
+
+    drop(rx);
     clean_up_channels(state, lobby_id).await;
 }
 
+// Sends the messages newer than `since` and returns the ID of the last one. When more were
+// missed than a socket should replay, the client gets a Resync notice to fetch the rest.
+async fn replay_missed(
+    state: &AppState,
+    sender: &mut SplitSink<WebSocket, Message>,
+    lobby_id: &str,
+    user_id: &str,
+    since: &str,
+) -> Result<Option<String>, axum::Error> {
+    let missed = match MessageService::get_messages_since(
+        &state.connection,
+        lobby_id,
+        user_id,
+        since,
+        REPLAY_LIMIT,
+        state.report_config.hide_threshold,
+    )
+    .await
+    {
+        Ok(page) => page,
+        Err(e) => {
+            tracing::error!(lobby_id = %lobby_id, "Failed to replay missed messages: {e}");
+            let notice: LobbyEventFrame = LobbyEvent::Resync {
+                since: Some(since.to_owned()),
+            }
+            .into();
+            send_json(sender, &notice).await?;
+            return Ok(None);
+        }
+    };
+
+    let mut last = None;
+    for message in missed.items {
+        last = Some(message.ulid.clone());
+        let frame: LobbyEventFrame = LobbyEvent::MessageCreated(message).into();
+        send_json(sender, &frame).await?;
+    }
+
+    if missed.has_more {
+        let notice: LobbyEventFrame = LobbyEvent::Resync {
+            since: last.clone(),
+        }
+        .into();
+        send_json(sender, &notice).await?;
+    }
+
+    Ok(last)
+}
+
+async fn send_json<T: Serialize>(
+    sender: &mut SplitSink<WebSocket, Message>,
+    value: &T,
+) -> Result<(), axum::Error> {
+    let text = serde_json::to_string(value).map_err(axum::Error::new)?;
+    sender.send(Message::Text(text.into())).await
+}
+
+// Runs one frame from the client. Returns the reply meant only for that socket, if any.
+pub(crate) async fn handle_command(
+    state: &AppState,
+    tx: &broadcast::Sender<LobbyEventFrame>,
+    lobby_id: &str,
+    user_id: &str,
+    last_acked: &mut Option<String>,
+    text: &str,
+) -> Option<CommandReply> {
+    let command = match serde_json::from_str::<LobbyCommand>(text) {
+        Ok(command) => command,
+        Err(e) => {
+            return Some(CommandReply::Rejected {
+                client_id: None,
+                error: AppError::Custom(format!("Unknown command: {e}")).into(),
+            });
+        }
+    };
+
+    match command {
+        LobbyCommand::SendMessage {
+            content,
+            client_id,
+            reply_to,
+        } => {
+            let request = SendMessageRequest { content, reply_to };
+            let valid = request
+                .validate()
+                .map_err(AppError::Validation)
+                .and_then(|()| match &request.reply_to {
+                    Some(reply_to) => validate_ulid(reply_to).map_err(AppError::from),
+                    None => Ok(()),
+                });
+            let sent = match valid {
+                Ok(()) => {
+                    MessageService::send_message(
+                        &state.connection,
+                        &state.events,
+                        &state.text_filter,
+                        lobby_id.to_owned(),
+                        user_id.to_owned(),
+                        request.content,
+                        request.reply_to,
+                    )
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+
+            match sent {
+                Ok(message) => {
+                    let message_id = message.ulid.clone();
+                    let _ = tx.send(LobbyEvent::MessageCreated(message).into());
+                    Some(CommandReply::Sent {
+                        client_id,
+                        message_id,
+                    })
+                }
+                Err(e) => Some(CommandReply::Rejected {
+                    client_id,
+                    error: e.into(),
+                }),
+            }
+        }
+        LobbyCommand::Typing => {
+            if state.presence.should_broadcast_typing(lobby_id, user_id) {
+                let _ = tx.send(
+                    LobbyEvent::Typing {
+                        user_id: user_id.to_owned(),
+                    }
+                    .into(),
+                );
+            }
+            None
+        }
+        LobbyCommand::Ack { message_id } => {
+            *last_acked = Some(message_id);
+            None
+        }
+    }
+}
+
 async fn clean_up_channels(state: AppState, lobby_id: String) {
     let mut channels = state.lobby_channels.lock().await;
     if let Some(tx) = channels.get(&lobby_id)
@@ -1,7 +1,12 @@
+pub mod follow_routes;
+pub mod lobby_access_routes;
 pub mod lobby_routes;
 pub mod message_routes;
 pub mod quest_proof_routes;
 pub mod refresh_token_routes;
+pub mod report_routes;
+pub mod search_routes;
+pub mod share_routes;
 pub mod tests;
 pub mod user_quest_status_routes;
 pub mod user_routes;
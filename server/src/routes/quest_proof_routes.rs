@@ -6,16 +6,18 @@ use axum::{
 };
 use shared::{endpoints::API, errors::AppError};
 use shared::{endpoints::QuestProofUlid, utils::ulid_validation::validate_ulid};
-use shared::{
-    endpoints::quest_proof_endpoints::QuestProofEndpoints, models::quest_proof_dto::PaginationQuery,
-};
+use shared::{endpoints::quest_proof_endpoints::QuestProofEndpoints, models::PageQuery};
 use shared::{
     endpoints::{QuestUlid, UserUlid},
     models::quest_proof_dto::{ProofDetailsResponse, SubmitProofRequest, SubmitProofResponse},
 };
-use shared::{errors::AppResult, models::quest_proof_dto::ProofFeedResponse};
+use shared::{errors::AppResult, models::Page};
 
-use crate::{AppState, service::quest_proof_service::QuestProofService};
+use crate::{
+    AppState, middleware::jwt_verify_middleware::AuthUser,
+    service::quest_proof_service::QuestProofService,
+};
+use std::time::Duration;
 use validator::Validate;
 
 pub fn quest_proof_router() -> Router<AppState> {
@@ -29,14 +31,26 @@ pub fn quest_proof_router() -> Router<AppState> {
             QuestProofEndpoints::ConfirmSubmission(QuestProofUlid::default()).template(),
             post(confirm_proof),
         )
+        .route(
+            QuestProofEndpoints::RefreshUploadUrls(QuestProofUlid::default()).template(),
+            post(refresh_upload_urls),
+        )
         .route(
             QuestProofEndpoints::GetDetails(QuestProofUlid::default()).template(),
-            get(get_proof_details),
+            get(get_proof_details).delete(cancel_proof),
         )
         .route(
             QuestProofEndpoints::GetFeed(UserUlid::default()).template(),
             get(get_proof_feed),
         )
+        .route(
+            QuestProofEndpoints::GetRankedFeed(UserUlid::default()).template(),
+            get(get_ranked_proof_feed),
+        )
+        .route(
+            QuestProofEndpoints::GetFollowingFeed(UserUlid::default()).template(),
+            get(get_following_proof_feed),
+        )
         .route(
             QuestProofEndpoints::BeliefProof(QuestProofUlid::default(), UserUlid::default())
                 .template(),
@@ -51,48 +65,77 @@ pub fn quest_proof_router() -> Router<AppState> {
 async fn init_proof(
     State(state): State<AppState>,
     Path((user_id, quest_id)): Path<(String, String)>,
+    user: AuthUser,
     axum::Json(payload): axum::Json<SubmitProofRequest>,
 ) -> AppResult<axum::Json<SubmitProofResponse>> {
     validate_ulid(&user_id)?;
     validate_ulid(&quest_id)?;
+    user.ensure_is(&user_id)?;
     payload.validate().map_err(AppError::Validation)?;
 
-    let (model, photo_urls, voice_urls) = QuestProofService::init_proof_submition(
+    let (model, photo_uploads, voice_uploads) = QuestProofService::init_proof_submition(
         &state.connection,
         &state.s3_manager,
+        &state.text_filter,
         user_id,
         quest_id,
-        payload.proof_text,
-        payload.photo_count,
-        payload.voice_count,
+        payload,
     )
     .await?;
 
     Ok(axum::Json(SubmitProofResponse {
         proof_ulid: model.ulid,
         status: format!("{:?}", model.status),
-        photo_upload_urls: photo_urls,
-        voice_upload_urls: voice_urls,
+        photo_uploads,
+        voice_uploads,
     }))
 }
 
 async fn confirm_proof(
     State(state): State<AppState>,
     Path(proof_id): Path<String>,
+    user: AuthUser,
 ) -> AppResult<StatusCode> {
     validate_ulid(&proof_id)?;
 
-    QuestProofService::confirm_proof_upload(&state.connection, proof_id)
-        .await
-        .map_err(AppError::from)?;
+    QuestProofService::confirm_proof_upload(
+        &state.connection,
+        &state.events,
+        proof_id,
+        &user.user_id,
+    )
+    .await?;
 
     Ok(StatusCode::OK)
 }
 
+async fn refresh_upload_urls(
+    State(state): State<AppState>,
+    Path(proof_id): Path<String>,
+    user: AuthUser,
+) -> AppResult<axum::Json<SubmitProofResponse>> {
+    validate_ulid(&proof_id)?;
+
+    let (model, photo_uploads, voice_uploads) = QuestProofService::refresh_upload_urls(
+        &state.connection,
+        &state.s3_manager,
+        &proof_id,
+        &user.user_id,
+    )
+    .await?;
+
+    Ok(axum::Json(SubmitProofResponse {
+        proof_ulid: model.ulid,
+        status: format!("{:?}", model.status),
+        photo_uploads,
+        voice_uploads,
+    }))
+}
+
 async fn get_proof_details(
     State(state): State<AppState>,
     Path(proof_id): Path<String>,
-    current_user_id: String,
+    user: AuthUser,
 ) -> AppResult<axum::Json<ProofDetailsResponse>> {
     validate_ulid(&proof_id)?;
 
@@ -100,7 +143,7 @@ async fn get_proof_details(
         &state.connection,
         &state.s3_manager,
         &proof_id,
-        &current_user_id,
+        &user.user_id,
     )
     .await?
     .ok_or(AppError::NotFound)?;
@@ -108,23 +151,96 @@ async fn get_proof_details(
     Ok(axum::Json(detail.into()))
 }
 
+async fn cancel_proof(
+    State(state): State<AppState>,
+    Path(proof_id): Path<String>,
+    user: AuthUser,
+) -> AppResult<StatusCode> {
+    validate_ulid(&proof_id)?;
+
+    QuestProofService::cancel_upload(
+        &state.connection,
+        &state.s3_manager,
+        &proof_id,
+        &user.user_id,
+    )
+    .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
 async fn get_proof_feed(
     State(state): State<AppState>,
     Path(user_id): Path<String>,
-    axum::extract::Query(pagination): axum::extract::Query<PaginationQuery>,
-) -> AppResult<axum::Json<ProofFeedResponse>> {
+    user: AuthUser,
+    axum::extract::Query(pagination): axum::extract::Query<PageQuery>,
+) -> AppResult<axum::Json<Page<ProofDetailsResponse>>> {
     validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
     pagination.validate().map_err(AppError::Validation)?;
 
     let limit = pagination.limit.unwrap_or(20);
-    let offset = pagination.offset.unwrap_or(0);
 
     let results = QuestProofService::get_feed(
         &state.connection,
         &state.s3_manager,
         &user_id,
-        limit as u32,
-        offset as u32,
+        limit as u64,
+        pagination.cursor.as_deref(),
+        state.report_config.hide_threshold,
+        false,
+    )
+    .await?;
+
+    Ok(axum::Json(results))
+}
+
+async fn get_following_proof_feed(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    user: AuthUser,
+    axum::extract::Query(pagination): axum::extract::Query<PageQuery>,
+) -> AppResult<axum::Json<Page<ProofDetailsResponse>>> {
+    validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let limit = pagination.limit.unwrap_or(20);
+
+    let results = QuestProofService::get_feed(
+        &state.connection,
+        &state.s3_manager,
+        &user_id,
+        limit as u64,
+        pagination.cursor.as_deref(),
+        state.report_config.hide_threshold,
+        true,
+    )
+    .await?;
+
+    Ok(axum::Json(results))
+}
+
+async fn get_ranked_proof_feed(
+    State(state): State<AppState>,
+    Path(user_id): Path<String>,
+    user: AuthUser,
+    axum::extract::Query(pagination): axum::extract::Query<PageQuery>,
+) -> AppResult<axum::Json<Page<ProofDetailsResponse>>> {
+    validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let limit = pagination.limit.unwrap_or(20);
+
+    let results = QuestProofService::get_ranked_feed(
+        &state.connection,
+        &state.s3_manager,
+        &user_id,
+        limit as u64,
+        pagination.cursor.as_deref(),
+        state.report_config.hide_threshold,
+        &state.feed_ranking,
     )
     .await?;
 
@@ -134,11 +250,26 @@ async fn get_proof_feed(
 async fn toggle_proof_belief(
     State(state): State<AppState>,
     Path((proof_id, user_id)): Path<(String, String)>,
+    user: AuthUser,
 ) -> AppResult<StatusCode> {
     validate_ulid(&proof_id)?;
     validate_ulid(&user_id)?;
+    user.ensure_is(&user_id)?;
+
+    let config = &state.belief_config;
+    state.rate_limiter.check(
+        &format!("belief:{user_id}"),
+        config.toggles_per_minute,
+        Duration::from_secs(60),
+    )?;
+    state.rate_limiter.check(
+        &format!("belief:{user_id}:{proof_id}"),
+        config.toggles_per_proof_per_hour,
+        Duration::from_secs(3600),
+    )?;
 
-    QuestProofService::toggle_belief(&state.connection, proof_id, user_id).await?;
+    QuestProofService::toggle_belief(&state.connection, &state.events, proof_id, user_id, config)
+        .await?;
 
     Ok(StatusCode::OK)
 }
@@ -146,11 +277,23 @@ async fn toggle_proof_belief(
 async fn get_user_proof_history(
     State(state): State<AppState>,
     Path(user_id): Path<String>,
-) -> AppResult<axum::Json<Vec<ProofDetailsResponse>>> {
+    viewer: AuthUser,
+    axum::extract::Query(pagination): axum::extract::Query<PageQuery>,
+) -> AppResult<axum::Json<Page<ProofDetailsResponse>>> {
     validate_ulid(&user_id)?;
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let limit = pagination.limit.unwrap_or(20);
 
-    let history =
-        QuestProofService::get_user_history(&state.connection, &state.s3_manager, &user_id).await?;
+    let history = QuestProofService::get_user_history(
+        &state.connection,
+        &state.s3_manager,
+        &user_id,
+        &viewer.user_id,
+        limit as u64,
+        pagination.cursor.as_deref(),
+    )
+    .await?;
 
     Ok(axum::Json(history))
 }
@@ -0,0 +1,114 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::{get, post},
+};
+use shared::{
+    endpoints::{API, report_endpoints::ReportEndpoints},
+    errors::{AppError, AppResult},
+    models::{
+        Page, PageQuery,
+        report_dto::{
+            CreateReportRequest, ModerationQueueItem, PhotoMatchDto, ReportDto,
+            ResolveReportsRequest,
+        },
+    },
+};
+use validator::Validate;
+
+use crate::{
+    AppState,
+    middleware::jwt_verify_middleware::AuthUser,
+    service::{photo_hash_service::PhotoHashService, report_service::ReportService},
+};
+
+pub fn report_router() -> Router<AppState> {
+    Router::new()
+        .route(ReportEndpoints::Create.template(), post(create_report))
+        .route(
+            ReportEndpoints::GetQueue.template(),
+            get(get_moderation_queue),
+        )
+        .route(ReportEndpoints::Resolve.template(), post(resolve_reports))
+        .route(
+            ReportEndpoints::GetPhotoMatches.template(),
+            get(get_photo_matches),
+        )
+}
+
+async fn create_report(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(payload): Json<CreateReportRequest>,
+) -> AppResult<Json<ReportDto>> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let report = ReportService::create_report(
+        &state.connection,
+        &user.user_id,
+        payload.target_type,
+        &payload.target_id,
+        payload.reason,
+        payload.details,
+    )
+    .await?;
+
+    Ok(Json(ReportDto::from(report)))
+}
+
+async fn get_moderation_queue(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(pagination): Query<PageQuery>,
+) -> AppResult<Json<Page<ModerationQueueItem>>> {
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let queue = ReportService::get_moderation_queue(
+        &state.connection,
+        &user.user_id,
+        &state.report_config,
+        pagination.limit.unwrap_or(20) as u64,
+        pagination.cursor.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(queue))
+}
+
+async fn resolve_reports(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(payload): Json<ResolveReportsRequest>,
+) -> AppResult<Json<u64>> {
+    payload.validate().map_err(AppError::Validation)?;
+
+    let resolved = ReportService::resolve_target(
+        &state.connection,
+        &user.user_id,
+        payload.target_type,
+        &payload.target_id,
+        payload.resolution,
+    )
+    .await?;
+
+    Ok(Json(resolved))
+}
+
+async fn get_photo_matches(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(pagination): Query<PageQuery>,
+) -> AppResult<Json<Page<PhotoMatchDto>>> {
+    pagination.validate().map_err(AppError::Validation)?;
+
+    let matches = PhotoHashService::get_matches(
+        &state.connection,
+        &state.s3_manager,
+        &user.user_id,
+        pagination.limit.unwrap_or(20) as u64,
+        pagination.cursor.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(matches))
+}
@@ -0,0 +1,119 @@
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    routing::get,
+};
+use shared::{
+    endpoints::{
+        API,
+        search_endpoints::{SearchEndpoints, SearchQuery},
+    },
+    errors::{AppError, AppResult},
+    models::{Page, search_dto::SearchHit},
+    utils::ulid_validation::validate_ulid,
+};
+use validator::Validate;
+
+use crate::{
+    AppState, middleware::jwt_verify_middleware::AuthUser, service::search_service::SearchService,
+};
+
+pub fn search_router() -> Router<AppState> {
+    Router::new()
+        .route(SearchEndpoints::Messages.template(), get(search_messages))
+        .route(SearchEndpoints::Proofs.template(), get(search_proofs))
+        .route(SearchEndpoints::Lobbies.template(), get(search_lobbies))
+        .route(SearchEndpoints::Quests.template(), get(search_quests))
+}
+
+fn validate_query(query: &SearchQuery) -> AppResult<u64> {
+    query.validate().map_err(AppError::Validation)?;
+    if let Some(cursor) = &query.cursor {
+        validate_ulid(cursor)?;
+    }
+    if let Some(lobby_id) = &query.lobby_id {
+        validate_ulid(lobby_id)?;
+    }
+
+    Ok(query.limit.unwrap_or(20) as u64)
+}
+
+async fn search_messages(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<Json<Page<SearchHit>>> {
+    let limit = validate_query(&query)?;
+
+    let hits = SearchService::search_messages(
+        &state.connection,
+        &user.user_id,
+        &query.q,
+        query.lobby_id.as_deref(),
+        limit,
+        query.cursor.as_deref(),
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(hits))
+}
+
+async fn search_proofs(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<Json<Page<SearchHit>>> {
+    let limit = validate_query(&query)?;
+
+    let hits = SearchService::search_proofs(
+        &state.connection,
+        &user.user_id,
+        &query.q,
+        limit,
+        query.cursor.as_deref(),
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(hits))
+}
+
+async fn search_lobbies(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<Json<Page<SearchHit>>> {
+    let limit = validate_query(&query)?;
+
+    let hits = SearchService::search_lobbies(
+        &state.connection,
+        &user.user_id,
+        &query.q,
+        limit,
+        query.cursor.as_deref(),
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Json(hits))
+}
+
+async fn search_quests(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Query(query): Query<SearchQuery>,
+) -> AppResult<Json<Page<SearchHit>>> {
+    let limit = validate_query(&query)?;
+
+    let hits = SearchService::search_quests(
+        &state.connection,
+        &user.user_id,
+        &query.q,
+        limit,
+        query.cursor.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(hits))
+}
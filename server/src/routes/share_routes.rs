@@ -0,0 +1,74 @@
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::Html,
+    routing::{get, post},
+};
+use shared::{
+    endpoints::{API, QuestProofUlid, share_endpoints::ShareEndpoints},
+    errors::AppResult,
+    models::quest_proof_dto::ProofShareResponse,
+    utils::ulid_validation::validate_ulid,
+};
+
+use crate::{
+    AppState, middleware::jwt_verify_middleware::AuthUser, service::share_service::ShareService,
+};
+
+pub fn share_router() -> Router<AppState> {
+    Router::new().route(
+        ShareEndpoints::CreateShare(QuestProofUlid::default()).template(),
+        post(create_share).delete(revoke_share),
+    )
+}
+
+// Unauthenticated, the page is what link previews and people without the app see
+pub fn public_share_router() -> Router<AppState> {
+    Router::new().route(
+        ShareEndpoints::PublicPage(String::new()).template(),
+        get(get_shared_proof_page),
+    )
+}
+
+async fn create_share(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(proof_id): Path<String>,
+) -> AppResult<Json<ProofShareResponse>> {
+    validate_ulid(&proof_id)?;
+
+    let share = ShareService::create_share(&state.connection, &proof_id, &user.user_id).await?;
+
+    Ok(Json(ProofShareResponse {
+        token: share.token,
+        created_at: share.created_at,
+    }))
+}
+
+async fn revoke_share(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(proof_id): Path<String>,
+) -> AppResult<StatusCode> {
+    validate_ulid(&proof_id)?;
+
+    ShareService::revoke_share(&state.connection, &proof_id, &user.user_id).await?;
+
+    Ok(StatusCode::OK)
+}
+
+async fn get_shared_proof_page(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> AppResult<Html<String>> {
+    let page = ShareService::get_shared_proof(
+        &state.connection,
+        &state.s3_manager,
+        &token,
+        state.report_config.hide_threshold,
+    )
+    .await?;
+
+    Ok(Html(page.render_html()))
+}
@@ -0,0 +1,186 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection};
+    use ulid::Ulid;
+
+    use crate::entities::quest_proofs;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::routes::{follow_routes::follow_router, quest_proof_routes::quest_proof_router};
+    use crate::service::quest_service::QuestService;
+    use shared::{
+        endpoints::{
+            API, UserUlid, follow_endpoints::FollowEndpoints,
+            quest_proof_endpoints::QuestProofEndpoints,
+        },
+        models::{
+            Page,
+            follow_dto::{FollowCountsResponse, FollowUserDto},
+            quest_dto::Complexity,
+            quest_proof_dto::{ProofDetailsResponse, ProofVisibility},
+        },
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = follow_router()
+            .merge(quest_proof_router())
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_proof(db: &DatabaseConnection, author_id: &str) -> String {
+        let quest = QuestService::create_quest(
+            db,
+            "Go for a walk".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        quest_proofs::ActiveModel {
+            ulid: Set(Ulid::new().to_string()),
+            user_id: Set(author_id.to_owned()),
+            quest_id: Set(quest.ulid),
+            proof_text: Set(Some("Walked".into())),
+            photos: Set(None),
+            voice_notes: Set(None),
+            status: Set(quest_proofs::ProofStatus::Pending),
+            beliefs_count: Set(0),
+            belief_weight: Set(0),
+            visibility: Set(ProofVisibility::Public),
+            photos_hashed_at: Set(None),
+            created_at: Set(chrono::Utc::now()),
+            updated_at: Set(chrono::Utc::now()),
+        }
+        .insert(db)
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    async fn follow(server: &TestServer, follower: &str, target: &str) {
+        server
+            .post(&FollowEndpoints::Follow(UserUlid(target.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(follower))
+            .await
+            .assert_status_success();
+    }
+
+    async fn counts(server: &TestServer, viewer: &str, user: &str) -> FollowCountsResponse {
+        server
+            .get(&FollowEndpoints::GetCounts(UserUlid(user.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(viewer))
+            .await
+            .json()
+    }
+
+    #[tokio::test]
+    async fn test_follow_and_unfollow_update_counts() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let bobby = create_user(&db, "bobby").await;
+
+        follow(&server, &alice, &bobby).await;
+        // Following twice is idempotent
+        follow(&server, &alice, &bobby).await;
+
+        let bobby_counts = counts(&server, &alice, &bobby).await;
+        assert_eq!(bobby_counts.followers, 1);
+        assert_eq!(bobby_counts.following, 0);
+        assert!(bobby_counts.is_following);
+
+        server
+            .delete(&FollowEndpoints::Unfollow(UserUlid(bobby.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&alice))
+            .await
+            .assert_status_success();
+
+        let bobby_counts = counts(&server, &alice, &bobby).await;
+        assert_eq!(bobby_counts.followers, 0);
+        assert!(!bobby_counts.is_following);
+    }
+
+    #[tokio::test]
+    async fn test_cannot_follow_yourself() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+
+        server
+            .post(&FollowEndpoints::Follow(UserUlid(alice.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&alice))
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_followers_list_is_paginated() {
+        let (server, db) = setup_test_server().await;
+        let target = create_user(&db, "target").await;
+
+        let mut followers = Vec::new();
+        for name in ["first", "second", "third"] {
+            let follower = create_user(&db, name).await;
+            follow(&server, &follower, &target).await;
+            followers.push(follower);
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut request = server
+                .get(&FollowEndpoints::GetFollowers(UserUlid(target.clone())).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(&target))
+                .add_query_param("limit", 2);
+            if let Some(cursor) = &cursor {
+                request = request.add_query_param("cursor", cursor);
+            }
+
+            let page: Page<FollowUserDto> = request.await.json();
+            seen.extend(page.items.into_iter().map(|u| u.ulid));
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        seen.sort();
+        followers.sort();
+        assert_eq!(seen, followers);
+    }
+
+    #[tokio::test]
+    async fn test_following_feed_only_shows_followed_authors() {
+        let (server, db) = setup_test_server().await;
+        let viewer = create_user(&db, "viewer").await;
+        let followed = create_user(&db, "followed").await;
+        let stranger = create_user(&db, "stranger").await;
+
+        let followed_proof = create_proof(&db, &followed).await;
+        create_proof(&db, &stranger).await;
+
+        follow(&server, &viewer, &followed).await;
+
+        let feed: Page<ProofDetailsResponse> = server
+            .get(&QuestProofEndpoints::GetFollowingFeed(UserUlid(viewer.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&viewer))
+            .await
+            .json();
+
+        let ids: Vec<String> = feed.items.into_iter().map(|p| p.ulid).collect();
+        assert_eq!(ids, vec![followed_proof]);
+    }
+}
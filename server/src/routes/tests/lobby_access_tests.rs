@@ -0,0 +1,342 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection};
+
+    use crate::entities::lobby_invites;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::routes::{lobby_access_routes::lobby_access_router, lobby_routes::lobby_router};
+    use crate::service::{
+        lobby_access_service::LobbyAccessService, lobby_member_service::LobbyMemberService,
+        lobby_service::LobbyService,
+    };
+    use shared::{
+        endpoints::{API, LobbyUlid, UserUlid, lobby_endpoints::LobbyEndpoints},
+        models::lobby_dto::{
+            CreateInviteRequest, CreateJoinRequest, InvitePreview, JoinRequestDto,
+            LobbyDetailsResponse, LobbyDto, LobbyFeedResponse, LobbyInviteDto, LobbyVisibility,
+            SetVisibilityRequest,
+        },
+        moderation::TextFilter,
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = lobby_router()
+            .merge(lobby_access_router())
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_lobby(
+        db: &DatabaseConnection,
+        owner: &str,
+        visibility: LobbyVisibility,
+    ) -> String {
+        LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            owner.to_owned(),
+            "Night owls".into(),
+            "Chat".into(),
+            None,
+            visibility,
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    async fn feed_ids(server: &TestServer, user: &str) -> Vec<String> {
+        server
+            .get(&LobbyEndpoints::GetAll(UserUlid(user.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+            .await
+            .json::<LobbyFeedResponse>()
+            .items
+            .into_iter()
+            .map(|item| item.lobby.ulid)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_private_lobby_join_requests() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "owner").await;
+        let guest = create_user(&db, "guest").await;
+        let other = create_user(&db, "other").await;
+        let lobby = create_lobby(&db, &owner, LobbyVisibility::Private).await;
+        let lobby_ulid = LobbyUlid(lobby.clone());
+
+        // Hidden from the feed and closed to direct joins
+        assert!(feed_ids(&server, &guest).await.is_empty());
+        assert_eq!(feed_ids(&server, &owner).await, vec![lobby.clone()]);
+        server
+            .post(&LobbyEndpoints::Join(lobby_ulid.clone(), UserUlid(guest.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .await
+            .assert_status_bad_request();
+
+        let request_path = LobbyEndpoints::RequestToJoin(lobby_ulid.clone()).path();
+        for (user, message) in [(&guest, Some("Let me in".to_owned())), (&other, None)] {
+            server
+                .post(&request_path)
+                .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+                .json(&CreateJoinRequest { message })
+                .await
+                .assert_status(axum::http::StatusCode::CREATED);
+        }
+        server
+            .post(&request_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .json(&CreateJoinRequest::default())
+            .await
+            .assert_status_bad_request();
+
+        // Only admins see who is waiting
+        server
+            .get(&request_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .await
+            .assert_status_not_found();
+        let requests = server
+            .get(&request_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Vec<JoinRequestDto>>();
+        assert_eq!(requests.len(), 2);
+        let from_guest = requests
+            .iter()
+            .find(|request| request.user.ulid == guest)
+            .unwrap();
+        assert_eq!(from_guest.message.as_deref(), Some("Let me in"));
+
+        server
+            .post(
+                &LobbyEndpoints::ApproveJoinRequest(lobby_ulid.clone(), UserUlid(guest.clone()))
+                    .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_ok();
+        assert!(LobbyMemberService::is_member(&db, &lobby, &guest).await);
+        assert_eq!(feed_ids(&server, &guest).await, vec![lobby.clone()]);
+
+        // Members can't reject others, but anyone can take back their own request
+        let other_request =
+            LobbyEndpoints::DeleteJoinRequest(lobby_ulid.clone(), UserUlid(other.clone())).path();
+        server
+            .delete(&other_request)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .await
+            .assert_status_bad_request();
+        server
+            .delete(&other_request)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_ok();
+        server
+            .post(&LobbyEndpoints::ApproveJoinRequest(lobby_ulid, UserUlid(other.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_not_found();
+        assert!(!LobbyMemberService::is_member(&db, &lobby, &other).await);
+    }
+
+    #[tokio::test]
+    async fn test_invites_run_out_and_expire() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "owner").await;
+        let guest = create_user(&db, "guest").await;
+        let other = create_user(&db, "other").await;
+        let lobby = create_lobby(&db, &owner, LobbyVisibility::Private).await;
+        let invites_path = LobbyEndpoints::CreateInvite(LobbyUlid(lobby.clone())).path();
+
+        server
+            .post(&invites_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .json(&CreateInviteRequest::default())
+            .await
+            .assert_status_not_found();
+
+        let single_use = server
+            .post(&invites_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .json(&CreateInviteRequest {
+                max_uses: Some(1),
+                ..Default::default()
+            })
+            .await
+            .json::<LobbyInviteDto>();
+
+        let preview_path = LobbyEndpoints::GetInvite(single_use.code.clone()).path();
+        let preview = server
+            .get(&preview_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .await
+            .json::<InvitePreview>();
+        assert_eq!(preview.lobby.ulid, lobby);
+        assert_eq!(preview.members_count, 1);
+
+        let accept_path = LobbyEndpoints::AcceptInvite(single_use.code.clone()).path();
+        server
+            .post(&accept_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .await
+            .assert_status_ok();
+        assert!(LobbyMemberService::is_member(&db, &lobby, &guest).await);
+
+        // Used up
+        server
+            .post(&accept_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_bad_request();
+        server
+            .get(&preview_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_bad_request();
+
+        let timed = server
+            .post(&invites_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .json(&CreateInviteRequest {
+                expires_in_hours: Some(1),
+                ..Default::default()
+            })
+            .await
+            .json::<LobbyInviteDto>();
+        lobby_invites::ActiveModel {
+            code: Set(timed.code.clone()),
+            expires_at: Set(Some(chrono::Utc::now() - chrono::Duration::minutes(1))),
+            ..Default::default()
+        }
+        .update(&db)
+        .await
+        .unwrap();
+        server
+            .post(&LobbyEndpoints::AcceptInvite(timed.code.clone()).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_bad_request();
+        assert!(!LobbyMemberService::is_member(&db, &lobby, &other).await);
+
+        let invites = server
+            .get(&invites_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Vec<LobbyInviteDto>>();
+        assert_eq!(invites.len(), 2);
+        let used = invites
+            .iter()
+            .find(|invite| invite.code == single_use.code)
+            .unwrap();
+        assert_eq!(used.uses, 1);
+
+        let revoke_path =
+            LobbyEndpoints::RevokeInvite(LobbyUlid(lobby.clone()), timed.code.clone()).path();
+        server
+            .delete(&revoke_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_ok();
+        server
+            .get(&LobbyEndpoints::GetInvite(timed.code).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_lobbies_are_joinable_but_hidden() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "owner").await;
+        let guest = create_user(&db, "guest").await;
+        let lobby = create_lobby(&db, &owner, LobbyVisibility::Public).await;
+        LobbyMemberService::join_lobby(&db, lobby.clone(), guest.clone())
+            .await
+            .unwrap();
+
+        let visibility_path = LobbyEndpoints::SetVisibility(LobbyUlid(lobby.clone())).path();
+        let unlisted = SetVisibilityRequest {
+            visibility: LobbyVisibility::Unlisted,
+        };
+        server
+            .put(&visibility_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .json(&unlisted)
+            .await
+            .assert_status_bad_request();
+        let updated = server
+            .put(&visibility_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .json(&unlisted)
+            .await
+            .json::<LobbyDto>();
+        assert_eq!(updated.visibility, LobbyVisibility::Unlisted);
+
+        let stranger = create_user(&db, "stranger").await;
+        assert!(feed_ids(&server, &stranger).await.is_empty());
+        assert_eq!(feed_ids(&server, &guest).await, vec![lobby.clone()]);
+
+        // The link is all it takes
+        server
+            .post(
+                &LobbyEndpoints::Join(LobbyUlid(lobby.clone()), UserUlid(stranger.clone())).path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+            .await
+            .assert_status_ok();
+        server
+            .post(&LobbyEndpoints::RequestToJoin(LobbyUlid(lobby)).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&guest))
+            .json(&CreateJoinRequest::default())
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_private_lobby_details_need_membership_or_invite() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "owner").await;
+        let stranger = create_user(&db, "stranger").await;
+        let lobby = create_lobby(&db, &owner, LobbyVisibility::Private).await;
+        let other_lobby = create_lobby(&db, &owner, LobbyVisibility::Private).await;
+        let invite = LobbyAccessService::create_invite(&db, &lobby, &owner, None, None)
+            .await
+            .unwrap()
+            .code;
+        let foreign_invite =
+            LobbyAccessService::create_invite(&db, &other_lobby, &owner, None, None)
+                .await
+                .unwrap()
+                .code;
+
+        let details = |user: &str, invite: Option<&str>| {
+            let mut request = server
+                .get(&LobbyEndpoints::GetDetails(LobbyUlid(lobby.clone())).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(user));
+            if let Some(invite) = invite {
+                request = request.add_query_param("invite", invite);
+            }
+            request
+        };
+
+        details(&owner, None).await.assert_status_ok();
+        details(&stranger, None).await.assert_status_not_found();
+        details(&stranger, Some(&foreign_invite))
+            .await
+            .assert_status_not_found();
+        let shown: LobbyDetailsResponse = details(&stranger, Some(&invite)).await.json();
+        assert_eq!(shown.lobby.ulid, lobby);
+    }
+}
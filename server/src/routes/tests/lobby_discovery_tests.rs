@@ -0,0 +1,311 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::DatabaseConnection;
+    use std::time::Duration;
+
+    use crate::events::EventBus;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::lobby_routes::lobby_router;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::{
+        lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+        message_service::MessageService,
+    };
+    use shared::{
+        endpoints::{
+            API,
+            lobby_endpoints::{DiscoverQuery, LobbyEndpoints, LobbySort},
+        },
+        models::{
+            Page,
+            lobby_dto::{DiscoveredLobby, LobbyVisibility, TopicCount},
+        },
+        moderation::TextFilter,
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = lobby_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    // ULIDs made within the same millisecond have no fixed order
+    async fn tick() {
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+
+    async fn create_lobby(
+        db: &DatabaseConnection,
+        owner: &str,
+        name: &str,
+        topic: &str,
+        visibility: LobbyVisibility,
+        members: &[&String],
+    ) -> String {
+        let lobby_id = LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            owner.to_owned(),
+            name.into(),
+            topic.into(),
+            None,
+            visibility,
+        )
+        .await
+        .unwrap()
+        .ulid;
+        for member in members {
+            LobbyMemberService::join_lobby(db, lobby_id.clone(), (*member).clone())
+                .await
+                .unwrap();
+        }
+        tick().await;
+        lobby_id
+    }
+
+    async fn send(db: &DatabaseConnection, lobby_id: &str, author: &str) {
+        MessageService::send_message(
+            db,
+            &EventBus::new(),
+            &TextFilter::default(),
+            lobby_id.to_owned(),
+            author.to_owned(),
+            "Hello there".into(),
+            None,
+        )
+        .await
+        .unwrap();
+        tick().await;
+    }
+
+    async fn discover(
+        server: &TestServer,
+        user: &str,
+        query: DiscoverQuery,
+    ) -> Page<DiscoveredLobby> {
+        let response = server
+            .get(&LobbyEndpoints::Discover.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+            .add_query_params(query)
+            .await;
+        response.assert_status_ok();
+        response.json()
+    }
+
+    fn sorted(sort: LobbySort) -> DiscoverQuery {
+        DiscoverQuery {
+            sort,
+            ..Default::default()
+        }
+    }
+
+    fn ids(page: &Page<DiscoveredLobby>) -> Vec<&str> {
+        page.items
+            .iter()
+            .map(|item| item.lobby.ulid.as_str())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_discovery_sorts_filters_and_pages() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        let carol = create_user(&db, "carol").await;
+        let dave = create_user(&db, "dave").await;
+
+        let rust = create_lobby(
+            &db,
+            &alice,
+            "Rust club",
+            "Programming",
+            LobbyVisibility::Public,
+            &[&bob, &carol],
+        )
+        .await;
+        let go = create_lobby(
+            &db,
+            &bob,
+            "Go corner",
+            "Programming",
+            LobbyVisibility::Public,
+            &[],
+        )
+        .await;
+        let books = create_lobby(
+            &db,
+            &carol,
+            "Book nook",
+            "Reading",
+            LobbyVisibility::Public,
+            &[&alice],
+        )
+        .await;
+        create_lobby(
+            &db,
+            &alice,
+            "Secret readers",
+            "Reading",
+            LobbyVisibility::Private,
+            &[],
+        )
+        .await;
+        let banned = create_lobby(
+            &db,
+            &alice,
+            "Loud readers",
+            "Reading",
+            LobbyVisibility::Public,
+            &[&bob, &carol],
+        )
+        .await;
+        LobbyMemberService::ban_member(&db, &banned, &alice, &dave, None, None)
+            .await
+            .unwrap();
+
+        send(&db, &rust, &alice).await;
+        send(&db, &go, &bob).await;
+
+        let page = discover(&server, &dave, sorted(LobbySort::Members)).await;
+        assert_eq!(ids(&page), vec![&rust, &books, &go]);
+        let counts: Vec<u32> = page.items.iter().map(|item| item.members_count).collect();
+        assert_eq!(counts, vec![3, 2, 1]);
+        assert!(page.items.iter().all(|item| !item.is_member));
+
+        let page = discover(&server, &dave, sorted(LobbySort::Activity)).await;
+        assert_eq!(ids(&page), vec![&go, &rust, &books]);
+        assert!(page.items[0].last_activity_at.is_some());
+        assert!(page.items[2].last_activity_at.is_none());
+
+        let page = discover(&server, &dave, sorted(LobbySort::Newest)).await;
+        assert_eq!(ids(&page), vec![&books, &go, &rust]);
+
+        // Name and topic search, topic filter
+        let page = discover(
+            &server,
+            &dave,
+            DiscoverQuery {
+                q: Some("book".into()),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(ids(&page), vec![&books]);
+        let page = discover(
+            &server,
+            &dave,
+            DiscoverQuery {
+                topic: Some("Programming".into()),
+                ..Default::default()
+            },
+        )
+        .await;
+        assert_eq!(ids(&page), vec![&rust, &go]);
+
+        // Alice isn't banned anywhere and also sees her private lobby
+        let page = discover(&server, &alice, sorted(LobbySort::Members)).await;
+        assert_eq!(page.items.len(), 5);
+        assert!(page.items[0].is_member);
+
+        LobbyMemberService::join_lobby(&db, go.clone(), dave.clone())
+            .await
+            .unwrap();
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = discover(
+                &server,
+                &dave,
+                DiscoverQuery {
+                    limit: Some(2),
+                    cursor: cursor.take(),
+                    ..Default::default()
+                },
+            )
+            .await;
+            seen.extend(page.items);
+            if !page.has_more {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        // Go and the book nook are tied on members, the newer one comes first
+        let seen_ids: Vec<&str> = seen.iter().map(|item| item.lobby.ulid.as_str()).collect();
+        assert_eq!(seen_ids, vec![&rust, &books, &go]);
+        assert!(seen[2].is_member);
+        assert_eq!(seen[2].members_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_discovery_rejects_foreign_cursors_and_lists_topics() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        for (name, topic) in [("Rust club", "Programming"), ("Go corner", "Programming")] {
+            create_lobby(&db, &alice, name, topic, LobbyVisibility::Public, &[]).await;
+        }
+        create_lobby(
+            &db,
+            &alice,
+            "Book nook",
+            "Reading",
+            LobbyVisibility::Public,
+            &[],
+        )
+        .await;
+        create_lobby(
+            &db,
+            &alice,
+            "Secret garden",
+            "Gardening",
+            LobbyVisibility::Private,
+            &[],
+        )
+        .await;
+
+        let topics = server
+            .get(&LobbyEndpoints::GetTopics.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&bob))
+            .await
+            .json::<Vec<TopicCount>>();
+        let topics: Vec<(&str, u32)> = topics
+            .iter()
+            .map(|topic| (topic.topic.as_str(), topic.lobbies_count))
+            .collect();
+        assert_eq!(topics, vec![("Programming", 2), ("Reading", 1)]);
+
+        let page = discover(
+            &server,
+            &bob,
+            DiscoverQuery {
+                limit: Some(1),
+                ..Default::default()
+            },
+        )
+        .await;
+        let members_cursor = page.next_cursor.unwrap();
+
+        for (sort, cursor) in [
+            ("newest", members_cursor.as_str()),
+            ("members", "garbage"),
+            ("activity", "1-01ARZ3NDEKTSV4RRFFQ69G5FAV"),
+        ] {
+            server
+                .get(&LobbyEndpoints::Discover.path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(&bob))
+                .add_query_param("sort", sort)
+                .add_query_param("cursor", cursor)
+                .await
+                .assert_status_bad_request();
+        }
+    }
+}
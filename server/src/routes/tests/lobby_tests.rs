@@ -1,44 +1,35 @@
 #[cfg(test)]
 mod tests {
     use axum_test::TestServer;
-    use migration::{Migrator, MigratorTrait};
-    use sea_orm::{ConnectOptions, Database};
     use serde_json::json;
-    use std::collections::HashMap;
-    use std::sync::Arc;
-    use tokio::sync::Mutex;
+    use tokio::sync::broadcast;
     use ulid::Ulid;
 
     use crate::AppState;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
     use crate::routes::lobby_routes::lobby_router;
+    use crate::routes::tests::support::{bearer, test_state};
+    use crate::service::{
+        lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+        user_service::UserService,
+    };
     use shared::{
         endpoints::{API, LobbyUlid, UserUlid, lobby_endpoints::LobbyEndpoints},
-        models::lobby_dto::{LobbyDetailsResponse, LobbyDto, LobbyFeedResponse, LobbyMemberDto},
+        models::{
+            lobby_dto::{
+                BanMemberRequest, LobbyBanDto, LobbyDetailsResponse, LobbyDto, LobbyFeedResponse,
+                LobbyMemberDto, LobbyVisibility, Role, SetRoleRequest, TransferOwnershipRequest,
+            },
+            message_dto::{LobbyEvent, LobbyUserDto},
+        },
+        moderation::TextFilter,
+        utils::jwt::create_access_token,
     };
 
     async fn setup_test_server() -> TestServer {
-        let mut opt = ConnectOptions::new("sqlite::memory:");
-        opt.sqlx_logging(false);
-        let connection = Database::connect(opt)
-            .await
-            .expect("Failed to connect to test DB");
-
-        Migrator::up(&connection, None)
-            .await
-            .expect("Failed to run migrations");
-
-        let state = AppState {
-            connection,
-            s3_manager: crate::file_storage::s3_client::S3Manager::new(
-                "test-bucket".into(),
-                "mock-endpoint".to_string(),
-                "mock-region".to_string(),
-            )
-            .await,
-            lobby_channels: Arc::new(Mutex::new(HashMap::new())),
-        };
-
-        let app = lobby_router().with_state(state);
+        let app = lobby_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(test_state().await);
         TestServer::new(app).expect("Failed to create test server")
     }
 
@@ -47,14 +38,19 @@ mod tests {
         let server = setup_test_server().await;
         let owner_id = Ulid::new().to_string();
 
+        // The owner is whoever sends the request, an owner in the body is ignored
         let create_payload = json!({
             "name": "Rust Enthusiasts",
             "topic": "Programming",
             "description": "A place for Rustaceans",
-            "owner_id": owner_id
+            "owner_id": Ulid::new().to_string()
         });
 
-        let response = server.post("/lobbies").json(&create_payload).await;
+        let response = server
+            .post("/lobbies")
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner_id))
+            .json(&create_payload)
+            .await;
         response.assert_status_success();
 
         let created_lobby: LobbyDto = response.json();
@@ -62,7 +58,10 @@ mod tests {
         assert_eq!(created_lobby.owner_id, owner_id);
 
         let details_url = format!("/lobbies/{}", created_lobby.ulid);
-        let details_response = server.get(&details_url).await;
+        let details_response = server
+            .get(&details_url)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner_id))
+            .await;
         details_response.assert_status_success();
 
         let details: LobbyDetailsResponse = details_response.json();
@@ -78,24 +77,36 @@ mod tests {
 
         let create_res = server
             .post("/lobbies")
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner_id))
             .json(&json!({
                 "name": "Join Test",
-                "topic": "Tests",
-                "owner_id": owner_id
+                "topic": "Tests"
             }))
             .await;
         let lobby: LobbyDto = create_res.json();
 
         let join_url =
             LobbyEndpoints::Join(LobbyUlid(lobby.ulid.clone()), UserUlid(user_id.clone()));
-        let join_res = server.post(&join_url.path()).await;
+        // Only the user in the token can join
+        server
+            .post(&join_url.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner_id))
+            .await
+            .assert_status_bad_request();
+        let join_res = server
+            .post(&join_url.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_id))
+            .await;
         join_res.assert_status_success();
 
         let membership: LobbyMemberDto = join_res.json();
         assert_eq!(membership.user_id, user_id);
 
         let count_url = LobbyEndpoints::GetMembersCount(LobbyUlid(lobby.ulid));
-        let count_res = server.get(&count_url.path()).await;
+        let count_res = server
+            .get(&count_url.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_id))
+            .await;
         let count: u32 = count_res.json();
         assert_eq!(count, 2);
     }
@@ -108,24 +119,32 @@ mod tests {
 
         server
             .post("/lobbies")
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_a))
             .json(&json!({
                 "name": "Lobby A",
-                "topic": "Topic A",
-                "owner_id": user_a
+                "topic": "Topic A"
             }))
             .await;
 
         server
             .post("/lobbies")
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_b))
             .json(&json!({
                 "name": "Lobby B",
-                "topic": "Topic B",
-                "owner_id": user_b
+                "topic": "Topic B"
             }))
             .await;
 
         let feed_url = format!("/lobbies/feed/{}", user_a);
-        let response = server.get(&feed_url).await;
+        server
+            .get(&feed_url)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_b))
+            .await
+            .assert_status_bad_request();
+        let response = server
+            .get(&feed_url)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user_a))
+            .await;
         response.assert_status_success();
 
         let feed: LobbyFeedResponse = response.json();
@@ -150,13 +169,17 @@ mod tests {
     async fn test_create_lobby_validation() {
         let server = setup_test_server().await;
 
+        let owner_id = Ulid::new().to_string();
         let bad_payload = json!({
             "name": "lo",
-            "topic": "valid_topic",
-            "owner_id": Ulid::new().to_string()
+            "topic": "valid_topic"
         });
 
-        let response = server.post("/lobbies").json(&bad_payload).await;
+        let response = server
+            .post("/lobbies")
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner_id))
+            .json(&bad_payload)
+            .await;
         response.assert_status_bad_request();
     }
 
@@ -168,7 +191,261 @@ mod tests {
 
         let response = server
             .post(&format!("/lobbies/{}/join/{}", fake_lobby, user))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user))
             .await;
         response.assert_status_not_found();
     }
+
+    #[tokio::test]
+    async fn test_online_members_follow_live_sockets() {
+        let state = test_state().await;
+        let db = &state.connection;
+        let mut users = Vec::new();
+        for name in ["night_owl", "early_bird", "outsider"] {
+            let user = UserService::create_user(
+                db,
+                name.into(),
+                format!("{name}@test.com"),
+                "password123".into(),
+            )
+            .await
+            .unwrap();
+            users.push(user.ulid);
+        }
+        let (owner, friend, outsider) = (&users[0], &users[1], &users[2]);
+
+        let lobby_id = LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            owner.clone(),
+            "Night owls".into(),
+            "Chat".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap()
+        .ulid;
+        LobbyMemberService::join_lobby(db, lobby_id.clone(), friend.clone())
+            .await
+            .unwrap();
+
+        // The friend has the app open on two devices and closes one of them
+        state.presence.connect(&lobby_id, owner);
+        state.presence.connect(&lobby_id, friend);
+        state.presence.connect(&lobby_id, friend);
+        state.presence.disconnect(&lobby_id, friend);
+
+        let app = lobby_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state.clone());
+        let server = TestServer::new(app).expect("Failed to create test server");
+        let path = LobbyEndpoints::GetOnlineMembers(LobbyUlid(lobby_id.clone())).path();
+        let bearer = |user_id: &str| {
+            let token = create_access_token(user_id.to_owned(), "test@test.com".into()).unwrap();
+            format!("Bearer {token}")
+        };
+
+        let online = server
+            .get(&path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(owner))
+            .await
+            .json::<Vec<LobbyUserDto>>();
+        let names: Vec<&str> = online.iter().map(|u| u.username.as_str()).collect();
+        assert_eq!(names, vec!["early_bird", "night_owl"]);
+
+        state.presence.disconnect(&lobby_id, friend);
+        let online = server
+            .get(&path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(owner))
+            .await
+            .json::<Vec<LobbyUserDto>>();
+        assert_eq!(online.len(), 1);
+
+        server
+            .get(&path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(outsider))
+            .await
+            .assert_status_not_found();
+    }
+
+    async fn lobby_with_users(
+        state: &AppState,
+        names: &[&str],
+    ) -> (TestServer, String, Vec<String>) {
+        let db = &state.connection;
+        let mut users = Vec::new();
+        for name in names {
+            let user = UserService::create_user(
+                db,
+                (*name).into(),
+                format!("{name}@test.com"),
+                "password123".into(),
+            )
+            .await
+            .unwrap();
+            users.push(user.ulid);
+        }
+
+        // The first user owns the lobby, everyone else joins it
+        let lobby_id = LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            users[0].clone(),
+            "Night owls".into(),
+            "Chat".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap()
+        .ulid;
+        for user in &users[1..] {
+            LobbyMemberService::join_lobby(db, lobby_id.clone(), user.clone())
+                .await
+                .unwrap();
+        }
+
+        let app = lobby_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state.clone());
+        let server = TestServer::new(app).expect("Failed to create test server");
+
+        (server, lobby_id, users)
+    }
+
+    #[tokio::test]
+    async fn test_roles_kicks_and_ownership() {
+        let state = test_state().await;
+        let (server, lobby_id, users) =
+            lobby_with_users(&state, &["owner", "moderator", "member"]).await;
+        let (owner, moderator, member) = (&users[0], &users[1], &users[2]);
+        let lobby = || LobbyUlid(lobby_id.clone());
+        let user = |id: &str| UserUlid(id.to_owned());
+
+        let mut events = {
+            let (tx, rx) = broadcast::channel(16);
+            state
+                .lobby_channels
+                .lock()
+                .await
+                .insert(lobby_id.clone(), tx);
+            rx
+        };
+
+        let set_role = |actor: &str, target: &str, role: Role| {
+            server
+                .put(&LobbyEndpoints::SetRole(lobby(), user(target)).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(actor))
+                .json(&SetRoleRequest { role })
+        };
+
+        let promoted: LobbyMemberDto = set_role(owner, moderator, Role::Moderator).await.json();
+        assert_eq!(promoted.role, Role::Moderator);
+        assert!(matches!(
+            events.recv().await.unwrap().event,
+            LobbyEvent::RoleChanged {
+                role: Role::Moderator,
+                ..
+            }
+        ));
+
+        // Moderators can't hand out roles, and admins can't make admins
+        set_role(moderator, member, Role::Helper)
+            .await
+            .assert_status_bad_request();
+
+        let kick = |actor: &str, target: &str| {
+            server
+                .delete(&LobbyEndpoints::KickMember(lobby(), user(target)).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(actor))
+        };
+        kick(moderator, owner).await.assert_status_bad_request();
+        kick(moderator, member).await.assert_status_success();
+        assert!(matches!(
+            events.recv().await.unwrap().event,
+            LobbyEvent::MemberKicked { ref user_id, .. } if user_id == member
+        ));
+        kick(moderator, member).await.assert_status_not_found();
+
+        let leave = |actor: &str| {
+            server
+                .post(&LobbyEndpoints::Leave(lobby()).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(actor))
+        };
+        leave(owner).await.assert_status_bad_request();
+
+        let transferred: LobbyDto = server
+            .post(&LobbyEndpoints::TransferOwnership(lobby()).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(owner))
+            .json(&TransferOwnershipRequest {
+                user_id: moderator.clone(),
+            })
+            .await
+            .json();
+        assert_eq!(&transferred.owner_id, moderator);
+
+        // The previous owner stays an admin and is free to leave now
+        leave(owner).await.assert_status_success();
+        leave(moderator).await.assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_bans_keep_users_out_until_lifted() {
+        let state = test_state().await;
+        let (server, lobby_id, users) = lobby_with_users(&state, &["owner", "troll"]).await;
+        let (owner, troll) = (&users[0], &users[1]);
+        let ban_path = LobbyEndpoints::Ban(LobbyUlid(lobby_id.clone()), UserUlid(troll.clone()));
+        let join_path = LobbyEndpoints::Join(LobbyUlid(lobby_id.clone()), UserUlid(troll.clone()));
+
+        let ban: LobbyBanDto = server
+            .put(&ban_path.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(owner))
+            .json(&BanMemberRequest {
+                duration_hours: Some(24),
+                reason: Some("Spam".into()),
+            })
+            .await
+            .json();
+        assert!(ban.expires_at.is_some());
+        assert!(!LobbyMemberService::is_member(&state.connection, &lobby_id, troll).await);
+
+        server
+            .post(&join_path.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(troll))
+            .await
+            .assert_status_bad_request();
+
+        // Members can't lift bans
+        server
+            .delete(&ban_path.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(troll))
+            .await
+            .assert_status_bad_request();
+        server
+            .delete(&ban_path.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(owner))
+            .await
+            .assert_status_success();
+        server
+            .post(&join_path.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(troll))
+            .await
+            .assert_status_success();
+
+        // An expired ban doesn't count
+        LobbyMemberService::ban_member(
+            &state.connection,
+            &lobby_id,
+            owner,
+            troll,
+            Some(chrono::Duration::hours(-1)),
+            None,
+        )
+        .await
+        .unwrap();
+        LobbyMemberService::join_lobby(&state.connection, lobby_id.clone(), troll.clone())
+            .await
+            .unwrap();
+    }
 }
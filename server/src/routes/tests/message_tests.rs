@@ -0,0 +1,651 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::DatabaseConnection;
+    use tokio::sync::broadcast;
+
+    use crate::events::{DomainEvent, EventBus};
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::message_routes::{handle_command, message_router};
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::{
+        lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+        message_service::MessageService,
+    };
+    use shared::{
+        endpoints::{
+            API, LobbyUlid,
+            message_endpoints::{
+                EditMessageRequest, MarkReadRequest, MessageEndpoints, SendMessageRequest,
+                SyncQuery,
+            },
+        },
+        models::{
+            Page,
+            lobby_dto::LobbyVisibility,
+            message_dto::{CommandReply, LobbyEvent, MessageDto},
+        },
+        moderation::TextFilter,
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = message_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_lobby(db: &DatabaseConnection, owner: &str) -> String {
+        LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            owner.to_owned(),
+            "Night owls".into(),
+            "Chat".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    fn messages_path(lobby_id: &str) -> String {
+        MessageEndpoints::SendMessage(LobbyUlid(lobby_id.to_owned())).path()
+    }
+
+    async fn send(server: &TestServer, lobby_id: &str, author: &str, content: &str) -> MessageDto {
+        let response = server
+            .post(&messages_path(lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(author))
+            .json(&SendMessageRequest {
+                content: content.into(),
+                reply_to: None,
+            })
+            .await;
+        response.assert_status_success();
+        response.json::<MessageDto>()
+    }
+
+    #[tokio::test]
+    async fn test_send_and_page_through_messages() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "night_owl").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+
+        let first = send(&server, &lobby_id, &owner, "Anyone awake?").await;
+        assert_eq!(first.author_id, owner);
+        assert_eq!(first.author_name, "night_owl");
+        let second = send(&server, &lobby_id, &owner, "Guess not").await;
+
+        let page = server
+            .get(&messages_path(&lobby_id))
+            .add_query_param("limit", 1)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].ulid, second.ulid);
+        assert_eq!(page.items[0].author_name, "night_owl");
+        assert!(page.has_more);
+
+        let older = server
+            .get(&messages_path(&lobby_id))
+            .add_query_param("limit", 1)
+            .add_query_param("cursor", page.next_cursor.unwrap())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        assert_eq!(older.items[0].ulid, first.ulid);
+        assert!(!older.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_sync_returns_missed_messages_oldest_first() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "night_owl").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+
+        let mut sent = Vec::new();
+        for content in ["one", "two", "three", "four"] {
+            sent.push(send(&server, &lobby_id, &owner, content).await.ulid);
+        }
+        let sync_path = MessageEndpoints::SyncMessages(LobbyUlid(lobby_id.clone())).path();
+
+        let page = server
+            .get(&sync_path)
+            .add_query_params(SyncQuery {
+                since: sent[0].clone(),
+                limit: Some(2),
+            })
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        let contents: Vec<&str> = page.items.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(contents, vec!["two", "three"]);
+        assert!(page.has_more);
+
+        let rest = server
+            .get(&sync_path)
+            .add_query_params(SyncQuery {
+                since: page.next_cursor.unwrap(),
+                limit: Some(2),
+            })
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        assert_eq!(rest.items.len(), 1);
+        assert_eq!(rest.items[0].ulid, sent[3]);
+        assert!(!rest.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_message_ids_follow_commit_order() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "night_owl").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+        let other_lobby = create_lobby(&db, &owner).await;
+
+        // Another send of the lobby is still writing
+        let lobby_write = MessageService::lobby_write_lock(&lobby_id);
+        let in_flight = lobby_write.lock().await;
+        let held_up = tokio::spawn({
+            let (db, lobby_id, owner) = (db.clone(), lobby_id.clone(), owner.clone());
+            async move {
+                MessageService::send_message(
+                    &db,
+                    &EventBus::new(),
+                    &TextFilter::default(),
+                    lobby_id,
+                    owner,
+                    "Later".into(),
+                    None,
+                )
+                .await
+                .unwrap()
+            }
+        });
+
+        // Other lobbies aren't held up
+        let elsewhere = send(&server, &other_lobby, &owner, "Elsewhere").await;
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(!held_up.is_finished());
+
+        // The waiting send only takes its ID once the one in flight committed, so it can't
+        // commit below a message a client may already have synced past
+        drop(in_flight);
+        let later = held_up.await.unwrap();
+        assert!(later.ulid > elsewhere.ulid);
+
+        let synced = server
+            .get(&MessageEndpoints::SyncMessages(LobbyUlid(lobby_id.clone())).path())
+            .add_query_param("since", &elsewhere.ulid)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        let ids: Vec<&str> = synced.items.iter().map(|m| m.ulid.as_str()).collect();
+        assert_eq!(ids, vec![later.ulid.as_str()]);
+    }
+
+    #[tokio::test]
+    async fn test_mark_read_drives_unread_counts() {
+        let state = test_state().await;
+        let db = state.connection.clone();
+        let owner = create_user(&db, "night_owl").await;
+        let reader = create_user(&db, "early_bird").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+        let (tx, mut rx) = broadcast::channel(16);
+        state
+            .lobby_channels
+            .lock()
+            .await
+            .insert(lobby_id.clone(), tx);
+        let server = TestServer::new(
+            message_router()
+                .layer(axum::middleware::from_fn(check_access_token))
+                .with_state(state.clone()),
+        )
+        .unwrap();
+
+        send(&server, &lobby_id, &owner, "Before you came").await;
+        // History from before joining isn't unread
+        LobbyMemberService::join_lobby(&db, lobby_id.clone(), reader.clone())
+            .await
+            .unwrap();
+        let mut sent = Vec::new();
+        for content in ["one", "two", "three"] {
+            sent.push(send(&server, &lobby_id, &owner, content).await.ulid);
+        }
+        while rx.try_recv().is_ok() {}
+
+        let unread = MessageService::unread_counts(&db, &reader, 3)
+            .await
+            .unwrap();
+        assert_eq!(unread.get(&lobby_id), Some(&3));
+        // Own messages never count
+        let unread = MessageService::unread_counts(&db, &owner, 3).await.unwrap();
+        assert!(unread.is_empty());
+
+        let read_path = MessageEndpoints::MarkRead(LobbyUlid(lobby_id.clone())).path();
+        server
+            .post(&read_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&reader))
+            .json(&MarkReadRequest {
+                message_id: sent[1].clone(),
+                silent: false,
+            })
+            .await
+            .assert_status_success();
+
+        let unread = MessageService::unread_counts(&db, &reader, 3)
+            .await
+            .unwrap();
+        assert_eq!(unread.get(&lobby_id), Some(&1));
+        match rx.try_recv().unwrap().event {
+            LobbyEvent::ReadReceipt {
+                user_id,
+                message_id,
+            } => {
+                assert_eq!(user_id, reader);
+                assert_eq!(message_id, sent[1]);
+            }
+            event => panic!("Unexpected event {event:?}"),
+        }
+
+        // An older message doesn't move the marker back and sends no receipt
+        server
+            .post(&read_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&reader))
+            .json(&MarkReadRequest {
+                message_id: sent[0].clone(),
+                silent: false,
+            })
+            .await
+            .assert_status_success();
+        let unread = MessageService::unread_counts(&db, &reader, 3)
+            .await
+            .unwrap();
+        assert_eq!(unread.get(&lobby_id), Some(&1));
+        assert!(rx.try_recv().is_err());
+
+        server
+            .post(&read_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&reader))
+            .json(&MarkReadRequest {
+                message_id: sent[2].clone(),
+                silent: true,
+            })
+            .await
+            .assert_status_success();
+        let unread = MessageService::unread_counts(&db, &reader, 3)
+            .await
+            .unwrap();
+        assert!(unread.is_empty());
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_outsiders_cannot_read_or_post() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "night_owl").await;
+        let outsider = create_user(&db, "outsider").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+
+        server
+            .get(&messages_path(&lobby_id))
+            .await
+            .assert_status_bad_request();
+        server
+            .get(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, "Bearer invalid-token")
+            .await
+            .assert_status_unauthorized();
+
+        server
+            .get(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&outsider))
+            .await
+            .assert_status_not_found();
+
+        server
+            .post(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&outsider))
+            .json(&SendMessageRequest {
+                content: "Let me in".into(),
+                reply_to: None,
+            })
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_only_author_or_lobby_admin_can_delete() {
+        let (server, db) = setup_test_server().await;
+        let owner = create_user(&db, "night_owl").await;
+        let author = create_user(&db, "early_bird").await;
+        let other = create_user(&db, "bystander").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+        for user in [&author, &other] {
+            LobbyMemberService::join_lobby(&db, lobby_id.clone(), user.clone())
+                .await
+                .unwrap();
+        }
+
+        let message = send(&server, &lobby_id, &author, "Good morning").await;
+        let delete_path =
+            MessageEndpoints::DeleteMessage(LobbyUlid(lobby_id.clone()), message.ulid).path();
+
+        server
+            .delete(&delete_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&other))
+            .await
+            .assert_status_not_found();
+
+        server
+            .delete(&delete_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_success();
+
+        // Soft delete: the placeholder stays in the history without its text
+        let page = server
+            .get(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .json::<Page<MessageDto>>();
+        assert_eq!(page.items.len(), 1);
+        assert!(page.items[0].deleted);
+        assert!(page.items[0].content.is_empty());
+
+        server
+            .delete(&delete_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_replies_edits_and_mentions() {
+        let state = test_state().await;
+        let db = state.connection.clone();
+        let mut domain_events = state.events.subscribe();
+        let owner = create_user(&db, "night_owl").await;
+        let member = create_user(&db, "early_bird").await;
+        create_user(&db, "outsider").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+        LobbyMemberService::join_lobby(&db, lobby_id.clone(), member.clone())
+            .await
+            .unwrap();
+        let server = TestServer::new(
+            message_router()
+                .layer(axum::middleware::from_fn(check_access_token))
+                .with_state(state),
+        )
+        .unwrap();
+
+        let question = send(&server, &lobby_id, &owner, "Who's up?").await;
+        // Outsiders and unknown names aren't mentions
+        let reply = server
+            .post(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .json(&SendMessageRequest {
+                content: "@outsider @nobody1 you there?".into(),
+                reply_to: Some(question.ulid.clone()),
+            })
+            .await
+            .json::<MessageDto>();
+        assert_eq!(reply.reply_to_id, Some(question.ulid.clone()));
+        assert!(reply.mentions.is_empty());
+        assert!(domain_events.try_recv().is_err());
+
+        let edit_path =
+            MessageEndpoints::EditMessage(LobbyUlid(lobby_id.clone()), reply.ulid.clone()).path();
+        server
+            .patch(&edit_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&member))
+            .json(&EditMessageRequest {
+                content: "Not yours".into(),
+            })
+            .await
+            .assert_status_not_found();
+
+        let edited = server
+            .patch(&edit_path)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .json(&EditMessageRequest {
+                content: "@early_bird you there?".into(),
+            })
+            .await
+            .json::<MessageDto>();
+        assert!(edited.edited_at.is_some());
+        assert_eq!(edited.mentions, vec![member.clone()]);
+        assert!(matches!(
+            domain_events.try_recv().unwrap(),
+            DomainEvent::UserMentioned { user_id, message_id, .. }
+                if user_id == member && message_id == reply.ulid
+        ));
+
+        // Replies keep pointing at a deleted parent
+        server
+            .delete(
+                &MessageEndpoints::DeleteMessage(
+                    LobbyUlid(lobby_id.clone()),
+                    question.ulid.clone(),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .assert_status_success();
+        let page = server
+            .get(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&member))
+            .await
+            .json::<Page<MessageDto>>();
+        assert_eq!(page.items[0].reply_to_id, Some(question.ulid.clone()));
+        assert_eq!(page.items[0].mentions, vec![member.clone()]);
+        assert!(page.items[1].deleted);
+
+        // ...but new replies to it are refused
+        server
+            .post(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&member))
+            .json(&SendMessageRequest {
+                content: "Too late".into(),
+                reply_to: Some(question.ulid),
+            })
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_reactions_are_unique_per_user() {
+        let state = test_state().await;
+        let db = state.connection.clone();
+        let owner = create_user(&db, "night_owl").await;
+        let member = create_user(&db, "early_bird").await;
+        let lobby_id = create_lobby(&db, &owner).await;
+        LobbyMemberService::join_lobby(&db, lobby_id.clone(), member.clone())
+            .await
+            .unwrap();
+        let (tx, mut rx) = broadcast::channel(16);
+        state
+            .lobby_channels
+            .lock()
+            .await
+            .insert(lobby_id.clone(), tx);
+        let server = TestServer::new(
+            message_router()
+                .layer(axum::middleware::from_fn(check_access_token))
+                .with_state(state),
+        )
+        .unwrap();
+
+        let message = send(&server, &lobby_id, &owner, "Made it to the meetup").await;
+        let _ = rx.try_recv();
+        let react = |user: &str, emoji: &str| {
+            let path = MessageEndpoints::AddReaction(
+                LobbyUlid(lobby_id.clone()),
+                message.ulid.clone(),
+                emoji.to_owned(),
+            )
+            .path();
+            server
+                .put(&path)
+                .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+        };
+
+        for user in [&member, &member, &owner] {
+            react(user, "🎉").await.assert_status_success();
+        }
+        react(&member, "👍").await.assert_status_success();
+        react(&member, "nice").await.assert_status_bad_request();
+
+        let page = server
+            .get(&messages_path(&lobby_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&owner))
+            .await
+            .json::<Page<MessageDto>>();
+        let reactions = &page.items[0].reactions;
+        assert_eq!(reactions.len(), 2);
+        assert_eq!(reactions[0].emoji, "🎉");
+        assert_eq!(reactions[0].user_ids, vec![member.clone(), owner.clone()]);
+        assert_eq!(reactions[1].user_ids, vec![member.clone()]);
+
+        // The repeated reaction wasn't broadcast a second time
+        let added: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(added.len(), 3);
+        assert!(
+            added
+                .iter()
+                .all(|frame| matches!(frame.event, LobbyEvent::ReactionAdded(_)))
+        );
+
+        server
+            .delete(
+                &MessageEndpoints::RemoveReaction(
+                    LobbyUlid(lobby_id.clone()),
+                    message.ulid.clone(),
+                    "🎉".into(),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&member))
+            .await
+            .assert_status_success();
+        assert!(matches!(
+            rx.try_recv().unwrap().event,
+            LobbyEvent::ReactionRemoved(change) if change.user_id == member && change.emoji == "🎉"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_socket_send_is_persisted_and_broadcast() {
+        let state = test_state().await;
+        let owner = create_user(&state.connection, "night_owl").await;
+        let lobby_id = create_lobby(&state.connection, &owner).await;
+        let (tx, mut rx) = broadcast::channel(16);
+        let mut last_acked = None;
+
+        let reply = handle_command(
+            &state,
+            &tx,
+            &lobby_id,
+            &owner,
+            &mut last_acked,
+            r#"{"type":"SendMessage","data":{"content":"Anyone awake?","client_id":"c-1"}}"#,
+        )
+        .await;
+        let Some(CommandReply::Sent {
+            client_id,
+            message_id,
+        }) = reply
+        else {
+            panic!("Expected the message to be sent");
+        };
+        assert_eq!(client_id.as_deref(), Some("c-1"));
+
+        let frame = rx.try_recv().unwrap();
+        // The event ID of a new message is the message's own ULID
+        assert_eq!(frame.id, message_id);
+        match frame.event {
+            LobbyEvent::MessageCreated(message) => {
+                assert_eq!(message.ulid, message_id);
+                assert_eq!(message.author_name, "night_owl");
+            }
+            event => panic!("Unexpected event {event:?}"),
+        }
+
+        let history = crate::service::message_service::MessageService::get_lobby_messages(
+            &state.connection,
+            &lobby_id,
+            &owner,
+            20,
+            None,
+            state.report_config.hide_threshold,
+        )
+        .await
+        .unwrap();
+        assert_eq!(history.items[0].ulid, message_id);
+
+        let reply = handle_command(
+            &state,
+            &tx,
+            &lobby_id,
+            &owner,
+            &mut last_acked,
+            r#"{"type":"Typing"}"#,
+        )
+        .await;
+        assert!(reply.is_none());
+        let frame = rx.try_recv().unwrap();
+        assert!(matches!(frame.event, LobbyEvent::Typing { .. }));
+        assert!(frame.id > message_id);
+
+        handle_command(
+            &state,
+            &tx,
+            &lobby_id,
+            &owner,
+            &mut last_acked,
+            &format!(r#"{{"type":"Ack","data":{{"message_id":"{message_id}"}}}}"#),
+        )
+        .await;
+        assert_eq!(last_acked, Some(message_id));
+    }
+
+    #[tokio::test]
+    async fn test_socket_rejections_only_reach_the_sender() {
+        let state = test_state().await;
+        let owner = create_user(&state.connection, "night_owl").await;
+        let outsider = create_user(&state.connection, "outsider").await;
+        let lobby_id = create_lobby(&state.connection, &owner).await;
+        let (tx, mut rx) = broadcast::channel(16);
+
+        let reply = handle_command(
+            &state,
+            &tx,
+            &lobby_id,
+            &outsider,
+            &mut None,
+            r#"{"type":"SendMessage","data":{"content":"Let me in","client_id":"c-2"}}"#,
+        )
+        .await;
+        assert!(matches!(
+            reply,
+            Some(CommandReply::Rejected { client_id: Some(ref id), .. }) if id == "c-2"
+        ));
+
+        let reply = handle_command(&state, &tx, &lobby_id, &owner, &mut None, "not json").await;
+        assert!(matches!(reply, Some(CommandReply::Rejected { .. })));
+
+        assert!(rx.try_recv().is_err());
+    }
+}
@@ -1,2 +1,78 @@
+pub mod follow_tests;
+pub mod lobby_access_tests;
+pub mod lobby_discovery_tests;
 pub mod lobby_tests;
+pub mod message_tests;
+pub mod proof_upload_tests;
+pub mod proof_visibility_tests;
+pub mod quest_proof_tests;
+pub mod report_tests;
+pub mod search_tests;
+pub mod share_tests;
 pub mod user_tests;
+
+// Shared by the route tests, every new AppState field only has to be added here
+#[cfg(test)]
+pub mod support {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectOptions, Database, DatabaseConnection};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    use crate::AppState;
+    use crate::service::user_service::UserService;
+    use shared::utils::jwt::create_access_token;
+
+    // Default configuration over a fresh in-memory database with every migration applied
+    pub async fn test_state() -> AppState {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.sqlx_logging(false);
+        let connection = Database::connect(opt)
+            .await
+            .expect("Failed to connect to test DB");
+
+        Migrator::up(&connection, None)
+            .await
+            .expect("Failed to run migrations");
+
+        AppState {
+            connection,
+            s3_manager: crate::file_storage::s3_client::S3Manager::new(
+                "test-bucket".into(),
+                "http://localhost:9000".to_string(),
+                "mock-region".to_string(),
+            )
+            .await
+            .with_credentials(aws_credential_types::Credentials::new(
+                "AKID", "SECRET", None, None, "test",
+            )),
+            report_config: crate::service::report_service::ReportConfig::default(),
+            feed_ranking: crate::service::feed_ranking::FeedRankingConfig::default(),
+            belief_config: crate::service::quest_proof_beliefs_service::BeliefConfig::default(),
+            text_filter: crate::service::moderation_service::ModerationConfig::default()
+                .build_filter(),
+            rate_limiter: crate::service::rate_limiter::RateLimiter::new(),
+            presence: crate::service::presence::PresenceRegistry::new(),
+            events: crate::events::EventBus::new(),
+            lobby_channels: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn create_user(db: &DatabaseConnection, name: &str) -> String {
+        UserService::create_user(
+            db,
+            name.into(),
+            format!("{name}@test.com"),
+            "password123".into(),
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    pub fn bearer(user_id: &str) -> String {
+        let token = create_access_token(user_id.to_owned(), "test@test.com".into()).unwrap();
+        format!("Bearer {token}")
+    }
+}
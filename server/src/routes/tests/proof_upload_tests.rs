@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::DatabaseConnection;
+
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::quest_proof_routes::quest_proof_router;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::quest_service::QuestService;
+    use shared::{
+        endpoints::{
+            API, QuestProofUlid, QuestUlid, UserUlid, quest_proof_endpoints::QuestProofEndpoints,
+        },
+        models::{
+            quest_dto::Complexity,
+            quest_proof_dto::{ProofVisibility, SubmitProofRequest, SubmitProofResponse},
+        },
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = quest_proof_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn init_proof(server: &TestServer, db: &DatabaseConnection, author: &str) -> String {
+        let quest = QuestService::create_quest(
+            db,
+            "Mirror Talk".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let response = server
+            .post(
+                &QuestProofEndpoints::InitSubmission(
+                    UserUlid(author.to_owned()),
+                    QuestUlid(quest.ulid),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(author))
+            .json(&SubmitProofRequest {
+                proof_text: Some("Done".into()),
+                photo_count: 0,
+                voice_count: 0,
+                visibility: ProofVisibility::Public,
+            })
+            .await;
+        response.assert_status_success();
+        response.json::<SubmitProofResponse>().proof_ulid
+    }
+
+    async fn refresh(
+        server: &TestServer,
+        proof_id: &str,
+        user_id: &str,
+    ) -> axum_test::TestResponse {
+        server
+            .post(
+                &QuestProofEndpoints::RefreshUploadUrls(QuestProofUlid(proof_id.to_owned())).path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user_id))
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_upload_urls_refresh_only_for_author() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let stranger = create_user(&db, "stranger").await;
+        let proof_id = init_proof(&server, &db, &author).await;
+
+        let response = refresh(&server, &proof_id, &author).await;
+        response.assert_status_success();
+        let body = response.json::<SubmitProofResponse>();
+        assert_eq!(body.proof_ulid, proof_id);
+        assert_eq!(body.status, "Uploading");
+
+        refresh(&server, &proof_id, &stranger)
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_upload_urls_are_not_reissued_after_confirm() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let proof_id = init_proof(&server, &db, &author).await;
+
+        server
+            .post(&QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(proof_id.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .assert_status_success();
+
+        refresh(&server, &proof_id, &author)
+            .await
+            .assert_status_bad_request();
+    }
+}
@@ -0,0 +1,334 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::{DatabaseConnection, EntityTrait, PaginatorTrait};
+
+    use crate::entities::prelude::QuestProof;
+    use crate::events::EventBus;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::quest_proof_routes::quest_proof_router;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::{
+        follow_service::FollowService, lobby_member_service::LobbyMemberService,
+        lobby_service::LobbyService, moderation_service::ModerationConfig,
+        quest_proof_beliefs_service::BeliefConfig, quest_proof_service::QuestProofService,
+        quest_service::QuestService,
+    };
+    use shared::{
+        endpoints::{
+            API, QuestProofUlid, QuestUlid, UserUlid, quest_proof_endpoints::QuestProofEndpoints,
+        },
+        models::{
+            Page,
+            lobby_dto::LobbyVisibility,
+            quest_dto::Complexity,
+            quest_proof_dto::{
+                ProofDetailsResponse, ProofVisibility, SubmitProofRequest, SubmitProofResponse,
+            },
+        },
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = quest_proof_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_quest(db: &DatabaseConnection, validation_type: &str) -> String {
+        QuestService::create_quest(
+            db,
+            "Mirror Talk".into(),
+            None,
+            10,
+            validation_type.into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    fn request(visibility: ProofVisibility) -> SubmitProofRequest {
+        SubmitProofRequest {
+            proof_text: Some("Done".into()),
+            photo_count: 0,
+            voice_count: 0,
+            visibility,
+        }
+    }
+
+    async fn submit(
+        server: &TestServer,
+        author: &str,
+        quest_id: &str,
+        visibility: ProofVisibility,
+    ) -> axum_test::TestResponse {
+        server
+            .post(
+                &QuestProofEndpoints::InitSubmission(
+                    UserUlid(author.to_owned()),
+                    QuestUlid(quest_id.to_owned()),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(author))
+            .json(&request(visibility))
+            .await
+    }
+
+    async fn journal_page(
+        server: &TestServer,
+        viewer: &str,
+        author: &str,
+    ) -> Page<ProofDetailsResponse> {
+        server
+            .get(&QuestProofEndpoints::GetUserJournal(UserUlid(author.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(viewer))
+            .await
+            .json()
+    }
+
+    async fn journal(server: &TestServer, viewer: &str, author: &str) -> Vec<ProofVisibility> {
+        let page = journal_page(server, viewer, author).await;
+
+        let mut visible: Vec<_> = page.items.into_iter().map(|p| p.visibility).collect();
+        visible.sort_by_key(|v| format!("{v:?}"));
+        visible
+    }
+
+    #[tokio::test]
+    async fn test_private_proof_requires_automatic_validation() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let community_quest = create_quest(&db, "COMMUNITY").await;
+        let automatic_quest = create_quest(&db, "AUTOMATIC").await;
+
+        submit(&server, &author, &community_quest, ProofVisibility::Private)
+            .await
+            .assert_status_bad_request();
+
+        let response = submit(&server, &author, &automatic_quest, ProofVisibility::Private).await;
+        response.assert_status_success();
+        let proof_id = response.json::<SubmitProofResponse>().proof_ulid;
+
+        let details: ProofDetailsResponse = server
+            .get(&QuestProofEndpoints::GetDetails(QuestProofUlid(proof_id)).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .json();
+        assert_eq!(details.visibility, ProofVisibility::Private);
+    }
+
+    #[tokio::test]
+    async fn test_proofs_are_submitted_as_the_caller() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let stranger = create_user(&db, "stranger").await;
+        let quest = create_quest(&db, "AUTOMATIC").await;
+
+        // A token for someone else can't submit in the author's name
+        server
+            .post(
+                &QuestProofEndpoints::InitSubmission(
+                    UserUlid(author.clone()),
+                    QuestUlid(quest.clone()),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+            .json(&request(ProofVisibility::Private))
+            .await
+            .assert_status_bad_request();
+
+        assert_eq!(QuestProof::find().count(&db).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_visibility_is_enforced_for_each_audience() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let follower = create_user(&db, "follower").await;
+        let lobby_peer = create_user(&db, "lobby_peer").await;
+        let stranger = create_user(&db, "stranger").await;
+
+        FollowService::follow(&db, &follower, &author)
+            .await
+            .unwrap();
+        let lobby = LobbyService::create_lobby(
+            &db,
+            &ModerationConfig::default().build_filter(),
+            author.clone(),
+            "Club".into(),
+            "x".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap();
+        LobbyMemberService::join_lobby(&db, lobby.ulid, lobby_peer.clone())
+            .await
+            .unwrap();
+
+        let quest = create_quest(&db, "AUTOMATIC").await;
+        let mut private_id = String::new();
+        for visibility in [
+            ProofVisibility::Public,
+            ProofVisibility::Followers,
+            ProofVisibility::Lobby,
+            ProofVisibility::Private,
+        ] {
+            let response = submit(&server, &author, &quest, visibility).await;
+            response.assert_status_success();
+            private_id = response.json::<SubmitProofResponse>().proof_ulid;
+        }
+
+        assert_eq!(
+            journal(&server, &author, &author).await,
+            vec![
+                ProofVisibility::Followers,
+                ProofVisibility::Lobby,
+                ProofVisibility::Private,
+                ProofVisibility::Public
+            ]
+        );
+        assert_eq!(
+            journal(&server, &follower, &author).await,
+            vec![ProofVisibility::Followers, ProofVisibility::Public]
+        );
+        assert_eq!(
+            journal(&server, &lobby_peer, &author).await,
+            vec![ProofVisibility::Lobby, ProofVisibility::Public]
+        );
+        assert_eq!(
+            journal(&server, &stranger, &author).await,
+            vec![ProofVisibility::Public]
+        );
+
+        let feed: Page<ProofDetailsResponse> = server
+            .get(&QuestProofEndpoints::GetFeed(UserUlid(stranger.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+            .await
+            .json();
+        let feed: Vec<_> = feed.items.into_iter().map(|p| p.visibility).collect();
+        assert_eq!(feed, vec![ProofVisibility::Public]);
+
+        server
+            .get(&QuestProofEndpoints::GetDetails(QuestProofUlid(private_id)).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_journal_reports_the_viewers_beliefs() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let believer = create_user(&db, "believer").await;
+        let stranger = create_user(&db, "stranger").await;
+
+        let quest = create_quest(&db, "COMMUNITY").await;
+        let proof_id = submit(&server, &author, &quest, ProofVisibility::Public)
+            .await
+            .json::<SubmitProofResponse>()
+            .proof_ulid;
+        QuestProofService::toggle_belief(
+            &db,
+            &EventBus::new(),
+            proof_id,
+            believer.clone(),
+            &BeliefConfig::default(),
+        )
+        .await
+        .unwrap();
+
+        let believed = |page: Page<ProofDetailsResponse>| page.items[0].is_believed;
+        assert!(believed(journal_page(&server, &believer, &author).await));
+        assert!(!believed(journal_page(&server, &stranger, &author).await));
+    }
+
+    #[tokio::test]
+    async fn test_feeds_only_serve_the_caller() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let follower = create_user(&db, "follower").await;
+        let stranger = create_user(&db, "stranger").await;
+        FollowService::follow(&db, &follower, &author)
+            .await
+            .unwrap();
+
+        let quest = create_quest(&db, "AUTOMATIC").await;
+        submit(&server, &author, &quest, ProofVisibility::Followers)
+            .await
+            .assert_status_success();
+
+        for endpoint in [
+            QuestProofEndpoints::GetFeed,
+            QuestProofEndpoints::GetFollowingFeed,
+            QuestProofEndpoints::GetRankedFeed,
+        ] {
+            // Asking for the follower's feed with another token is refused
+            server
+                .get(&endpoint(UserUlid(follower.clone())).path())
+                .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+                .await
+                .assert_status_bad_request();
+
+            let visible = |viewer: &str| {
+                server
+                    .get(&endpoint(UserUlid(viewer.to_owned())).path())
+                    .add_header(axum::http::header::AUTHORIZATION, bearer(viewer))
+            };
+            let own: Page<ProofDetailsResponse> = visible(&stranger).await.json();
+            assert!(own.items.is_empty());
+            let follows: Page<ProofDetailsResponse> = visible(&follower).await.json();
+            assert_eq!(follows.items.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_beliefs_need_the_callers_token_and_a_visible_proof() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let follower = create_user(&db, "follower").await;
+        let stranger = create_user(&db, "stranger").await;
+        FollowService::follow(&db, &follower, &author)
+            .await
+            .unwrap();
+
+        let quest = create_quest(&db, "COMMUNITY").await;
+        let proof_id = submit(&server, &author, &quest, ProofVisibility::Followers)
+            .await
+            .json::<SubmitProofResponse>()
+            .proof_ulid;
+
+        let believe = |believer: &str, token_user: &str| {
+            server
+                .post(
+                    &QuestProofEndpoints::BeliefProof(
+                        QuestProofUlid(proof_id.clone()),
+                        UserUlid(believer.to_owned()),
+                    )
+                    .path(),
+                )
+                .add_header(axum::http::header::AUTHORIZATION, bearer(token_user))
+        };
+
+        believe(&follower, &stranger)
+            .await
+            .assert_status_bad_request();
+        believe(&stranger, &stranger)
+            .await
+            .assert_status_not_found();
+        believe(&follower, &follower).await.assert_status_ok();
+    }
+}
@@ -0,0 +1,594 @@
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum_test::TestServer;
+    use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use sea_orm::{
+        ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+        QueryFilter,
+    };
+    use ulid::Ulid;
+
+    use crate::entities::{
+        moderation_flags,
+        prelude::{ModerationFlag, QuestProof, User, UserQuestStatus},
+        quest_proofs, user_quest_status, users,
+    };
+    use crate::events::{CompletionSource, DomainEvent, EventBus};
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::quest_proof_routes::quest_proof_router;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::{
+        quest_proof_beliefs_service::FULL_BELIEF_WEIGHT, quest_proof_service::QuestProofService,
+        quest_service::QuestService,
+    };
+    use shared::{
+        endpoints::{API, QuestProofUlid, UserUlid, quest_proof_endpoints::QuestProofEndpoints},
+        models::{
+            Page,
+            quest_dto::Complexity,
+            quest_proof_dto::{ProofDetailsResponse, ProofVisibility},
+            user_quest_status_dto::QuestStatus,
+        },
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = quest_proof_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_proof(
+        db: &DatabaseConnection,
+        author_id: &str,
+        created_at: DateTime<Utc>,
+    ) -> String {
+        let quest = QuestService::create_quest(
+            db,
+            "Read a book".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        quest_proofs::ActiveModel {
+            ulid: Set(Ulid::new().to_string()),
+            user_id: Set(author_id.to_owned()),
+            quest_id: Set(quest.ulid),
+            proof_text: Set(Some("Done".into())),
+            photos: Set(None),
+            voice_notes: Set(None),
+            status: Set(quest_proofs::ProofStatus::Pending),
+            beliefs_count: Set(0),
+            belief_weight: Set(0),
+            visibility: Set(ProofVisibility::Public),
+            photos_hashed_at: Set(None),
+            created_at: Set(created_at),
+            updated_at: Set(created_at),
+        }
+        .insert(db)
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    async fn feed_page(
+        server: &TestServer,
+        viewer_id: &str,
+        cursor: Option<&str>,
+    ) -> Page<ProofDetailsResponse> {
+        let mut request = server
+            .get(&QuestProofEndpoints::GetFeed(UserUlid(viewer_id.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(viewer_id))
+            .add_query_param("limit", 2);
+        if let Some(cursor) = cursor {
+            request = request.add_query_param("cursor", cursor);
+        }
+
+        let response = request.await;
+        response.assert_status_success();
+        response.json()
+    }
+
+    // Ages the account and sets its level, which is what belief weights depend on
+    async fn age_account(db: &DatabaseConnection, user_id: &str, days: i64, level: u32) {
+        let mut user: users::ActiveModel = User::find_by_id(user_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        user.created_at = Set(Utc::now() - Duration::days(days));
+        user.level = Set(level);
+        user.update(db).await.unwrap();
+    }
+
+    // Assigns the proof's quest to its author so a completion can be recorded
+    async fn assign_quest(db: &DatabaseConnection, proof_id: &str, day: NaiveDate) {
+        let proof = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        user_quest_status::ActiveModel::new_user_quest_status(
+            Ulid::from_string(&proof.user_id).unwrap(),
+            Ulid::from_string(&proof.quest_id).unwrap(),
+            day,
+        )
+        .insert(db)
+        .await
+        .unwrap();
+    }
+
+    async fn quest_completed(db: &DatabaseConnection, proof_id: &str) -> bool {
+        let proof = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        UserQuestStatus::find()
+            .filter(user_quest_status::Column::UserId.eq(&proof.user_id))
+            .filter(user_quest_status::Column::QuestId.eq(&proof.quest_id))
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .is_completed
+    }
+
+    async fn set_status(
+        db: &DatabaseConnection,
+        proof_id: &str,
+        status: quest_proofs::ProofStatus,
+    ) {
+        let mut proof: quest_proofs::ActiveModel = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        proof.status = Set(status);
+        proof.update(db).await.unwrap();
+    }
+
+    fn confirm(server: &TestServer, proof_id: &str, user_id: &str) -> axum_test::TestRequest {
+        server
+            .post(
+                &QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(proof_id.to_owned())).path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user_id))
+    }
+
+    async fn xp_balance(db: &DatabaseConnection, user_id: &str) -> i32 {
+        User::find_by_id(user_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap()
+            .xp_balance
+    }
+
+    async fn believe(server: &TestServer, proof_id: &str, user_id: &str) -> StatusCode {
+        server
+            .post(
+                &QuestProofEndpoints::BeliefProof(
+                    QuestProofUlid(proof_id.to_owned()),
+                    UserUlid(user_id.to_owned()),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user_id))
+            .await
+            .status_code()
+    }
+
+    #[tokio::test]
+    async fn test_feed_cursor_is_stable_when_new_proofs_arrive() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let viewer = create_user(&db, "viewer").await;
+
+        let now = Utc::now();
+        let oldest = create_proof(&db, &author, now - Duration::minutes(3)).await;
+        // Two proofs with the same timestamp are ordered by ulid
+        let tied_a = create_proof(&db, &author, now - Duration::minutes(1)).await;
+        let tied_b = create_proof(&db, &author, now - Duration::minutes(1)).await;
+
+        let mut tied = vec![tied_a, tied_b];
+        tied.sort_by(|a, b| b.cmp(a));
+
+        let first = feed_page(&server, &viewer, None).await;
+        let first_ids: Vec<String> = first.items.iter().map(|p| p.ulid.clone()).collect();
+        assert_eq!(first_ids, tied);
+        assert!(first.has_more);
+
+        // A proof published while the user scrolls must not shift the next page
+        create_proof(&db, &author, Utc::now()).await;
+
+        let second = feed_page(&server, &viewer, first.next_cursor.as_deref()).await;
+        let second_ids: Vec<String> = second.items.iter().map(|p| p.ulid.clone()).collect();
+        assert_eq!(second_ids, vec![oldest]);
+        assert!(!second.has_more);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_for_you_feed_surfaces_proofs_close_to_completion() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let viewer = create_user(&db, "viewer").await;
+
+        let now = Utc::now();
+        // Easy quest at level 1 needs two beliefs, this one is a single belief away
+        let almost_done = create_proof(&db, &author, now - Duration::hours(6)).await;
+        let mut active: quest_proofs::ActiveModel = QuestProof::find_by_id(&almost_done)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        active.beliefs_count = Set(1);
+        active.belief_weight = Set(FULL_BELIEF_WEIGHT);
+        active.update(&db).await.unwrap();
+
+        let newest = create_proof(&db, &author, now).await;
+
+        let chronological = feed_page(&server, &viewer, None).await;
+        assert_eq!(chronological.items[0].ulid, newest);
+
+        let ranked: Page<ProofDetailsResponse> = server
+            .get(&QuestProofEndpoints::GetRankedFeed(UserUlid(viewer.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&viewer))
+            .await
+            .json();
+        let ranked_ids: Vec<String> = ranked.items.iter().map(|p| p.ulid.clone()).collect();
+        assert_eq!(ranked_ids, vec![almost_done, newest]);
+    }
+
+    #[tokio::test]
+    async fn test_feed_rejects_malformed_cursor() {
+        let (server, db) = setup_test_server().await;
+        let viewer = create_user(&db, "viewer").await;
+
+        server
+            .get(&QuestProofEndpoints::GetFeed(UserUlid(viewer.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&viewer))
+            .add_query_param("cursor", "garbage")
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_fresh_accounts_cannot_complete_quest_alone() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        // Easy quest at level 1 needs two full beliefs
+        for name in ["fresh1", "fresh2", "fresh3"] {
+            let fresh = create_user(&db, name).await;
+            assert_eq!(believe(&server, &proof_id, &fresh).await, StatusCode::OK);
+        }
+        assert!(!quest_completed(&db, &proof_id).await);
+
+        for name in ["veteran1", "veteran2"] {
+            let veteran = create_user(&db, name).await;
+            age_account(&db, &veteran, 30, 5).await;
+            assert_eq!(believe(&server, &proof_id, &veteran).await, StatusCode::OK);
+        }
+        assert!(quest_completed(&db, &proof_id).await);
+
+        // Users still see the raw number of believers
+        let proof = QuestProof::find_by_id(&proof_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.beliefs_count, 5);
+        assert_eq!(proof.belief_weight, 2300);
+        assert!(ModerationFlag::find().all(&db).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_completion_by_mutual_believers_is_flagged() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        age_account(&db, &author, 30, 1).await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        // Old level 1 accounts give half a belief each, so four are needed
+        for name in ["ring1", "ring2", "ring3", "ring4"] {
+            let peer = create_user(&db, name).await;
+            age_account(&db, &peer, 30, 1).await;
+
+            let peer_proof = create_proof(&db, &peer, Utc::now()).await;
+            assert_eq!(believe(&server, &peer_proof, &author).await, StatusCode::OK);
+            assert_eq!(believe(&server, &proof_id, &peer).await, StatusCode::OK);
+        }
+
+        assert!(quest_completed(&db, &proof_id).await);
+
+        let flags = ModerationFlag::find().all(&db).await.unwrap();
+        assert_eq!(flags.len(), 1);
+        assert_eq!(flags[0].target_id, proof_id);
+        assert_eq!(
+            flags[0].reason,
+            moderation_flags::FlagReason::MutualBeliefCluster
+        );
+    }
+
+    #[tokio::test]
+    async fn test_belief_toggles_are_rate_limited_per_proof() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let spammer = create_user(&db, "spammer").await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        let other_proof = create_proof(&db, &author, Utc::now()).await;
+
+        for _ in 0..4 {
+            assert_eq!(believe(&server, &proof_id, &spammer).await, StatusCode::OK);
+        }
+        assert_eq!(
+            believe(&server, &proof_id, &spammer).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // The limit is per proof, other proofs can still be believed
+        assert_eq!(
+            believe(&server, &other_proof, &spammer).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdrawn_beliefs_revert_completion() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        let mut veterans = Vec::new();
+        for name in ["veteran1", "veteran2"] {
+            let veteran = create_user(&db, name).await;
+            age_account(&db, &veteran, 30, 5).await;
+            assert_eq!(believe(&server, &proof_id, &veteran).await, StatusCode::OK);
+            veterans.push(veteran);
+        }
+        assert!(quest_completed(&db, &proof_id).await);
+
+        // Toggling again withdraws the belief and drops below the threshold
+        assert_eq!(
+            believe(&server, &proof_id, &veterans[0]).await,
+            StatusCode::OK
+        );
+        assert!(!quest_completed(&db, &proof_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_completion_targets_the_proof_day_and_emits_events() {
+        let (_server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let yesterday = Utc::now() - Duration::days(1);
+        let proof_id = create_proof(&db, &author, yesterday).await;
+        assign_quest(&db, &proof_id, yesterday.date_naive()).await;
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        let bus = EventBus::new();
+        let mut events = bus.subscribe();
+
+        QuestProofService::update_status(&db, &bus, &proof_id, quest_proofs::ProofStatus::Approved)
+            .await
+            .unwrap();
+
+        let rows = UserQuestStatus::find()
+            .filter(user_quest_status::Column::UserId.eq(&author))
+            .all(&db)
+            .await
+            .unwrap();
+        for row in rows {
+            assert_eq!(row.is_completed, row.assigned_at == yesterday.date_naive());
+        }
+
+        let completed = events.try_recv().unwrap();
+        let DomainEvent::QuestCompleted(completion) = &completed else {
+            panic!("expected QuestCompleted, got {completed:?}");
+        };
+        assert_eq!(completion.assigned_at, yesterday.date_naive());
+        assert_eq!(completion.source, CompletionSource::Moderation);
+        assert_eq!(xp_balance(&db, &author).await, 10);
+
+        // Approving twice doesn't complete or pay twice
+        QuestProofService::update_status(&db, &bus, &proof_id, quest_proofs::ProofStatus::Approved)
+            .await
+            .unwrap();
+        assert!(events.try_recv().is_err());
+        assert_eq!(xp_balance(&db, &author).await, 10);
+
+        QuestProofService::update_status(&db, &bus, &proof_id, quest_proofs::ProofStatus::Rejected)
+            .await
+            .unwrap();
+        let reverted = events.try_recv().unwrap();
+        assert!(matches!(reverted, DomainEvent::QuestCompletionReverted(_)));
+        assert_eq!(xp_balance(&db, &author).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_automatic_quest_completes_on_confirm() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let quest = QuestService::create_quest(
+            &db,
+            "Mirror Talk".into(),
+            None,
+            10,
+            "AUTOMATIC".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        let mut proof: quest_proofs::ActiveModel = QuestProof::find_by_id(&proof_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        proof.quest_id = Set(quest.ulid);
+        proof.status = Set(quest_proofs::ProofStatus::Uploading);
+        proof.update(&db).await.unwrap();
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        server
+            .post(&QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(proof_id.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .assert_status_success();
+
+        assert!(quest_completed(&db, &proof_id).await);
+    }
+
+    #[tokio::test]
+    async fn test_confirming_community_proof_only_marks_it_pending() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        set_status(&db, &proof_id, quest_proofs::ProofStatus::Uploading).await;
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        confirm(&server, &proof_id, &author)
+            .await
+            .assert_status_success();
+
+        let status = UserQuestStatus::find()
+            .filter(user_quest_status::Column::UserId.eq(&author))
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(status.quest_status, QuestStatus::InPending);
+        assert!(!status.is_completed);
+    }
+
+    #[tokio::test]
+    async fn test_only_the_authors_uploading_proof_can_be_confirmed() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let stranger = create_user(&db, "stranger").await;
+        let quest = QuestService::create_quest(
+            &db,
+            "Mirror Talk".into(),
+            None,
+            10,
+            "AUTOMATIC".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+        let mut proof: quest_proofs::ActiveModel = QuestProof::find_by_id(&proof_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        proof.quest_id = Set(quest.ulid);
+        proof.status = Set(quest_proofs::ProofStatus::Uploading);
+        proof.update(&db).await.unwrap();
+        assign_quest(&db, &proof_id, Utc::now().date_naive()).await;
+
+        confirm(&server, &proof_id, &stranger)
+            .await
+            .assert_status_not_found();
+        assert!(!quest_completed(&db, &proof_id).await);
+
+        confirm(&server, &proof_id, &author)
+            .await
+            .assert_status_success();
+        assert_eq!(xp_balance(&db, &author).await, 10);
+
+        // A repeated confirm, e.g. a client retry, changes nothing
+        confirm(&server, &proof_id, &author)
+            .await
+            .assert_status_bad_request();
+        assert_eq!(xp_balance(&db, &author).await, 10);
+
+        // Nor does it bring back a rejected proof
+        QuestProofService::update_status(
+            &db,
+            &EventBus::new(),
+            &proof_id,
+            quest_proofs::ProofStatus::Rejected,
+        )
+        .await
+        .unwrap();
+        confirm(&server, &proof_id, &author)
+            .await
+            .assert_status_bad_request();
+        let proof = QuestProof::find_by_id(&proof_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(proof.status, quest_proofs::ProofStatus::Rejected);
+        assert_eq!(xp_balance(&db, &author).await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_only_the_author_can_cancel_an_unconfirmed_proof() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let stranger = create_user(&db, "stranger").await;
+        let proof_id = create_proof(&db, &author, Utc::now()).await;
+
+        let cancel = |user: &str| {
+            server
+                .delete(
+                    &QuestProofEndpoints::CancelSubmission(QuestProofUlid(proof_id.clone())).path(),
+                )
+                .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+        };
+
+        // Already confirmed proofs stay
+        cancel(&author).await.assert_status_bad_request();
+
+        let mut proof: quest_proofs::ActiveModel = QuestProof::find_by_id(&proof_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        proof.status = Set(quest_proofs::ProofStatus::Uploading);
+        proof.update(&db).await.unwrap();
+
+        cancel(&stranger).await.assert_status_not_found();
+        cancel(&author).await.assert_status(StatusCode::NO_CONTENT);
+        assert!(
+            QuestProof::find_by_id(&proof_id)
+                .one(&db)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,393 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::{
+        ActiveModelTrait, ActiveValue::Set, ColumnTrait, DatabaseConnection, EntityTrait,
+        QueryFilter,
+    };
+    use serde_json::json;
+    use ulid::Ulid;
+
+    use crate::entities::{
+        moderation_flags::{self, FlagReason},
+        prelude::{ModerationFlag, QuestProof},
+        quest_proofs, users,
+    };
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::tests::support::{bearer, test_state};
+    use crate::routes::{quest_proof_routes::quest_proof_router, report_routes::report_router};
+    use crate::service::{
+        photo_hash_service::PhotoHashService, quest_service::QuestService,
+        report_service::ReportConfig, user_service::UserService,
+    };
+    use shared::{
+        endpoints::report_endpoints::ReportEndpoints,
+        endpoints::{API, UserUlid, quest_proof_endpoints::QuestProofEndpoints},
+        models::{
+            Page,
+            quest_dto::Complexity,
+            quest_proof_dto::{ProofDetailsResponse, ProofVisibility},
+            report_dto::{ModerationQueueItem, PhotoMatchDto, PhotoMatchKind, ReportDto},
+        },
+    };
+
+    async fn setup_test_server(hide_threshold: u64) -> (TestServer, DatabaseConnection) {
+        let mut state = test_state().await;
+        state.report_config = ReportConfig { hide_threshold };
+        let connection = state.connection.clone();
+
+        let app = report_router()
+            .merge(quest_proof_router())
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_user(db: &DatabaseConnection, name: &str, is_moderator: bool) -> String {
+        let user = UserService::create_user(
+            db,
+            name.into(),
+            format!("{name}@test.com"),
+            "password123".into(),
+        )
+        .await
+        .unwrap();
+
+        if is_moderator {
+            let mut active: users::ActiveModel = user.clone().into();
+            active.is_moderator = Set(true);
+            active.update(db).await.unwrap();
+        }
+
+        user.ulid
+    }
+
+    async fn create_proof(db: &DatabaseConnection, author_id: &str) -> String {
+        let quest = QuestService::create_quest(
+            db,
+            "Say hi to a stranger".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let proof = quest_proofs::ActiveModel {
+            ulid: Set(Ulid::new().to_string()),
+            user_id: Set(author_id.to_owned()),
+            quest_id: Set(quest.ulid),
+            proof_text: Set(Some("I did it".into())),
+            photos: Set(None),
+            voice_notes: Set(None),
+            status: Set(quest_proofs::ProofStatus::Pending),
+            beliefs_count: Set(0),
+            belief_weight: Set(0),
+            visibility: Set(ProofVisibility::Public),
+            photos_hashed_at: Set(None),
+            created_at: Set(chrono::Utc::now()),
+            updated_at: Set(chrono::Utc::now()),
+        }
+        .insert(db)
+        .await
+        .unwrap();
+
+        proof.ulid
+    }
+
+    async fn report_proof(server: &TestServer, reporter_id: &str, proof_id: &str) -> ReportDto {
+        let response = server
+            .post(&ReportEndpoints::Create.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(reporter_id))
+            .json(&json!({
+                "target_type": "proof",
+                "target_id": proof_id,
+                "reason": "SPAM",
+                "details": "Same photo every day"
+            }))
+            .await;
+        response.assert_status_success();
+        response.json()
+    }
+
+    async fn feed_ids(server: &TestServer, viewer_id: &str) -> Vec<String> {
+        let response = server
+            .get(&QuestProofEndpoints::GetFeed(UserUlid(viewer_id.to_owned())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(viewer_id))
+            .await;
+        response.assert_status_success();
+        let feed: Page<ProofDetailsResponse> = response.json();
+        feed.items.into_iter().map(|p| p.ulid).collect()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_report_is_deduplicated() {
+        let (server, db) = setup_test_server(3).await;
+        let author = create_user(&db, "author", false).await;
+        let reporter = create_user(&db, "reporter", false).await;
+        let moderator = create_user(&db, "moderator", true).await;
+        let proof_id = create_proof(&db, &author).await;
+
+        let first = report_proof(&server, &reporter, &proof_id).await;
+        let second = report_proof(&server, &reporter, &proof_id).await;
+        assert_eq!(first.ulid, second.ulid);
+
+        let queue: Page<ModerationQueueItem> = server
+            .get(&ReportEndpoints::GetQueue.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .await
+            .json();
+        assert_eq!(queue.items.len(), 1);
+        assert_eq!(queue.items[0].open_reports, 1);
+        assert!(!queue.items[0].is_hidden);
+    }
+
+    #[tokio::test]
+    async fn test_proof_hidden_after_threshold_until_dismissed() {
+        let (server, db) = setup_test_server(2).await;
+        let author = create_user(&db, "author", false).await;
+        let reporter_a = create_user(&db, "reporter_a", false).await;
+        let reporter_b = create_user(&db, "reporter_b", false).await;
+        let viewer = create_user(&db, "viewer", false).await;
+        let moderator = create_user(&db, "moderator", true).await;
+        let proof_id = create_proof(&db, &author).await;
+
+        report_proof(&server, &reporter_a, &proof_id).await;
+        assert!(feed_ids(&server, &viewer).await.contains(&proof_id));
+
+        report_proof(&server, &reporter_b, &proof_id).await;
+        assert!(!feed_ids(&server, &viewer).await.contains(&proof_id));
+
+        server
+            .post(&ReportEndpoints::Resolve.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .json(&json!({
+                "target_type": "proof",
+                "target_id": proof_id,
+                "resolution": "dismiss"
+            }))
+            .await
+            .assert_status_success();
+
+        assert!(feed_ids(&server, &viewer).await.contains(&proof_id));
+    }
+
+    #[tokio::test]
+    async fn test_removed_proof_stays_hidden() {
+        let (server, db) = setup_test_server(5).await;
+        let author = create_user(&db, "author", false).await;
+        let reporter = create_user(&db, "reporter", false).await;
+        let viewer = create_user(&db, "viewer", false).await;
+        let moderator = create_user(&db, "moderator", true).await;
+        let proof_id = create_proof(&db, &author).await;
+
+        report_proof(&server, &reporter, &proof_id).await;
+
+        server
+            .post(&ReportEndpoints::Resolve.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .json(&json!({
+                "target_type": "proof",
+                "target_id": proof_id,
+                "resolution": "remove"
+            }))
+            .await
+            .assert_status_success();
+
+        assert!(!feed_ids(&server, &viewer).await.contains(&proof_id));
+    }
+
+    #[tokio::test]
+    async fn test_queue_pages_targets_oldest_first() {
+        let (server, db) = setup_test_server(3).await;
+        let author = create_user(&db, "author", false).await;
+        let reporter_a = create_user(&db, "reporter_a", false).await;
+        let reporter_b = create_user(&db, "reporter_b", false).await;
+        let moderator = create_user(&db, "moderator", true).await;
+        let older = create_proof(&db, &author).await;
+        let newer = create_proof(&db, &author).await;
+
+        report_proof(&server, &reporter_a, &older).await;
+        report_proof(&server, &reporter_a, &newer).await;
+        report_proof(&server, &reporter_b, &older).await;
+
+        let first: Page<ModerationQueueItem> = server
+            .get(&ReportEndpoints::GetQueue.path())
+            .add_query_param("limit", 1)
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .await
+            .json();
+        assert_eq!(first.items.len(), 1);
+        assert_eq!(first.items[0].target_id, older);
+        assert_eq!(first.items[0].open_reports, 2);
+        assert_eq!(first.items[0].reports.len(), 2);
+        assert!(first.has_more);
+
+        let second: Page<ModerationQueueItem> = server
+            .get(&ReportEndpoints::GetQueue.path())
+            .add_query_param("limit", 1)
+            .add_query_param("cursor", first.next_cursor.unwrap())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .await
+            .json();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.items[0].target_id, newer);
+        assert_eq!(second.items[0].reports.len(), 1);
+        assert!(!second.has_more);
+    }
+
+    #[tokio::test]
+    async fn test_queue_requires_moderator() {
+        let (server, db) = setup_test_server(3).await;
+        let user = create_user(&db, "regular", false).await;
+
+        server
+            .get(&ReportEndpoints::GetQueue.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user))
+            .await
+            .assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_cannot_report_own_proof() {
+        let (server, db) = setup_test_server(3).await;
+        let author = create_user(&db, "author", false).await;
+        let proof_id = create_proof(&db, &author).await;
+
+        server
+            .post(&ReportEndpoints::Create.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .json(&json!({
+                "target_type": "proof",
+                "target_id": proof_id,
+                "reason": "OTHER"
+            }))
+            .await
+            .assert_status_bad_request();
+    }
+
+    async fn index(db: &DatabaseConnection, proof_id: &str, hash: u64) -> usize {
+        let proof = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await
+            .unwrap()
+            .unwrap();
+        let key = format!(
+            "users/{}/proofs/{proof_id}/photos/photo_0.jpg",
+            proof.user_id
+        );
+
+        PhotoHashService::index_photo(db, &proof, key, hash)
+            .await
+            .unwrap()
+            .len()
+    }
+
+    async fn flags(db: &DatabaseConnection, proof_id: &str) -> Vec<FlagReason> {
+        ModerationFlag::find()
+            .filter(moderation_flags::Column::TargetId.eq(proof_id))
+            .all(db)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|f| f.reason)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_photos_are_flagged_and_listed_for_moderators() {
+        let (server, db) = setup_test_server(3).await;
+        let author = create_user(&db, "author", false).await;
+        let copier = create_user(&db, "copier", false).await;
+        let moderator = create_user(&db, "moderator", true).await;
+
+        // Top bit set, hashes are stored as signed integers
+        let hash: u64 = 0xf123_4567_89ab_cdef;
+        let original = create_proof(&db, &author).await;
+        let reused = create_proof(&db, &author).await;
+        let copied = create_proof(&db, &copier).await;
+        let unrelated = create_proof(&db, &copier).await;
+
+        assert_eq!(index(&db, &original, hash).await, 0);
+        // Three bits off, e.g. the same photo re-saved with another quality
+        assert_eq!(index(&db, &reused, hash ^ 0b111).await, 1);
+        assert_eq!(index(&db, &copied, hash ^ 1).await, 2);
+        assert_eq!(index(&db, &unrelated, !hash).await, 0);
+        // Processing an upload again changes nothing
+        assert_eq!(index(&db, &reused, hash ^ 0b111).await, 0);
+
+        assert!(flags(&db, &original).await.is_empty());
+        assert_eq!(flags(&db, &reused).await, vec![FlagReason::ReusedPhoto]);
+        assert_eq!(flags(&db, &copied).await, vec![FlagReason::CopiedPhoto]);
+        assert!(flags(&db, &unrelated).await.is_empty());
+
+        server
+            .get(&ReportEndpoints::GetPhotoMatches.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .assert_status_not_found();
+
+        let page: Page<PhotoMatchDto> = server
+            .get(&ReportEndpoints::GetPhotoMatches.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&moderator))
+            .await
+            .json();
+        assert_eq!(page.items.len(), 3);
+
+        let reused_match = page.items.iter().find(|m| m.proof_id == reused).unwrap();
+        assert!(matches!(reused_match.kind, PhotoMatchKind::Reused));
+        assert_eq!(reused_match.matched_proof_id, original);
+        assert_eq!(reused_match.distance, 3);
+
+        let copied_matches: Vec<_> = page.items.iter().filter(|m| m.proof_id == copied).collect();
+        assert_eq!(copied_matches.len(), 2);
+        assert!(
+            copied_matches
+                .iter()
+                .all(|m| matches!(m.kind, PhotoMatchKind::Copied) && m.matched_user_id == author)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_photo_sweep_marks_each_confirmed_proof_once() {
+        let state = test_state().await;
+        let db = state.connection.clone();
+        let author = create_user(&db, "author", false).await;
+        let confirmed = create_proof(&db, &author).await;
+        let uploading = create_proof(&db, &author).await;
+        let mut active: quest_proofs::ActiveModel = QuestProof::find_by_id(&uploading)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap()
+            .into();
+        active.status = Set(quest_proofs::ProofStatus::Uploading);
+        active.update(&db).await.unwrap();
+
+        // A missed ProofUploaded event leaves the mark unset, the next sweep picks it up
+        assert_eq!(
+            PhotoHashService::sweep(&db, &state.s3_manager)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            PhotoHashService::sweep(&db, &state.s3_manager)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let proofs = QuestProof::find().all(&db).await.unwrap();
+        for proof in proofs {
+            assert_eq!(proof.photos_hashed_at.is_some(), proof.ulid == confirmed);
+        }
+    }
+}
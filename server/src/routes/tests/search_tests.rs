@@ -0,0 +1,253 @@
+#[cfg(test)]
+mod tests {
+    use axum_test::TestServer;
+    use sea_orm::DatabaseConnection;
+
+    use crate::events::EventBus;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::search_routes::search_router;
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::{
+        lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+        message_service::MessageService, quest_service::QuestService,
+    };
+    use shared::{
+        endpoints::{
+            API,
+            search_endpoints::{SearchEndpoints, SearchQuery},
+        },
+        models::{Page, lobby_dto::LobbyVisibility, quest_dto::Complexity, search_dto::SearchHit},
+        moderation::TextFilter,
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let app = search_router()
+            .layer(axum::middleware::from_fn(check_access_token))
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn create_lobby(db: &DatabaseConnection, owner: &str, name: &str) -> String {
+        LobbyService::create_lobby(
+            db,
+            &TextFilter::default(),
+            owner.to_owned(),
+            name.into(),
+            "Chat".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    async fn send(db: &DatabaseConnection, lobby_id: &str, author: &str, content: &str) -> String {
+        MessageService::send_message(
+            db,
+            &EventBus::new(),
+            &TextFilter::default(),
+            lobby_id.to_owned(),
+            author.to_owned(),
+            content.into(),
+            None,
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    async fn search(
+        server: &TestServer,
+        endpoint: SearchEndpoints,
+        user: &str,
+        query: SearchQuery,
+    ) -> Page<SearchHit> {
+        let response = server
+            .get(&endpoint.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(user))
+            .add_query_params(query)
+            .await;
+        response.assert_status_ok();
+        response.json()
+    }
+
+    fn query(q: &str) -> SearchQuery {
+        SearchQuery {
+            q: q.into(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_message_search_is_limited_to_own_lobbies() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        let alice_lobby = create_lobby(&db, &alice, "Alice club").await;
+        let bob_lobby = create_lobby(&db, &bob, "Bob club").await;
+
+        let found = send(&db, &alice_lobby, &alice, "Meet me at the Šabac market").await;
+        send(&db, &bob_lobby, &bob, "The market is closed today").await;
+        let deleted = send(&db, &alice_lobby, &alice, "Market was fun").await;
+        MessageService::delete_message(&db, &alice_lobby, &deleted, &alice)
+            .await
+            .unwrap();
+
+        // Prefix of the last term, diacritics ignored
+        let page = search(
+            &server,
+            SearchEndpoints::Messages,
+            &alice,
+            query("sabac mark"),
+        )
+        .await;
+        assert_eq!(page.items.len(), 1);
+        let hit = &page.items[0];
+        assert_eq!(hit.ulid, found);
+        assert_eq!(hit.lobby_id.as_deref(), Some(alice_lobby.as_str()));
+
+        let highlighted: Vec<&str> = hit
+            .snippet
+            .iter()
+            .filter(|part| part.highlighted)
+            .map(|part| part.text.as_str())
+            .collect();
+        assert_eq!(highlighted, vec!["Šabac", "market"]);
+
+        // Bob's lobby is out of reach until alice joins it
+        let page = search(&server, SearchEndpoints::Messages, &alice, query("closed")).await;
+        assert!(page.items.is_empty());
+
+        server
+            .get(&SearchEndpoints::Messages.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&alice))
+            .add_query_params(SearchQuery {
+                q: "closed".into(),
+                lobby_id: Some(bob_lobby.clone()),
+                ..Default::default()
+            })
+            .await
+            .assert_status_not_found();
+
+        LobbyMemberService::join_lobby(&db, bob_lobby.clone(), alice.clone())
+            .await
+            .unwrap();
+        let page = search(&server, SearchEndpoints::Messages, &alice, query("closed")).await;
+        assert_eq!(page.items.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_search_pages_newest_first() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let lobby = create_lobby(&db, &alice, "Owls").await;
+
+        let mut sent = Vec::new();
+        for i in 0..3 {
+            sent.push(send(&db, &lobby, &alice, &format!("Hello number {i}")).await);
+        }
+        // Same-millisecond ULIDs have no fixed order, so compare against ULID order
+        sent.sort_by(|a, b| b.cmp(a));
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = search(
+                &server,
+                SearchEndpoints::Messages,
+                &alice,
+                SearchQuery {
+                    q: "hello".into(),
+                    limit: Some(2),
+                    cursor: cursor.take(),
+                    ..Default::default()
+                },
+            )
+            .await;
+            seen.extend(page.items.into_iter().map(|hit| hit.ulid));
+            if !page.has_more {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(seen, sent);
+
+        server
+            .get(&SearchEndpoints::Messages.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&alice))
+            .add_query_param("q", "hello")
+            .add_query_param("cursor", "garbage")
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_lobby_and_quest_search() {
+        let (server, db) = setup_test_server().await;
+        let alice = create_user(&db, "alice").await;
+        let bob = create_user(&db, "bob").await;
+        let lobby = create_lobby(&db, &alice, "Public speaking").await;
+        create_lobby(&db, &alice, "Quiet readers").await;
+        LobbyService::create_lobby(
+            &db,
+            &TextFilter::default(),
+            alice.clone(),
+            "Speaking secrets".into(),
+            "Chat".into(),
+            None,
+            LobbyVisibility::Private,
+        )
+        .await
+        .unwrap();
+
+        // Private lobbies only turn up for their members
+        let page = search(&server, SearchEndpoints::Lobbies, &bob, query("speak")).await;
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(page.items[0].ulid, lobby);
+
+        let page = search(&server, SearchEndpoints::Lobbies, &alice, query("speak")).await;
+        assert_eq!(page.items.len(), 2);
+
+        let global = QuestService::create_quest(
+            &db,
+            "Speak to a stranger".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap()
+        .ulid;
+        QuestService::create_quest(
+            &db,
+            "Speak in the club".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            Some(lobby.clone()),
+        )
+        .await
+        .unwrap();
+
+        // Lobby quests only show up for the lobby's members
+        let page = search(&server, SearchEndpoints::Quests, &bob, query("speak")).await;
+        let ulids: Vec<_> = page.items.into_iter().map(|hit| hit.ulid).collect();
+        assert_eq!(ulids, vec![global]);
+
+        let page = search(&server, SearchEndpoints::Quests, &alice, query("speak")).await;
+        assert_eq!(page.items.len(), 2);
+    }
+}
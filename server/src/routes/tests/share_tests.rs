@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum_test::TestServer;
+    use sea_orm::DatabaseConnection;
+
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::quest_proof_routes::quest_proof_router;
+    use crate::routes::share_routes::{public_share_router, share_router};
+    use crate::routes::tests::support::{bearer, create_user, test_state};
+    use crate::service::quest_service::QuestService;
+    use shared::{
+        endpoints::{
+            API, QuestProofUlid, QuestUlid, UserUlid, quest_proof_endpoints::QuestProofEndpoints,
+            share_endpoints::ShareEndpoints,
+        },
+        models::{
+            quest_dto::Complexity,
+            quest_proof_dto::{
+                ProofShareResponse, ProofVisibility, SubmitProofRequest, SubmitProofResponse,
+            },
+        },
+    };
+
+    async fn setup_test_server() -> (TestServer, DatabaseConnection) {
+        let state = test_state().await;
+        let connection = state.connection.clone();
+
+        let protected = Router::new()
+            .merge(quest_proof_router())
+            .merge(share_router())
+            .layer(axum::middleware::from_fn(check_access_token));
+
+        let app = Router::new()
+            .merge(public_share_router())
+            .merge(protected)
+            .with_state(state);
+
+        (
+            TestServer::new(app).expect("Failed to create test server"),
+            connection,
+        )
+    }
+
+    async fn submit_proof(
+        server: &TestServer,
+        db: &DatabaseConnection,
+        author: &str,
+        visibility: ProofVisibility,
+    ) -> String {
+        let quest = QuestService::create_quest(
+            db,
+            "Compliment a <stranger>".into(),
+            None,
+            10,
+            "COMMUNITY".into(),
+            1,
+            Complexity::Easy,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let response = server
+            .post(
+                &QuestProofEndpoints::InitSubmission(
+                    UserUlid(author.to_owned()),
+                    QuestUlid(quest.ulid),
+                )
+                .path(),
+            )
+            .add_header(axum::http::header::AUTHORIZATION, bearer(author))
+            .json(&SubmitProofRequest {
+                proof_text: Some("Said hi & smiled".into()),
+                photo_count: 0,
+                voice_count: 0,
+                visibility,
+            })
+            .await;
+        response.assert_status_success();
+        let proof_id = response.json::<SubmitProofResponse>().proof_ulid;
+
+        server
+            .post(&QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(proof_id.clone())).path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(author))
+            .await
+            .assert_status_success();
+
+        proof_id
+    }
+
+    fn share_path(proof_id: &str) -> String {
+        ShareEndpoints::CreateShare(QuestProofUlid(proof_id.to_owned())).path()
+    }
+
+    #[tokio::test]
+    async fn test_share_page_renders_until_revoked() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let proof_id = submit_proof(&server, &db, &author, ProofVisibility::Public).await;
+
+        let response = server
+            .post(&share_path(&proof_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await;
+        response.assert_status_success();
+        let share = response.json::<ProofShareResponse>();
+
+        // Sharing again hands out the same link
+        let again = server
+            .post(&share_path(&proof_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .json::<ProofShareResponse>();
+        assert_eq!(again.token, share.token);
+
+        let page_path = ShareEndpoints::PublicPage(share.token).path();
+        let page = server.get(&page_path).await;
+        page.assert_status_success();
+        let html = page.text();
+        assert!(
+            html.contains("<meta property=\"og:title\" content=\"Compliment a &lt;stranger&gt;\">")
+        );
+        assert!(html.contains("Said hi &amp; smiled"));
+
+        server
+            .delete(&share_path(&proof_id))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .assert_status_success();
+
+        server.get(&page_path).await.assert_status_not_found();
+    }
+
+    #[tokio::test]
+    async fn test_only_author_can_share_public_proofs() {
+        let (server, db) = setup_test_server().await;
+        let author = create_user(&db, "author").await;
+        let stranger = create_user(&db, "stranger").await;
+
+        let public_proof = submit_proof(&server, &db, &author, ProofVisibility::Public).await;
+        server
+            .post(&share_path(&public_proof))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&stranger))
+            .await
+            .assert_status_not_found();
+
+        let followers_proof = submit_proof(&server, &db, &author, ProofVisibility::Followers).await;
+        server
+            .post(&share_path(&followers_proof))
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&author))
+            .await
+            .assert_status_bad_request();
+    }
+
+    #[tokio::test]
+    async fn test_unknown_share_token_is_not_found() {
+        let (server, _db) = setup_test_server().await;
+
+        server
+            .get(&ShareEndpoints::PublicPage("0123456789abcdef0123456789abcdef".into()).path())
+            .await
+            .assert_status_not_found();
+    }
+}
@@ -1,42 +1,22 @@
 #[cfg(test)]
 mod tests {
     use axum_test::TestServer;
-    use migration::{Migrator, MigratorTrait};
-    use sea_orm::{ConnectOptions, Database};
     use serde_json::json;
     use shared::endpoints::API;
     use shared::endpoints::user_endpoints::UserEndpoints;
-    use shared::models::user_dto::{LoginResponse, RegisterResponse};
-    use std::collections::HashMap;
-    use std::sync::Arc;
-    use tokio::sync::Mutex;
+    use shared::errors::{AppError, ErrorBody, auth_errors::AuthError};
+    use shared::models::user_dto::{LoginResponse, ProfileResponse, RegisterResponse};
 
-    use crate::AppState;
-    use crate::routes::user_routes::public_user_router;
+    use crate::middleware::jwt_verify_middleware::check_access_token;
+    use crate::routes::tests::support::{bearer, test_state};
+    use crate::routes::user_routes::{public_user_router, user_router};
 
     async fn setup_test_server() -> TestServer {
-        let mut opt = ConnectOptions::new("sqlite::memory:");
-        opt.sqlx_logging(false);
-        let connection = Database::connect(opt)
-            .await
-            .expect("Failed to connect to test DB");
-
-        Migrator::up(&connection, None)
-            .await
-            .expect("Failed to run migrations");
-
-        let state = AppState {
-            connection,
-            s3_manager: crate::file_storage::s3_client::S3Manager::new(
-                "test-bucket".into(),
-                "mock-endpoint".to_string(),
-                "mock-region".to_string(),
-            )
-            .await,
-            lobby_channels: Arc::new(Mutex::new(HashMap::new())),
-        };
+        let state = test_state().await;
 
-        let app = public_user_router().with_state(state);
+        let app = public_user_router()
+            .merge(user_router().layer(axum::middleware::from_fn(check_access_token)))
+            .with_state(state);
 
         TestServer::new(app).expect("Failed to create test server")
     }
@@ -177,4 +157,110 @@ mod tests {
 
         response.assert_status_unauthorized();
     }
+
+    async fn register(server: &TestServer, username: &str) -> RegisterResponse {
+        server
+            .post(&UserEndpoints::RegisterUserEndpoint.path())
+            .json(&json!({
+                "username": username,
+                "email": format!("{username}@test.com"),
+                "password": "password123"
+            }))
+            .await
+            .json()
+    }
+
+    #[tokio::test]
+    async fn test_profile_requires_authentication() {
+        let server = setup_test_server().await;
+
+        // No header fails the extractor, a bad token fails verification
+        server
+            .get(&UserEndpoints::GetMyProfile.path())
+            .await
+            .assert_status_bad_request();
+        server
+            .get(&UserEndpoints::GetMyProfile.path())
+            .add_header(axum::http::header::AUTHORIZATION, "Bearer invalid-token")
+            .await
+            .assert_status_unauthorized();
+    }
+
+    #[tokio::test]
+    async fn test_get_and_update_own_profile() {
+        let server = setup_test_server().await;
+        let user = register(&server, "rust_ace").await;
+
+        let profile: ProfileResponse = server
+            .get(&UserEndpoints::GetMyProfile.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user.ulid))
+            .await
+            .json();
+        assert_eq!(profile.username, "rust_ace");
+        assert!(profile.bio.is_none());
+
+        let response = server
+            .patch(&UserEndpoints::UpdateMyProfile.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user.ulid))
+            .json(&json!({ "username": "rust_king", "bio": "Talking to strangers daily" }))
+            .await;
+        response.assert_status_success();
+
+        let updated: ProfileResponse = response.json();
+        assert_eq!(updated.username, "rust_king");
+        assert_eq!(updated.bio.as_deref(), Some("Talking to strangers daily"));
+    }
+
+    #[tokio::test]
+    async fn test_taken_username_is_a_conflict() {
+        let server = setup_test_server().await;
+        let user = register(&server, "first_user").await;
+        register(&server, "second_user").await;
+
+        let response = server
+            .patch(&UserEndpoints::UpdateMyProfile.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user.ulid))
+            .json(&json!({ "username": "second_user" }))
+            .await;
+
+        response.assert_status_conflict();
+        assert!(matches!(
+            AppError::from(response.json::<ErrorBody>()),
+            AppError::Auth(AuthError::UsernameTaken)
+        ));
+
+        // Keeping your own name is not a conflict
+        server
+            .patch(&UserEndpoints::UpdateMyProfile.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user.ulid))
+            .json(&json!({ "username": "first_user" }))
+            .await
+            .assert_status_success();
+    }
+
+    #[tokio::test]
+    async fn test_confirmed_avatar_is_never_the_raw_key() {
+        let server = setup_test_server().await;
+        let user = register(&server, "avatar_user").await;
+
+        let response = server
+            .post(&UserEndpoints::ConfirmAvatarUpload.path())
+            .add_header(axum::http::header::AUTHORIZATION, bearer(&user.ulid))
+            .await;
+        response.assert_status_success();
+
+        let raw_key = format!("users/{}/avatar.jpg", user.ulid);
+        let profile: ProfileResponse = response.json();
+        let avatar_url = profile.avatar_url.expect("Confirmed avatar has a URL");
+        assert_ne!(avatar_url, raw_key);
+        assert!(avatar_url.contains(&raw_key) && avatar_url.contains("X-Amz-Signature"));
+
+        let login: LoginResponse = server
+            .post(&UserEndpoints::LoginUserEndpoint.path())
+            .json(&json!({ "email": "avatar_user@test.com", "password": "password123" }))
+            .await
+            .json();
+        let avatar_url = login.avatar_url.expect("Login returns the avatar URL");
+        assert!(avatar_url.contains(&raw_key) && avatar_url.contains("X-Amz-Signature"));
+    }
 }
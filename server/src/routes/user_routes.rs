@@ -1,14 +1,25 @@
-use axum::{Json, Router, extract::State, routing::post};
+use axum::{
+    Json, Router,
+    extract::State,
+    routing::{get, post},
+};
 use shared::{
     endpoints::{API, user_endpoints::UserEndpoints},
     errors::{AppResult, auth_errors::AuthError},
-    models::user_dto::{LoginRequest, LoginResponse, RegisterRequest, RegisterResponse},
+    models::{
+        PresignedUpload,
+        user_dto::{
+            LoginRequest, LoginResponse, ProfileResponse, RegisterRequest, RegisterResponse,
+            UpdateProfileRequest,
+        },
+    },
 };
 use ulid::Ulid;
 use validator::Validate;
 
 use crate::{
     AppState,
+    middleware::jwt_verify_middleware::AuthUser,
     service::{refresh_token_service::RefreshTokenService, user_service::UserService},
 };
 
@@ -21,6 +32,23 @@ pub fn public_user_router() -> Router<AppState> {
         .route(UserEndpoints::LoginUserEndpoint.template(), post(login))
 }
 
+// Profile of the authenticated user
+pub fn user_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            UserEndpoints::GetMyProfile.template(),
+            get(get_my_profile).patch(update_my_profile),
+        )
+        .route(
+            UserEndpoints::GetAvatarUploadUrl.template(),
+            post(get_avatar_upload_url),
+        )
+        .route(
+            UserEndpoints::ConfirmAvatarUpload.template(),
+            post(confirm_avatar_upload),
+        )
+}
+
 #[tracing::instrument(skip(state))]
 async fn register(
     State(state): State<AppState>,
@@ -49,7 +77,10 @@ async fn register(
         created_at: user_model.created_at,
         refresh_token,
         level: user_model.level,
-        avatar_url: user_model.avatar_url,
+        avatar_url: state
+            .s3_manager
+            .resolve_url(user_model.avatar_url.as_deref())
+            .await,
     };
 
     Ok(Json(response))
@@ -81,8 +112,64 @@ async fn login(
         email: user_model.email,
         refresh_token,
         level: user_model.level,
-        avatar_url: user_model.avatar_url,
+        avatar_url: state
+            .s3_manager
+            .resolve_url(user_model.avatar_url.as_deref())
+            .await,
     };
 
     Ok(Json(response))
 }
+
+async fn get_my_profile(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<ProfileResponse>> {
+    let profile =
+        UserService::get_profile(&state.connection, &state.s3_manager, &user.user_id).await?;
+
+    Ok(Json(profile))
+}
+
+async fn update_my_profile(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(payload): Json<UpdateProfileRequest>,
+) -> AppResult<Json<ProfileResponse>> {
+    payload
+        .validate()
+        .map_err(|e| AuthError::ValidationError(e.to_string()))?;
+
+    let updated = UserService::update_profile(
+        &state.connection,
+        &state.text_filter,
+        &user.user_id,
+        payload.username,
+        payload.bio,
+    )
+    .await?;
+
+    Ok(Json(
+        UserService::to_profile(&state.s3_manager, updated).await,
+    ))
+}
+
+async fn get_avatar_upload_url(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<PresignedUpload>> {
+    let upload = UserService::get_avatar_upload_url(&user.user_id, &state.s3_manager).await?;
+
+    Ok(Json(upload))
+}
+
+async fn confirm_avatar_upload(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> AppResult<Json<ProfileResponse>> {
+    let updated = UserService::confirm_avatar_update(&state.connection, &user.user_id).await?;
+
+    Ok(Json(
+        UserService::to_profile(&state.s3_manager, updated).await,
+    ))
+}
@@ -0,0 +1,162 @@
+use chrono::{DateTime, Utc};
+use shared::errors::{AppError, AppResult};
+
+use crate::{config::env_or, service::quest_proof_beliefs_service::FULL_BELIEF_WEIGHT};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeedRankingConfig {
+    pub recency_weight: f64,
+    pub belief_progress_weight: f64,
+    pub shared_lobby_weight: f64,
+    pub followed_author_weight: f64,
+    // Age after which the recency signal drops to one half
+    pub recency_half_life_hours: f64,
+    // How many of the newest proofs are considered for one ranked snapshot
+    pub candidate_limit: u64,
+}
+
+impl Default for FeedRankingConfig {
+    fn default() -> Self {
+        Self {
+            recency_weight: 1.0,
+            belief_progress_weight: 1.5,
+            shared_lobby_weight: 0.75,
+            followed_author_weight: 1.0,
+            recency_half_life_hours: 24.0,
+            candidate_limit: 300,
+        }
+    }
+}
+
+impl FeedRankingConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            recency_weight: env_or("FEED_RECENCY_WEIGHT", default.recency_weight),
+            belief_progress_weight: env_or(
+                "FEED_BELIEF_PROGRESS_WEIGHT",
+                default.belief_progress_weight,
+            ),
+            shared_lobby_weight: env_or("FEED_SHARED_LOBBY_WEIGHT", default.shared_lobby_weight),
+            followed_author_weight: env_or(
+                "FEED_FOLLOWED_AUTHOR_WEIGHT",
+                default.followed_author_weight,
+            ),
+            recency_half_life_hours: env_or(
+                "FEED_RECENCY_HALF_LIFE_HOURS",
+                default.recency_half_life_hours,
+            ),
+            candidate_limit: env_or("FEED_CANDIDATE_LIMIT", default.candidate_limit),
+        }
+    }
+}
+
+// Everything the score depends on, gathered up front so scoring never touches the database
+#[derive(Debug, Clone)]
+pub struct RankingSignals {
+    pub created_at: DateTime<Utc>,
+    // Weighted belief sum in thousandths, the same value completion is measured against
+    pub belief_weight: u32,
+    pub required_beliefs: u32,
+    pub shares_lobby: bool,
+    pub follows_author: bool,
+}
+
+pub struct FeedRanking;
+
+impl FeedRanking {
+    pub fn score(config: &FeedRankingConfig, signals: &RankingSignals, now: DateTime<Utc>) -> f64 {
+        let mut score = config.recency_weight * Self::recency(config, signals.created_at, now)
+            + config.belief_progress_weight
+                * Self::belief_progress(signals.belief_weight, signals.required_beliefs);
+
+        if signals.shares_lobby {
+            score += config.shared_lobby_weight;
+        }
+        if signals.follows_author {
+            score += config.followed_author_weight;
+        }
+
+        score
+    }
+
+    // 1.0 for a brand-new proof, halving every recency_half_life_hours
+    pub fn recency(
+        config: &FeedRankingConfig,
+        created_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> f64 {
+        if config.recency_half_life_hours <= 0.0 {
+            return 0.0;
+        }
+
+        let age_hours = (now - created_at).num_seconds().max(0) as f64 / 3600.0;
+        0.5_f64.powf(age_hours / config.recency_half_life_hours)
+    }
+
+    // Share of the weight needed for completion, so light beliefs from new accounts move a
+    // proof less than full ones. 0.0 once the proof already has enough.
+    pub fn belief_progress(belief_weight: u32, required_beliefs: u32) -> f64 {
+        let required_weight = required_beliefs.saturating_mul(FULL_BELIEF_WEIGHT);
+        if belief_weight >= required_weight {
+            return 0.0;
+        }
+
+        f64::from(belief_weight) / f64::from(required_weight)
+    }
+}
+
+// Position in a ranked snapshot. Scores are computed relative to `as_of`, so following pages
+// reuse it and proofs published later don't reshuffle the list.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RankCursor {
+    pub as_of: DateTime<Utc>,
+    pub score: f64,
+    pub ulid: String,
+}
+
+impl RankCursor {
+    pub fn encode(&self) -> String {
+        format!(
+            "{:x}.{:x}.{:x}-{}",
+            self.as_of.timestamp(),
+            self.as_of.timestamp_subsec_nanos(),
+            self.score.to_bits(),
+            self.ulid
+        )
+    }
+
+    pub fn decode(raw: &str) -> AppResult<Self> {
+        let invalid = || AppError::Custom("Invalid cursor".into());
+
+        let (head, ulid) = raw.split_once('-').ok_or_else(invalid)?;
+        let mut parts = head.split('.');
+        let (Some(secs), Some(nanos), Some(score), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid());
+        };
+
+        let secs = i64::from_str_radix(secs, 16).map_err(|_| invalid())?;
+        let nanos = u32::from_str_radix(nanos, 16).map_err(|_| invalid())?;
+        let as_of = DateTime::from_timestamp(secs, nanos).ok_or_else(invalid)?;
+
+        let score = f64::from_bits(u64::from_str_radix(score, 16).map_err(|_| invalid())?);
+        if !score.is_finite() {
+            return Err(invalid());
+        }
+
+        ulid::Ulid::from_string(ulid).map_err(|_| invalid())?;
+
+        Ok(Self {
+            as_of,
+            score,
+            ulid: ulid.to_owned(),
+        })
+    }
+
+    // Whether (score, ulid) comes after this cursor in (score DESC, ulid DESC) order
+    pub fn comes_after(&self, score: f64, ulid: &str) -> bool {
+        score < self.score || (score == self.score && ulid < self.ulid.as_str())
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::{HashMap, HashSet};
+
+use sea_orm::{Condition, QueryOrder, QuerySelect, SqlErr, entity::prelude::*, sea_query::Query};
+use shared::{
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        follow_dto::{FollowCountsResponse, FollowUserDto},
+    },
+    utils::cursor::Cursor,
+};
+
+use crate::{
+    entities::{
+        prelude::{User, UserFollow},
+        user_follows, users,
+    },
+    file_storage::s3_client::S3Manager,
+};
+
+pub struct FollowService;
+
+impl FollowService {
+    // Following someone twice is a no-op that returns the original follow.
+    #[tracing::instrument(skip(db))]
+    pub async fn follow(
+        db: &DatabaseConnection,
+        follower_id: &str,
+        following_id: &str,
+    ) -> AppResult<user_follows::Model> {
+        if follower_id == following_id {
+            return Err(AppError::Custom("Cannot follow yourself".into()));
+        }
+
+        if User::find_by_id(following_id).one(db).await?.is_none() {
+            return Err(AppError::NotFound);
+        }
+
+        if let Some(existing) = Self::find(db, follower_id, following_id).await? {
+            return Ok(existing);
+        }
+
+        let new_follow =
+            user_follows::ActiveModel::new_follow(follower_id.to_owned(), following_id.to_owned());
+
+        match new_follow.insert(db).await {
+            Ok(model) => {
+                tracing::info!("User followed");
+                Ok(model)
+            }
+            // A concurrent request created the same follow
+            Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                Self::find(db, follower_id, following_id)
+                    .await?
+                    .ok_or(AppError::Database(err))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    // Unfollowing someone you don't follow is not an error.
+    #[tracing::instrument(skip(db))]
+    pub async fn unfollow(
+        db: &DatabaseConnection,
+        follower_id: &str,
+        following_id: &str,
+    ) -> AppResult<()> {
+        UserFollow::delete_by_id((follower_id.to_owned(), following_id.to_owned()))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn find(
+        db: &DatabaseConnection,
+        follower_id: &str,
+        following_id: &str,
+    ) -> AppResult<Option<user_follows::Model>> {
+        UserFollow::find_by_id((follower_id.to_owned(), following_id.to_owned()))
+            .one(db)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn get_counts(
+        db: &DatabaseConnection,
+        user_id: &str,
+        viewer_id: &str,
+    ) -> AppResult<FollowCountsResponse> {
+        let followers = UserFollow::find()
+            .filter(user_follows::Column::FollowingId.eq(user_id))
+            .count(db)
+            .await?;
+
+        let following = UserFollow::find()
+            .filter(user_follows::Column::FollowerId.eq(user_id))
+            .count(db)
+            .await?;
+
+        let is_following = Self::find(db, viewer_id, user_id).await?.is_some();
+
+        Ok(FollowCountsResponse {
+            followers,
+            following,
+            is_following,
+        })
+    }
+
+    // Users following `user_id`, most recent follows first
+    pub async fn get_followers(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        user_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<FollowUserDto>> {
+        Self::list(
+            db,
+            s3,
+            user_follows::Column::FollowingId,
+            user_follows::Column::FollowerId,
+            user_id,
+            limit,
+            cursor,
+        )
+        .await
+    }
+
+    // Users `user_id` follows, most recent follows first
+    pub async fn get_following(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        user_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<FollowUserDto>> {
+        Self::list(
+            db,
+            s3,
+            user_follows::Column::FollowerId,
+            user_follows::Column::FollowingId,
+            user_id,
+            limit,
+            cursor,
+        )
+        .await
+    }
+
+    // Pages over follows where `owner_column` is the user and `other_column` the listed user
+    async fn list(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        owner_column: user_follows::Column,
+        other_column: user_follows::Column,
+        user_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<FollowUserDto>> {
+        let mut query = UserFollow::find().filter(owner_column.eq(user_id));
+
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query.filter(cursor.after(user_follows::Column::CreatedAt, other_column));
+        }
+
+        let rows = query
+            .order_by_desc(user_follows::Column::CreatedAt)
+            .order_by_desc(other_column)
+            .limit(limit + 1)
+            .all(db)
+            .await?;
+
+        let listed_id = |follow: &user_follows::Model| match other_column {
+            user_follows::Column::FollowerId => follow.follower_id.clone(),
+            _ => follow.following_id.clone(),
+        };
+
+        let page = Page::from_rows(rows, limit, |f| {
+            Cursor::new(f.created_at, &listed_id(f)).encode()
+        });
+
+        let ids: Vec<String> = page.items.iter().map(listed_id).collect();
+        let mut users: HashMap<String, users::Model> = User::find()
+            .filter(users::Column::Ulid.is_in(ids))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|u| (u.ulid.clone(), u))
+            .collect();
+
+        let avatar_keys: Vec<String> = users
+            .values()
+            .filter_map(|u| u.avatar_url.clone())
+            .collect();
+        let avatars = s3.get_download_urls(&avatar_keys, 3600).await;
+
+        let items = page
+            .items
+            .iter()
+            .filter_map(|follow| {
+                let user = users.remove(&listed_id(follow))?;
+                Some(FollowUserDto {
+                    ulid: user.ulid,
+                    username: user.username,
+                    avatar_url: user.avatar_url.and_then(|key| avatars.get(&key).cloned()),
+                    level: user.level,
+                    followed_at: follow.created_at,
+                })
+            })
+            .collect();
+
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    // Which of the given users `follower_id` follows, answered with a single query
+    pub async fn followed_ids(
+        db: &DatabaseConnection,
+        follower_id: &str,
+        candidate_ids: &[String],
+    ) -> AppResult<HashSet<String>> {
+        if candidate_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let followed: Vec<String> = UserFollow::find()
+            .select_only()
+            .column(user_follows::Column::FollowingId)
+            .filter(user_follows::Column::FollowerId.eq(follower_id))
+            .filter(user_follows::Column::FollowingId.is_in(candidate_ids.iter().cloned()))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(followed.into_iter().collect())
+    }
+
+    // Condition restricting an author column to users `follower_id` follows
+    pub fn followed_by_condition<C>(follower_id: &str, author_column: C) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        let followed = Query::select()
+            .column(user_follows::Column::FollowingId)
+            .from(UserFollow)
+            .and_where(user_follows::Column::FollowerId.eq(follower_id))
+            .to_owned();
+
+        Condition::all().add(author_column.in_subquery(followed))
+    }
+}
@@ -0,0 +1,283 @@
+use crate::{
+    entities::{
+        lobbies, lobbies_members, lobby_invites, lobby_join_requests,
+        prelude::{LobbyInvite, LobbyJoinRequest, User},
+    },
+    file_storage::s3_client::S3Manager,
+    service::{
+        lobby_member_service::LobbyMemberService, lobby_permissions::LobbyRank,
+        moderation_service::ModerationService, user_service::UserService,
+    },
+};
+use sea_orm::{
+    Condition, QueryOrder, TransactionTrait,
+    entity::prelude::*,
+    sea_query::{Expr, ExprTrait},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    models::lobby_dto::{JoinRequestDto, LobbyVisibility},
+    moderation::TextFilter,
+};
+use ulid::Ulid;
+
+// Invite codes and join requests, the two ways into a private lobby. Invites work for any
+// lobby, e.g. to hand out a link to an unlisted one.
+pub struct LobbyAccessService;
+
+impl LobbyAccessService {
+    // The last 12 characters of a ULID are 60 random bits, Crockford base32 encoded: short
+    // enough to type and nothing that leaks when the code was made
+    fn generate_code() -> String {
+        Ulid::new().to_string()[14..].to_owned()
+    }
+
+    pub async fn create_invite(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        expires_in: Option<chrono::Duration>,
+        max_uses: Option<u32>,
+    ) -> AppResult<lobby_invites::Model> {
+        Self::require_rank(db, lobby_id, actor_id, LobbyRank::Moderator).await?;
+
+        let invite = lobby_invites::ActiveModel::new_invite(
+            Self::generate_code(),
+            lobby_id.to_owned(),
+            actor_id.to_owned(),
+            max_uses,
+            expires_in.map(|duration| chrono::Utc::now() + duration),
+        );
+
+        Ok(invite.insert(db).await?)
+    }
+
+    // Newest first, used up and expired invites included so moderators see where people came from
+    pub async fn get_invites(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+    ) -> AppResult<Vec<lobby_invites::Model>> {
+        Self::require_rank(db, lobby_id, actor_id, LobbyRank::Moderator).await?;
+
+        LobbyInvite::find()
+            .filter(lobby_invites::Column::LobbyId.eq(lobby_id))
+            .order_by_desc(lobby_invites::Column::CreatedAt)
+            .all(db)
+            .await
+            .map_err(AppError::from)
+    }
+
+    pub async fn revoke_invite(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        code: &str,
+    ) -> AppResult<()> {
+        Self::require_rank(db, lobby_id, actor_id, LobbyRank::Moderator).await?;
+
+        let result = LobbyInvite::delete_many()
+            .filter(lobby_invites::Column::Code.eq(code))
+            .filter(lobby_invites::Column::LobbyId.eq(lobby_id))
+            .exec(db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
+    // The invite and its lobby, as long as the invite can still be used
+    pub async fn find_invite(
+        db: &DatabaseConnection,
+        code: &str,
+    ) -> AppResult<(lobby_invites::Model, lobbies::Model)> {
+        let invite = LobbyInvite::find_by_id(code.to_owned())
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        if !invite.is_usable() {
+            return Err(AppError::Custom("This invite is no longer valid".into()));
+        }
+
+        let lobby = LobbyMemberService::find_lobby(db, &invite.lobby_id).await?;
+        Ok((invite, lobby))
+    }
+
+    pub async fn accept_invite(
+        db: &DatabaseConnection,
+        code: &str,
+        user_id: &str,
+    ) -> AppResult<lobbies_members::Model> {
+        let (invite, _) = Self::find_invite(db, code).await?;
+
+        let txn = db.begin().await?;
+
+        // Counted in the UPDATE itself, so two people can't both take an invite's last use
+        let now = chrono::Utc::now();
+        let taken = LobbyInvite::update_many()
+            .col_expr(
+                lobby_invites::Column::Uses,
+                Expr::col(lobby_invites::Column::Uses).add(1),
+            )
+            .filter(lobby_invites::Column::Code.eq(code))
+            .filter(
+                Condition::any()
+                    .add(lobby_invites::Column::MaxUses.is_null())
+                    .add(
+                        Expr::col(lobby_invites::Column::Uses)
+                            .lt(Expr::col(lobby_invites::Column::MaxUses)),
+                    ),
+            )
+            .filter(
+                Condition::any()
+                    .add(lobby_invites::Column::ExpiresAt.is_null())
+                    .add(lobby_invites::Column::ExpiresAt.gt(now)),
+            )
+            .exec(&txn)
+            .await?;
+        if taken.rows_affected == 0 {
+            return Err(AppError::Custom("This invite is no longer valid".into()));
+        }
+
+        let member = LobbyMemberService::add_member(&txn, &invite.lobby_id, user_id).await?;
+        // An invite answers any pending request
+        LobbyJoinRequest::delete_by_id((invite.lobby_id, user_id.to_owned()))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await?;
+        Ok(member)
+    }
+
+    pub async fn request_to_join(
+        db: &DatabaseConnection,
+        filter: &TextFilter,
+        lobby_id: &str,
+        user_id: &str,
+        message: Option<String>,
+    ) -> AppResult<lobby_join_requests::Model> {
+        let lobby = LobbyMemberService::find_lobby(db, lobby_id).await?;
+        if lobby.visibility != LobbyVisibility::Private {
+            return Err(AppError::Custom(
+                "This lobby is open, join it directly".into(),
+            ));
+        }
+        if LobbyMemberService::is_member(db, lobby_id, user_id).await {
+            return Err(AppError::Custom("Already a member".into()));
+        }
+        if LobbyMemberService::active_ban(db, lobby_id, user_id)
+            .await?
+            .is_some()
+        {
+            return Err(AppError::Custom("You are banned from this lobby".into()));
+        }
+
+        let existing = LobbyJoinRequest::find_by_id((lobby_id.to_owned(), user_id.to_owned()))
+            .one(db)
+            .await?;
+        if existing.is_some() {
+            return Err(AppError::Custom("You already asked to join".into()));
+        }
+
+        let message = ModerationService::moderate_opt(filter, message)?;
+        let request = lobby_join_requests::ActiveModel::new_request(
+            lobby_id.to_owned(),
+            user_id.to_owned(),
+            message.map(|m| m.text),
+        );
+
+        Ok(request.insert(db).await?)
+    }
+
+    // Oldest first, the longest waiting get answered first
+    pub async fn get_join_requests(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        lobby_id: &str,
+        actor_id: &str,
+    ) -> AppResult<Vec<JoinRequestDto>> {
+        Self::require_rank(db, lobby_id, actor_id, LobbyRank::Admin).await?;
+
+        let requests = LobbyJoinRequest::find()
+            .filter(lobby_join_requests::Column::LobbyId.eq(lobby_id))
+            .order_by_asc(lobby_join_requests::Column::CreatedAt)
+            .find_also_related(User)
+            .all(db)
+            .await?;
+
+        let mut dtos = Vec::with_capacity(requests.len());
+        for (request, user) in requests {
+            let Some(user) = user else { continue };
+            dtos.push(JoinRequestDto {
+                lobby_id: request.lobby_id,
+                user: UserService::to_lobby_user(s3, user).await,
+                message: request.message,
+                created_at: request.created_at,
+            });
+        }
+        Ok(dtos)
+    }
+
+    pub async fn approve_join_request(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        user_id: &str,
+    ) -> AppResult<lobbies_members::Model> {
+        Self::require_rank(db, lobby_id, actor_id, LobbyRank::Admin).await?;
+
+        let txn = db.begin().await?;
+
+        let result = LobbyJoinRequest::delete_by_id((lobby_id.to_owned(), user_id.to_owned()))
+            .exec(&txn)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        let member = LobbyMemberService::add_member(&txn, lobby_id, user_id).await?;
+
+        txn.commit().await?;
+        Ok(member)
+    }
+
+    // Admins reject requests, users take back their own
+    pub async fn delete_join_request(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
+        if actor_id != user_id {
+            Self::require_rank(db, lobby_id, actor_id, LobbyRank::Admin).await?;
+        }
+
+        let result = LobbyJoinRequest::delete_by_id((lobby_id.to_owned(), user_id.to_owned()))
+            .exec(db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
+    async fn require_rank(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        rank: LobbyRank,
+    ) -> AppResult<()> {
+        let lobby = LobbyMemberService::find_lobby(db, lobby_id).await?;
+        let actor = LobbyMemberService::rank_of(db, &lobby, actor_id).await?;
+
+        if actor == LobbyRank::Stranger {
+            return Err(AppError::NotFound);
+        }
+        if actor < rank {
+            return Err(AppError::Custom(
+                "You don't have permission to do this".into(),
+            ));
+        }
+        Ok(())
+    }
+}
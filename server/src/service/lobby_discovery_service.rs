@@ -0,0 +1,281 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    Condition, FromQueryResult, QueryOrder, QuerySelect,
+    entity::prelude::*,
+    sea_query::{Alias, Expr, ExprTrait, Func},
+};
+use shared::{
+    endpoints::lobby_endpoints::{DiscoverQuery, LobbySort},
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        lobby_dto::{DiscoveredLobby, LobbyDto, LobbyVisibility, TopicCount},
+        report_dto::ReportTargetType,
+    },
+};
+use ulid::Ulid;
+
+use crate::entities::{lobbies, prelude::Lobby};
+use crate::service::{
+    lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+    report_service::ReportService, search_service::SearchService,
+};
+
+// Computed per lobby in the listing query itself, from the primary key of lobby_members and
+// the (lobby_id, ulid) index of messages
+const MEMBERS_COUNT: &str =
+    "(SELECT COUNT(*) FROM lobby_members WHERE lobby_members.lobby_id = lobbies.ulid)";
+const LAST_MESSAGE_ID: &str =
+    "(SELECT MAX(messages.ulid) FROM messages WHERE messages.lobby_id = lobbies.ulid)";
+// Message and lobby ULIDs both start with their creation time, so a lobby nobody wrote in yet
+// sorts by when it was made
+const LAST_ACTIVITY: &str = "COALESCE((SELECT MAX(messages.ulid) FROM messages \
+     WHERE messages.lobby_id = lobbies.ulid), lobbies.ulid)";
+const IS_MEMBER: &str = "EXISTS (SELECT 1 FROM lobby_members \
+     WHERE lobby_members.lobby_id = lobbies.ulid AND lobby_members.user_id = ?)";
+
+const MAX_TOPICS: u64 = 50;
+
+#[derive(Debug, FromQueryResult)]
+struct DiscoveryRow {
+    ulid: String,
+    name: String,
+    description: Option<String>,
+    topic: String,
+    owner_id: String,
+    visibility: LobbyVisibility,
+    created_at: DateTime<Utc>,
+    members_count: i64,
+    last_message_id: Option<String>,
+    is_member: bool,
+}
+
+impl DiscoveryRow {
+    fn last_activity(&self) -> &str {
+        self.last_message_id.as_deref().unwrap_or(&self.ulid)
+    }
+
+    fn into_discovered(self) -> DiscoveredLobby {
+        let last_activity_at = self
+            .last_message_id
+            .as_deref()
+            .and_then(|id| Ulid::from_string(id).ok())
+            .map(|id| DateTime::<Utc>::from(id.datetime()));
+
+        DiscoveredLobby {
+            members_count: self.members_count as u32,
+            is_member: self.is_member,
+            last_activity_at,
+            lobby: LobbyDto::from(lobbies::Model {
+                ulid: self.ulid,
+                name: self.name,
+                description: self.description,
+                topic: self.topic,
+                owner_id: self.owner_id,
+                visibility: self.visibility,
+                created_at: self.created_at,
+            }),
+        }
+    }
+}
+
+// Position in the discovery list: the last lobby's sort key, with its ULID breaking ties.
+// Newest needs no key of its own, lobby ULIDs already sort by creation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryCursor {
+    Members { members_count: u64, ulid: String },
+    Activity { last_activity: String, ulid: String },
+    Newest { ulid: String },
+}
+
+impl DiscoveryCursor {
+    fn of(sort: LobbySort, row: &DiscoveryRow) -> Self {
+        let ulid = row.ulid.clone();
+        match sort {
+            LobbySort::Members => Self::Members {
+                members_count: row.members_count as u64,
+                ulid,
+            },
+            LobbySort::Activity => Self::Activity {
+                last_activity: row.last_activity().to_owned(),
+                ulid,
+            },
+            LobbySort::Newest => Self::Newest { ulid },
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        match self {
+            Self::Members {
+                members_count,
+                ulid,
+            } => format!("{members_count:x}-{ulid}"),
+            Self::Activity {
+                last_activity,
+                ulid,
+            } => format!("{last_activity}-{ulid}"),
+            Self::Newest { ulid } => ulid.clone(),
+        }
+    }
+
+    // The sort has to be the one the cursor was made for
+    pub fn decode(sort: LobbySort, raw: &str) -> AppResult<Self> {
+        let invalid = || AppError::Custom("Invalid cursor".into());
+        let valid_ulid = |raw: &str| {
+            Ulid::from_string(raw)
+                .map(|_| raw.to_owned())
+                .map_err(|_| invalid())
+        };
+
+        if sort == LobbySort::Newest {
+            return Ok(Self::Newest {
+                ulid: valid_ulid(raw)?,
+            });
+        }
+
+        let (key, ulid) = raw.split_once('-').ok_or_else(invalid)?;
+        let ulid = valid_ulid(ulid)?;
+        match sort {
+            LobbySort::Members => Ok(Self::Members {
+                members_count: u64::from_str_radix(key, 16).map_err(|_| invalid())?,
+                ulid,
+            }),
+            _ => Ok(Self::Activity {
+                last_activity: valid_ulid(key)?,
+                ulid,
+            }),
+        }
+    }
+
+    // Lobbies strictly after this cursor in (sort key DESC, ulid DESC) order
+    fn after(&self) -> Condition {
+        match self {
+            Self::Members {
+                members_count,
+                ulid,
+            } => Self::keyset(Expr::cust(MEMBERS_COUNT), *members_count as i64, ulid),
+            Self::Activity {
+                last_activity,
+                ulid,
+            } => Self::keyset(Expr::cust(LAST_ACTIVITY), last_activity.as_str(), ulid),
+            Self::Newest { ulid } => Condition::all().add(lobbies::Column::Ulid.lt(ulid.as_str())),
+        }
+    }
+
+    fn keyset(key: Expr, value: impl Into<Value>, ulid: &str) -> Condition {
+        let value = value.into();
+        Condition::any().add(key.clone().lt(value.clone())).add(
+            Condition::all()
+                .add(key.eq(value))
+                .add(lobbies::Column::Ulid.lt(ulid)),
+        )
+    }
+}
+
+pub struct LobbyDiscoveryService;
+
+impl LobbyDiscoveryService {
+    // Listed lobbies the user isn't banned from, with their member counts, in one query per page
+    pub async fn discover(
+        db: &DatabaseConnection,
+        user_id: &str,
+        query: &DiscoverQuery,
+        limit: u64,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<DiscoveredLobby>> {
+        let cursor = query
+            .cursor
+            .as_deref()
+            .map(|raw| DiscoveryCursor::decode(query.sort, raw))
+            .transpose()?;
+
+        let mut select = Lobby::find()
+            .column_as(Expr::cust(MEMBERS_COUNT), "members_count")
+            .column_as(Expr::cust(LAST_MESSAGE_ID), "last_message_id")
+            .column_as(Expr::cust_with_values(IS_MEMBER, [user_id]), "is_member")
+            .filter(Self::visible_condition(user_id, report_hide_threshold));
+
+        if let Some(q) = query.q.as_deref()
+            && let Some(matching) = SearchService::lobbies_matching(db.get_database_backend(), q)?
+        {
+            select = select.filter(matching);
+        }
+        if let Some(topic) = query.topic.as_deref() {
+            select = select.filter(lobbies::Column::Topic.eq(topic.trim()));
+        }
+        if let Some(cursor) = &cursor {
+            select = select.filter(cursor.after());
+        }
+
+        select = match query.sort {
+            LobbySort::Members => select.order_by_desc(Expr::cust(MEMBERS_COUNT)),
+            LobbySort::Activity => select.order_by_desc(Expr::cust(LAST_ACTIVITY)),
+            LobbySort::Newest => select,
+        };
+
+        let rows = select
+            .order_by_desc(lobbies::Column::Ulid)
+            .limit(limit + 1)
+            .into_model::<DiscoveryRow>()
+            .all(db)
+            .await?;
+        let page = Page::from_rows(rows, limit, |row| {
+            DiscoveryCursor::of(query.sort, row).encode()
+        });
+
+        Ok(Page {
+            items: page
+                .items
+                .into_iter()
+                .map(DiscoveryRow::into_discovered)
+                .collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    // Topics to filter discovery by, most used first
+    pub async fn get_topics(
+        db: &DatabaseConnection,
+        user_id: &str,
+        report_hide_threshold: u64,
+    ) -> AppResult<Vec<TopicCount>> {
+        let topics: Vec<(String, i64)> = Lobby::find()
+            .select_only()
+            .column(lobbies::Column::Topic)
+            .column_as(
+                Func::count(Expr::col(lobbies::Column::Ulid)),
+                "lobbies_count",
+            )
+            .filter(Self::visible_condition(user_id, report_hide_threshold))
+            .group_by(lobbies::Column::Topic)
+            .order_by_desc(Expr::col(Alias::new("lobbies_count")))
+            .order_by_asc(lobbies::Column::Topic)
+            .limit(MAX_TOPICS)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(topics
+            .into_iter()
+            .map(|(topic, count)| TopicCount {
+                topic,
+                lobbies_count: count as u32,
+            })
+            .collect())
+    }
+
+    fn visible_condition(user_id: &str, report_hide_threshold: u64) -> Condition {
+        Condition::all()
+            .add(LobbyService::listed_condition(user_id))
+            .add(LobbyMemberService::not_banned_condition(
+                user_id,
+                lobbies::Column::Ulid,
+            ))
+            .add(ReportService::not_hidden_condition(
+                ReportTargetType::Lobby,
+                lobbies::Column::Ulid,
+                report_hide_threshold,
+            ))
+    }
+}
@@ -1,27 +1,62 @@
-use crate::entities::{
-    lobbies_members::{self},
-    prelude::{Lobby, LobbyMembers},
+use crate::{
+    entities::{
+        lobbies, lobbies_members, lobby_bans, messages,
+        prelude::{Lobby, LobbyBan, LobbyMembers, Message, User},
+        users,
+    },
+    file_storage::s3_client::S3Manager,
+    service::{
+        lobby_permissions::LobbyRank, presence::PresenceRegistry, user_service::UserService,
+    },
+};
+use sea_orm::{
+    ActiveValue::Set,
+    Condition, QueryOrder, QuerySelect, TransactionTrait,
+    entity::prelude::*,
+    sea_query::{Query, SelectStatement},
 };
-use sea_orm::{ActiveValue::Set, entity::prelude::*};
 use shared::{
     errors::{AppError, AppResult},
-    models::lobby_dto::Role,
+    models::{
+        lobby_dto::{LobbyVisibility, Role},
+        message_dto::LobbyUserDto,
+    },
 };
+use std::collections::HashSet;
 
 pub struct LobbyMemberService;
 
 impl LobbyMemberService {
+    // Public and unlisted lobbies only, private ones are entered through an invite or an
+    // approved join request
     pub async fn join_lobby(
         db: &DatabaseConnection,
         lobby_id: String,
         user_id: String,
     ) -> AppResult<lobbies_members::Model> {
-        let lobby_exists = Lobby::find_by_id(lobby_id.clone()).one(db).await?.is_some();
-        if !lobby_exists {
-            return Err(AppError::NotFound);
+        let lobby = Self::find_lobby(db, &lobby_id).await?;
+        if lobby.visibility == LobbyVisibility::Private {
+            return Err(AppError::Custom(
+                "This lobby is private, join it through an invite or a join request".into(),
+            ));
+        }
+
+        Self::add_member(db, &lobby_id, &user_id).await
+    }
+
+    pub(crate) async fn add_member<C>(
+        db: &C,
+        lobby_id: &str,
+        user_id: &str,
+    ) -> AppResult<lobbies_members::Model>
+    where
+        C: ConnectionTrait,
+    {
+        if Self::active_ban(db, lobby_id, user_id).await?.is_some() {
+            return Err(AppError::Custom("You are banned from this lobby".into()));
         }
 
-        let existing = LobbyMembers::find_by_id((lobby_id.clone(), user_id.clone()))
+        let existing = LobbyMembers::find_by_id((lobby_id.to_owned(), user_id.to_owned()))
             .one(db)
             .await?;
 
@@ -29,16 +64,235 @@ impl LobbyMemberService {
             return Err(AppError::Custom("Already a member".into()));
         }
 
+        // History from before joining doesn't count as unread
+        let latest_message: Option<String> = Message::find()
+            .select_only()
+            .column(messages::Column::Ulid)
+            .filter(messages::Column::LobbyId.eq(lobby_id))
+            .order_by_desc(messages::Column::Ulid)
+            .into_tuple()
+            .one(db)
+            .await?;
+
         let new_member = lobbies_members::ActiveModel {
-            lobby_id: Set(lobby_id),
-            user_id: Set(user_id),
+            lobby_id: Set(lobby_id.to_owned()),
+            user_id: Set(user_id.to_owned()),
             joined_at: Set(chrono::Utc::now()),
             role: Set(Role::Member),
+            last_read_message_id: Set(latest_message),
         };
 
         Ok(new_member.insert(db).await?)
     }
 
+    // The owner can't leave, the lobby would be left without one
+    pub async fn leave_lobby(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        if lobby.owner_id == user_id {
+            return Err(AppError::Custom(
+                "Transfer the ownership before leaving the lobby".into(),
+            ));
+        }
+
+        Self::remove_member(db, lobby_id, user_id).await
+    }
+
+    pub async fn kick_member(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        target_id: &str,
+    ) -> AppResult<()> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        let actor = Self::rank_of(db, &lobby, actor_id).await?;
+        let target = Self::rank_of(db, &lobby, target_id).await?;
+
+        if target == LobbyRank::Stranger {
+            return Err(AppError::NotFound);
+        }
+        if !actor.can_remove(target) {
+            return Err(AppError::Custom("You can't remove this member".into()));
+        }
+
+        Self::remove_member(db, lobby_id, target_id).await
+    }
+
+    // Removes the user from the lobby and keeps them out until the ban expires. Users who
+    // aren't members can be banned too, before they ever join.
+    pub async fn ban_member(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        target_id: &str,
+        duration: Option<chrono::Duration>,
+        reason: Option<String>,
+    ) -> AppResult<lobby_bans::Model> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        let actor = Self::rank_of(db, &lobby, actor_id).await?;
+        let target = Self::rank_of(db, &lobby, target_id).await?;
+
+        if !actor.can_remove(target) {
+            return Err(AppError::Custom("You can't ban this user".into()));
+        }
+        if target == LobbyRank::Stranger && UserService::find_by_id(db, target_id).await?.is_none()
+        {
+            return Err(AppError::NotFound);
+        }
+
+        let txn = db.begin().await?;
+
+        LobbyMembers::delete_by_id((lobby_id.to_owned(), target_id.to_owned()))
+            .exec(&txn)
+            .await?;
+        LobbyBan::delete_by_id((lobby_id.to_owned(), target_id.to_owned()))
+            .exec(&txn)
+            .await?;
+
+        let ban = lobby_bans::ActiveModel::new_ban(
+            lobby_id.to_owned(),
+            target_id.to_owned(),
+            actor_id.to_owned(),
+            reason,
+            duration.map(|duration| chrono::Utc::now() + duration),
+        )
+        .insert(&txn)
+        .await?;
+
+        txn.commit().await?;
+        Ok(ban)
+    }
+
+    pub async fn unban_member(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        target_id: &str,
+    ) -> AppResult<()> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        if Self::rank_of(db, &lobby, actor_id).await? < LobbyRank::Moderator {
+            return Err(AppError::Custom("You can't unban users".into()));
+        }
+
+        let result = LobbyBan::delete_by_id((lobby_id.to_owned(), target_id.to_owned()))
+            .exec(db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
+    pub async fn active_ban<C>(
+        db: &C,
+        lobby_id: &str,
+        user_id: &str,
+    ) -> AppResult<Option<lobby_bans::Model>>
+    where
+        C: ConnectionTrait,
+    {
+        let ban = LobbyBan::find_by_id((lobby_id.to_owned(), user_id.to_owned()))
+            .one(db)
+            .await?;
+        Ok(ban.filter(lobby_bans::Model::is_active))
+    }
+
+    pub async fn set_role(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        target_id: &str,
+        role: Role,
+    ) -> AppResult<lobbies_members::Model> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        let actor = Self::rank_of(db, &lobby, actor_id).await?;
+
+        let member = LobbyMembers::find_by_id((lobby_id.to_owned(), target_id.to_owned()))
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let target = LobbyRank::of(&member.role, lobby.owner_id == target_id);
+
+        if !actor.can_assign(target, &role) {
+            return Err(AppError::Custom(
+                "You can't give this member that role".into(),
+            ));
+        }
+
+        let mut active: lobbies_members::ActiveModel = member.into();
+        active.role = Set(role);
+        Ok(active.update(db).await?)
+    }
+
+    // The new owner becomes an admin, the previous one stays an admin
+    pub async fn transfer_ownership(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        owner_id: &str,
+        new_owner_id: &str,
+    ) -> AppResult<lobbies::Model> {
+        let lobby = Self::find_lobby(db, lobby_id).await?;
+        if lobby.owner_id != owner_id {
+            return Err(AppError::Custom(
+                "Only the owner can transfer the lobby".into(),
+            ));
+        }
+        if new_owner_id == owner_id {
+            return Err(AppError::Custom("You already own this lobby".into()));
+        }
+
+        let member = LobbyMembers::find_by_id((lobby_id.to_owned(), new_owner_id.to_owned()))
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let txn = db.begin().await?;
+
+        let mut active_member: lobbies_members::ActiveModel = member.into();
+        active_member.role = Set(Role::Admin);
+        active_member.update(&txn).await?;
+
+        let mut active_lobby: lobbies::ActiveModel = lobby.into();
+        active_lobby.owner_id = Set(new_owner_id.to_owned());
+        let lobby = active_lobby.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(lobby)
+    }
+
+    // Members with a live socket in the lobby right now, sorted by username
+    pub async fn get_online_members(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        presence: &PresenceRegistry,
+        lobby_id: &str,
+        user_id: &str,
+    ) -> AppResult<Vec<LobbyUserDto>> {
+        if !Self::is_member(db, lobby_id, user_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        let online = presence.online_users(lobby_id);
+        if online.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let users = User::find()
+            .filter(users::Column::Ulid.is_in(online))
+            .order_by_asc(users::Column::Username)
+            .all(db)
+            .await?;
+
+        let mut members = Vec::with_capacity(users.len());
+        for user in users {
+            members.push(UserService::to_lobby_user(s3, user).await);
+        }
+        Ok(members)
+    }
+
     pub async fn is_member(db: &DatabaseConnection, lobby_id: &str, user_id: &str) -> bool {
         LobbyMembers::find_by_id((lobby_id.to_string(), user_id.to_string()))
             .one(db)
@@ -47,14 +301,14 @@ impl LobbyMemberService {
             .unwrap_or(false)
     }
 
-    // I haven't figured out yet how to make a role system, I don't know which approach is the best
+    // Moderators and up can moderate the lobby's content, e.g. delete others' messages
     pub async fn can_manage_lobby(db: &DatabaseConnection, lobby_id: &str, user_id: &str) -> bool {
         if let Ok(Some(member)) =
             LobbyMembers::find_by_id((lobby_id.to_string(), user_id.to_string()))
                 .one(db)
                 .await
         {
-            return member.role == Role::Admin || member.role == Role::Moderator;
+            return LobbyRank::of(&member.role, false) >= LobbyRank::Moderator;
         }
         false
     }
@@ -69,4 +323,114 @@ impl LobbyMemberService {
             .await
             .map_err(AppError::from)
     }
+
+    // Which of the given users share at least one lobby with `user_id`
+    pub async fn lobby_peers(
+        db: &DatabaseConnection,
+        user_id: &str,
+        candidate_ids: &[String],
+    ) -> AppResult<HashSet<String>> {
+        if candidate_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let peers: Vec<String> = LobbyMembers::find()
+            .select_only()
+            .column(lobbies_members::Column::UserId)
+            .distinct()
+            .filter(lobbies_members::Column::UserId.is_in(candidate_ids.iter().cloned()))
+            .filter(lobbies_members::Column::LobbyId.in_subquery(Self::own_lobbies(user_id)))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(peers.into_iter().collect())
+    }
+
+    // Condition restricting a user column to members of any lobby `user_id` belongs to
+    pub fn shares_lobby_condition<C>(user_id: &str, user_column: C) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        let peers = Query::select()
+            .column(lobbies_members::Column::UserId)
+            .from(LobbyMembers)
+            .and_where(lobbies_members::Column::LobbyId.in_subquery(Self::own_lobbies(user_id)))
+            .to_owned();
+
+        Condition::all().add(user_column.in_subquery(peers))
+    }
+
+    // Condition restricting a lobby column to lobbies `user_id` is a member of
+    pub fn member_of_condition<C>(user_id: &str, lobby_column: C) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        Condition::all().add(lobby_column.in_subquery(Self::own_lobbies(user_id)))
+    }
+
+    // Condition leaving out the lobbies `user_id` is banned from right now
+    pub fn not_banned_condition<C>(user_id: &str, lobby_column: C) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        let bans = Query::select()
+            .column(lobby_bans::Column::LobbyId)
+            .from(LobbyBan)
+            .and_where(lobby_bans::Column::UserId.eq(user_id))
+            .cond_where(
+                Condition::any()
+                    .add(lobby_bans::Column::ExpiresAt.is_null())
+                    .add(lobby_bans::Column::ExpiresAt.gt(chrono::Utc::now())),
+            )
+            .to_owned();
+
+        Condition::all().add(lobby_column.not_in_subquery(bans))
+    }
+
+    pub(crate) async fn find_lobby(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+    ) -> AppResult<lobbies::Model> {
+        Lobby::find_by_id(lobby_id.to_owned())
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    pub(crate) async fn rank_of(
+        db: &DatabaseConnection,
+        lobby: &lobbies::Model,
+        user_id: &str,
+    ) -> AppResult<LobbyRank> {
+        let member = LobbyMembers::find_by_id((lobby.ulid.clone(), user_id.to_owned()))
+            .one(db)
+            .await?;
+
+        Ok(member.map_or(LobbyRank::Stranger, |member| {
+            LobbyRank::of(&member.role, lobby.owner_id == user_id)
+        }))
+    }
+
+    async fn remove_member(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
+        let result = LobbyMembers::delete_by_id((lobby_id.to_owned(), user_id.to_owned()))
+            .exec(db)
+            .await?;
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+        Ok(())
+    }
+
+    fn own_lobbies(user_id: &str) -> SelectStatement {
+        Query::select()
+            .column(lobbies_members::Column::LobbyId)
+            .from(LobbyMembers)
+            .and_where(lobbies_members::Column::UserId.eq(user_id))
+            .to_owned()
+    }
 }
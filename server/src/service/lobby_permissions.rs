@@ -0,0 +1,45 @@
+use shared::models::lobby_dto::Role;
+
+// Where someone stands in a lobby. The owner is an admin who also outranks the other admins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LobbyRank {
+    Stranger,
+    Member,
+    Helper,
+    Moderator,
+    Admin,
+    Owner,
+}
+
+impl LobbyRank {
+    pub fn of(role: &Role, is_owner: bool) -> Self {
+        if is_owner {
+            return Self::Owner;
+        }
+
+        match role {
+            Role::Stranger => Self::Stranger,
+            Role::Member => Self::Member,
+            Role::Helper => Self::Helper,
+            Role::Moderator => Self::Moderator,
+            Role::Admin => Self::Admin,
+        }
+    }
+
+    // Kicks and bans. Moderators remove members, admins also moderators, and only the owner
+    // removes admins.
+    pub fn can_remove(self, target: Self) -> bool {
+        self >= Self::Moderator && self > target
+    }
+
+    // Role changes need an admin, who can hand out roles up to moderator. Only the owner makes
+    // admins. Nobody changes the role of someone at their own rank or above.
+    pub fn can_assign(self, target: Self, role: &Role) -> bool {
+        let new_rank = Self::of(role, false);
+        self >= Self::Admin
+            && self > target
+            && self > new_rank
+            && target != Self::Stranger
+            && new_rank != Self::Stranger
+    }
+}
@@ -1,12 +1,20 @@
 use crate::entities::{
     lobbies::{self, LobbyToSelfMember},
     lobbies_members,
-    prelude::Lobby,
+    prelude::{Lobby, LobbyInvite},
 };
-use sea_orm::{ActiveValue::Set, TransactionTrait, entity::prelude::*};
+use crate::service::{
+    lobby_member_service::LobbyMemberService, lobby_permissions::LobbyRank,
+    moderation_service::ModerationService,
+};
+use sea_orm::{ActiveValue::Set, Condition, TransactionTrait, entity::prelude::*};
 use shared::{
     errors::{AppError, AppResult},
-    models::lobby_dto::Role,
+    models::{
+        lobby_dto::{LobbyVisibility, Role},
+        report_dto::ReportTargetType,
+    },
+    moderation::TextFilter,
 };
 use ulid::Ulid;
 
@@ -15,21 +23,27 @@ pub struct LobbyService;
 impl LobbyService {
     pub async fn create_lobby(
         db: &DatabaseConnection,
+        filter: &TextFilter,
         owner_id: String,
         name: String,
         topic: String,
         description: Option<String>,
+        visibility: LobbyVisibility,
     ) -> AppResult<lobbies::Model> {
+        let name = filter.moderate(&name)?;
+        let description = ModerationService::moderate_opt(filter, description)?;
+
         let txn = db.begin().await?;
 
         let lobby_id = Ulid::new().to_string();
 
         let new_lobby = lobbies::ActiveModel {
             ulid: Set(lobby_id.clone()),
-            name: Set(name),
+            name: Set(name.text.clone()),
             topic: Set(topic),
-            description: Set(description),
+            description: Set(description.as_ref().map(|d| d.text.clone())),
             owner_id: Set(owner_id.clone()),
+            visibility: Set(visibility),
             created_at: Set(chrono::Utc::now()),
         };
         let lobby_model = new_lobby.insert(&txn).await?;
@@ -39,9 +53,18 @@ impl LobbyService {
             user_id: Set(owner_id),
             joined_at: Set(chrono::Utc::now()),
             role: Set(Role::Admin),
+            last_read_message_id: Set(None),
         };
         membership.insert(&txn).await?;
 
+        ModerationService::flag_for_review(
+            &txn,
+            ReportTargetType::Lobby,
+            &lobby_model.ulid,
+            &[Some(&name), description.as_ref()],
+        )
+        .await?;
+
         txn.commit().await?;
         Ok(lobby_model)
     }
@@ -53,16 +76,42 @@ impl LobbyService {
             .ok_or(AppError::NotFound)
     }
 
+    // Private lobbies look like they don't exist to anyone but their members, unless `invite`
+    // is a usable code of that lobby
+    pub async fn find_visible(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        user_id: &str,
+        invite: Option<&str>,
+    ) -> AppResult<lobbies::Model> {
+        let lobby = Self::find_by_id(db, lobby_id).await?;
+        if lobby.visibility != LobbyVisibility::Private
+            || LobbyMemberService::is_member(db, lobby_id, user_id).await
+        {
+            return Ok(lobby);
+        }
+
+        if let Some(code) = invite
+            && let Some(invite) = LobbyInvite::find_by_id(code.to_owned()).one(db).await?
+            && invite.lobby_id == lobby.ulid
+            && invite.is_usable()
+        {
+            return Ok(lobby);
+        }
+        Err(AppError::NotFound)
+    }
+
     pub async fn get_all_lobbies(db: &DatabaseConnection) -> AppResult<Vec<lobbies::Model>> {
         Lobby::find().all(db).await.map_err(AppError::from)
     }
 
-    // Get all lobbies and a boolean indicating if the user is a member.
+    // Get all lobbies listed for the user and a boolean indicating if the user is a member.
     pub async fn get_lobbies_with_membership_status(
         db: &DatabaseConnection,
         user_id: &str,
     ) -> AppResult<Vec<(lobbies::Model, bool)>> {
         let lobbies_with_member = Lobby::find()
+            .filter(Self::listed_condition(user_id))
             .find_also_linked(LobbyToSelfMember(user_id.to_owned()))
             .all(db)
             .await?;
@@ -74,4 +123,32 @@ impl LobbyService {
 
         Ok(result)
     }
+
+    pub async fn set_visibility(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        actor_id: &str,
+        visibility: LobbyVisibility,
+    ) -> AppResult<lobbies::Model> {
+        let lobby = Self::find_by_id(db, lobby_id).await?;
+        if LobbyMemberService::rank_of(db, &lobby, actor_id).await? < LobbyRank::Admin {
+            return Err(AppError::Custom(
+                "Only admins can change who can join".into(),
+            ));
+        }
+
+        let mut active: lobbies::ActiveModel = lobby.into();
+        active.visibility = Set(visibility);
+        Ok(active.update(db).await?)
+    }
+
+    // Lobbies that show up in lists for `user_id`: the public ones and their own
+    pub fn listed_condition(user_id: &str) -> Condition {
+        Condition::any()
+            .add(lobbies::Column::Visibility.eq(LobbyVisibility::Public))
+            .add(LobbyMemberService::member_of_condition(
+                user_id,
+                lobbies::Column::Ulid,
+            ))
+    }
 }
@@ -1,17 +1,52 @@
-use crate::entities::prelude::Message;
-use crate::{entities::messages, service::lobby_member_service::LobbyMemberService};
-use sea_orm::{QueryOrder, QuerySelect, entity::prelude::*};
-use shared::errors::{AppError, AppResult};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use crate::entities::prelude::{LobbyMembers, Message, MessageMention, MessageReaction, User};
+use crate::events::{DomainEvent, EventBus};
+use crate::service::{moderation_service::ModerationService, report_service::ReportService};
+use crate::{
+    entities::{lobbies_members, message_mentions, message_reactions, messages, users},
+    service::lobby_member_service::LobbyMemberService,
+};
+use sea_orm::{
+    ActiveValue::Set,
+    Condition, QueryOrder, QuerySelect, TransactionTrait,
+    entity::prelude::*,
+    sea_query::{Expr, Func, OnConflict, Query},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        message_dto::{MessageDto, ReactionDto},
+        report_dto::ReportTargetType,
+    },
+    moderation::TextFilter,
+    utils::{cursor::Cursor, mentions::parse_mentions},
+};
+
+// Long enough for flags and emoji joined into families, short enough to keep words out
+const MAX_REACTION_CHARS: usize = 8;
+
+// One write lock per lobby, see MessageService::lobby_write_lock. Kept per process, like the
+// ULID generator.
+static LOBBY_WRITES: LazyLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(Default::default);
 
 pub struct MessageService;
 
 impl MessageService {
     pub async fn send_message(
         db: &DatabaseConnection,
+        events: &EventBus,
+        filter: &TextFilter,
         lobby_id: String,
         author_id: String,
         content: String,
-    ) -> AppResult<messages::Model> {
+        reply_to: Option<String>,
+    ) -> AppResult<MessageDto> {
         let is_member = LobbyMemberService::is_member(db, &lobby_id, &author_id).await;
         if !is_member {
             return Err(AppError::Custom(
@@ -19,10 +54,119 @@ impl MessageService {
             ));
         }
 
-        let new_message = messages::ActiveModel::new_message(lobby_id, author_id, content);
-        let saved_message = new_message.insert(db).await.map_err(AppError::from)?;
+        if content.trim().is_empty() {
+            return Err(AppError::Custom("Message cannot be empty".into()));
+        }
+
+        // Only live messages of the same lobby can be replied to
+        if let Some(parent_id) = &reply_to {
+            Self::find_live(db, &lobby_id, parent_id).await?;
+        }
+
+        let content = filter.moderate(&content)?;
+        let author = User::find_by_id(author_id.clone())
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        // Held until the commit, the ID is taken inside
+        let lobby_write = Self::lobby_write_lock(&lobby_id);
+        let _writing = lobby_write.lock().await;
+        let txn = db.begin().await?;
+
+        let new_message =
+            messages::ActiveModel::new_message(lobby_id, author_id, content.text.clone(), reply_to);
+        let saved_message = new_message.insert(&txn).await.map_err(AppError::from)?;
+        let mentions = Self::save_mentions(&txn, &saved_message).await?;
+
+        ModerationService::flag_for_review(
+            &txn,
+            ReportTargetType::Message,
+            &saved_message.ulid,
+            &[Some(&content)],
+        )
+        .await?;
+
+        txn.commit().await?;
+        Self::publish_mentions(events, &saved_message, &mentions);
+
+        let mut message = saved_message.into_dto(author.username);
+        message.mentions = mentions;
+        Ok(message)
+    }
+
+    // `since` replays and the socket dedupe treat every ID up to the newest one a client has as
+    // delivered. That only holds if a smaller ID can't commit after a bigger one, so sends of
+    // one lobby are serialized through this lock.
+    pub(crate) fn lobby_write_lock(lobby_id: &str) -> Arc<tokio::sync::Mutex<()>> {
+        let mut locks = LOBBY_WRITES.lock().unwrap_or_else(|e| e.into_inner());
+        locks.entry(lobby_id.to_owned()).or_default().clone()
+    }
+
+    // Only the author edits, and only while the message is live. Mentions follow the new text,
+    // members mentioned for the first time get notified.
+    pub async fn edit_message(
+        db: &DatabaseConnection,
+        events: &EventBus,
+        filter: &TextFilter,
+        lobby_id: &str,
+        message_id: &str,
+        user_id: &str,
+        content: String,
+    ) -> AppResult<MessageDto> {
+        let message = Self::find_live(db, lobby_id, message_id).await?;
+        if message.author_id != user_id {
+            return Err(AppError::NotFound);
+        }
+
+        if content.trim().is_empty() {
+            return Err(AppError::Custom("Message cannot be empty".into()));
+        }
+        let content = filter.moderate(&content)?;
 
-        Ok(saved_message)
+        let txn = db.begin().await?;
+
+        let previous: HashSet<String> = MessageMention::find()
+            .select_only()
+            .column(message_mentions::Column::UserId)
+            .filter(message_mentions::Column::MessageId.eq(message_id))
+            .into_tuple()
+            .all(&txn)
+            .await?
+            .into_iter()
+            .collect();
+        MessageMention::delete_many()
+            .filter(message_mentions::Column::MessageId.eq(message_id))
+            .exec(&txn)
+            .await?;
+
+        let mut active: messages::ActiveModel = message.into();
+        active.content = Set(content.text.clone());
+        active.edited_at = Set(Some(chrono::Utc::now()));
+        let edited = active.update(&txn).await?;
+        let mentions = Self::save_mentions(&txn, &edited).await?;
+
+        ModerationService::flag_for_review(
+            &txn,
+            ReportTargetType::Message,
+            &edited.ulid,
+            &[Some(&content)],
+        )
+        .await?;
+
+        txn.commit().await?;
+
+        let new_mentions: Vec<String> = mentions
+            .iter()
+            .filter(|id| !previous.contains(*id))
+            .cloned()
+            .collect();
+        Self::publish_mentions(events, &edited, &new_mentions);
+
+        let author = User::find_by_id(edited.author_id.clone()).one(db).await?;
+        Ok(Self::with_details(db, vec![(edited, author)])
+            .await?
+            .remove(0))
     }
 
     pub async fn get_lobby_messages(
@@ -30,42 +174,258 @@ impl MessageService {
         lobby_id: &str,
         user_id: &str,
         limit: u64,
-        offset: u64,
-    ) -> AppResult<Vec<messages::Model>> {
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<MessageDto>> {
         if !LobbyMemberService::is_member(db, lobby_id, user_id).await {
             return Err(AppError::NotFound);
         }
 
-        let messages = Message::find()
+        let mut query = Message::find()
             .filter(messages::Column::LobbyId.eq(lobby_id))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Message,
+                messages::Column::Ulid,
+                report_hide_threshold,
+            ));
+
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query.filter(cursor.after(messages::Column::CreatedAt, messages::Column::Ulid));
+        }
+
+        let rows = query
+            .find_also_related(User)
             .order_by_desc(messages::Column::CreatedAt)
-            .limit(limit)
-            .offset(offset)
+            .order_by_desc(messages::Column::Ulid)
+            .limit(limit + 1)
             .all(db)
             .await?;
+        let messages = Self::with_details(db, rows).await?;
 
-        Ok(messages)
+        Ok(Page::from_rows(messages, limit, |m: &MessageDto| {
+            Cursor::new(m.created_at, &m.ulid).encode()
+        }))
     }
 
-    pub async fn delete_message(
+    // Catch-up after a reconnect. Message ULIDs only grow, so "newer than the last one seen"
+    // is a plain comparison and needs no timestamp.
+    pub async fn get_messages_since(
         db: &DatabaseConnection,
-        message_id: &str,
+        lobby_id: &str,
         user_id: &str,
-    ) -> AppResult<()> {
-        let message = Message::find_by_id(message_id.to_string())
+        since: &str,
+        limit: u64,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<MessageDto>> {
+        if !LobbyMemberService::is_member(db, lobby_id, user_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        let rows = Message::find()
+            .filter(messages::Column::LobbyId.eq(lobby_id))
+            .filter(messages::Column::Ulid.gt(since))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Message,
+                messages::Column::Ulid,
+                report_hide_threshold,
+            ))
+            .find_also_related(User)
+            .order_by_asc(messages::Column::Ulid)
+            .limit(limit + 1)
+            .all(db)
+            .await?;
+        let messages = Self::with_details(db, rows).await?;
+
+        Ok(Page::from_rows(messages, limit, |m: &MessageDto| {
+            m.ulid.clone()
+        }))
+    }
+
+    // Moves the member's read marker up to `message_id`. Returns false when the marker was
+    // already there or further, an older message never moves it back.
+    pub async fn mark_read(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        user_id: &str,
+        message_id: &str,
+    ) -> AppResult<bool> {
+        if !LobbyMemberService::is_member(db, lobby_id, user_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        Message::find_by_id(message_id.to_string())
+            .filter(messages::Column::LobbyId.eq(lobby_id))
             .one(db)
             .await?
             .ok_or(AppError::NotFound)?;
 
+        let result = LobbyMembers::update_many()
+            .col_expr(
+                lobbies_members::Column::LastReadMessageId,
+                Expr::value(message_id),
+            )
+            .filter(lobbies_members::Column::LobbyId.eq(lobby_id))
+            .filter(lobbies_members::Column::UserId.eq(user_id))
+            .filter(
+                Condition::any()
+                    .add(lobbies_members::Column::LastReadMessageId.is_null())
+                    .add(lobbies_members::Column::LastReadMessageId.lt(message_id)),
+            )
+            .exec(db)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    // Unread messages per lobby of the user, lobbies with none are left out. One grouped query
+    // over the `(lobby_id, ulid)` index, so the cost follows the unread messages and not the
+    // length of the history. The user's own messages never count.
+    pub async fn unread_counts(
+        db: &DatabaseConnection,
+        user_id: &str,
+        report_hide_threshold: u64,
+    ) -> AppResult<HashMap<String, u64>> {
+        let markers: Vec<(String, Option<String>)> = LobbyMembers::find()
+            .select_only()
+            .column(lobbies_members::Column::LobbyId)
+            .column(lobbies_members::Column::LastReadMessageId)
+            .filter(lobbies_members::Column::UserId.eq(user_id))
+            .into_tuple()
+            .all(db)
+            .await?;
+        if markers.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut unread = Condition::any();
+        for (lobby_id, last_read) in markers {
+            let mut lobby = Condition::all().add(messages::Column::LobbyId.eq(lobby_id));
+            if let Some(last_read) = last_read {
+                lobby = lobby.add(messages::Column::Ulid.gt(last_read));
+            }
+            unread = unread.add(lobby);
+        }
+
+        let counts: Vec<(String, i64)> = Message::find()
+            .select_only()
+            .column(messages::Column::LobbyId)
+            .column_as(Func::count(Expr::col(messages::Column::Ulid)), "unread")
+            .filter(unread)
+            .filter(messages::Column::AuthorId.ne(user_id))
+            .filter(messages::Column::DeletedAt.is_null())
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Message,
+                messages::Column::Ulid,
+                report_hide_threshold,
+            ))
+            .group_by(messages::Column::LobbyId)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(counts
+            .into_iter()
+            .map(|(lobby_id, count)| (lobby_id, count as u64))
+            .collect())
+    }
+
+    // Soft delete: the text, mentions and reactions go, the row stays so the thread keeps its
+    // shape and replies can still show "deleted message"
+    pub async fn delete_message(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        message_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
+        let message = Self::find_live(db, lobby_id, message_id).await?;
+
         let can_manage = LobbyMemberService::can_manage_lobby(db, &message.lobby_id, user_id).await;
         let is_author = message.author_id == user_id;
+        if !is_author && !can_manage {
+            return Err(AppError::NotFound);
+        }
+
+        let txn = db.begin().await?;
 
-        if is_author || can_manage {
-            message.delete(db).await?;
-            Ok(())
-        } else {
-            Err(AppError::NotFound)
+        MessageReaction::delete_many()
+            .filter(message_reactions::Column::MessageId.eq(message_id))
+            .exec(&txn)
+            .await?;
+        MessageMention::delete_many()
+            .filter(message_mentions::Column::MessageId.eq(message_id))
+            .exec(&txn)
+            .await?;
+
+        let mut active: messages::ActiveModel = message.into();
+        active.content = Set(String::new());
+        active.deleted_at = Set(Some(chrono::Utc::now()));
+        active.update(&txn).await?;
+
+        txn.commit().await?;
+        Ok(())
+    }
+
+    // Returns false when the user already reacted with this emoji
+    pub async fn add_reaction(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        message_id: &str,
+        user_id: &str,
+        emoji: &str,
+    ) -> AppResult<bool> {
+        let count = emoji.chars().count();
+        let has_text = emoji
+            .chars()
+            .any(|c| c.is_ascii_alphanumeric() || c.is_whitespace());
+        if count == 0 || count > MAX_REACTION_CHARS || has_text {
+            return Err(AppError::Custom("Invalid reaction".into()));
+        }
+
+        if !LobbyMemberService::is_member(db, lobby_id, user_id).await {
+            return Err(AppError::NotFound);
         }
+        Self::find_live(db, lobby_id, message_id).await?;
+
+        // The primary key makes a repeated reaction a no-op, even when two requests race
+        let inserted = MessageReaction::insert(message_reactions::ActiveModel::new_reaction(
+            message_id.to_owned(),
+            user_id.to_owned(),
+            emoji.to_owned(),
+        ))
+        .on_conflict(
+            OnConflict::columns([
+                message_reactions::Column::MessageId,
+                message_reactions::Column::UserId,
+                message_reactions::Column::Emoji,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+
+        Ok(inserted > 0)
+    }
+
+    // Returns false when there was no such reaction
+    pub async fn remove_reaction(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        message_id: &str,
+        user_id: &str,
+        emoji: &str,
+    ) -> AppResult<bool> {
+        Self::find_live(db, lobby_id, message_id).await?;
+
+        let result = MessageReaction::delete_by_id((
+            message_id.to_owned(),
+            user_id.to_owned(),
+            emoji.to_owned(),
+        ))
+        .exec(db)
+        .await?;
+
+        Ok(result.rows_affected > 0)
     }
 
     pub async fn find_by_id(
@@ -77,4 +437,117 @@ impl MessageService {
             .await?
             .ok_or(AppError::NotFound)
     }
+
+    // A message of the lobby that isn't deleted
+    async fn find_live(
+        db: &DatabaseConnection,
+        lobby_id: &str,
+        message_id: &str,
+    ) -> AppResult<messages::Model> {
+        Message::find_by_id(message_id.to_string())
+            .filter(messages::Column::LobbyId.eq(lobby_id))
+            .filter(messages::Column::DeletedAt.is_null())
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)
+    }
+
+    // Resolves the `@username`s of the message to lobby members and stores them. Unknown names
+    // and people outside the lobby are plain text, and nobody mentions themselves.
+    async fn save_mentions<C>(db: &C, message: &messages::Model) -> AppResult<Vec<String>>
+    where
+        C: ConnectionTrait,
+    {
+        let names = parse_mentions(&message.content);
+        if names.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let members = Query::select()
+            .column(lobbies_members::Column::UserId)
+            .from(LobbyMembers)
+            .and_where(lobbies_members::Column::LobbyId.eq(message.lobby_id.as_str()))
+            .to_owned();
+
+        let mentioned: Vec<String> = User::find()
+            .select_only()
+            .column(users::Column::Ulid)
+            .filter(users::Column::Username.is_in(names))
+            .filter(users::Column::Ulid.ne(message.author_id.as_str()))
+            .filter(users::Column::Ulid.in_subquery(members))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        if !mentioned.is_empty() {
+            MessageMention::insert_many(mentioned.iter().map(|user_id| {
+                message_mentions::ActiveModel::new_mention(message.ulid.clone(), user_id.clone())
+            }))
+            .exec(db)
+            .await?;
+        }
+
+        Ok(mentioned)
+    }
+
+    fn publish_mentions(events: &EventBus, message: &messages::Model, user_ids: &[String]) {
+        events.publish_all(user_ids.iter().map(|user_id| DomainEvent::UserMentioned {
+            user_id: user_id.clone(),
+            lobby_id: message.lobby_id.clone(),
+            message_id: message.ulid.clone(),
+        }));
+    }
+
+    // Turns rows into DTOs, loading the mentions and reactions of all of them in two queries
+    async fn with_details(
+        db: &DatabaseConnection,
+        rows: Vec<(messages::Model, Option<users::Model>)>,
+    ) -> AppResult<Vec<MessageDto>> {
+        let ids: Vec<String> = rows
+            .iter()
+            .map(|(message, _)| message.ulid.clone())
+            .collect();
+        let mut mentions: HashMap<String, Vec<String>> = HashMap::new();
+        let mut reactions: HashMap<String, Vec<ReactionDto>> = HashMap::new();
+
+        if !ids.is_empty() {
+            let mention_rows = MessageMention::find()
+                .filter(message_mentions::Column::MessageId.is_in(ids.clone()))
+                .all(db)
+                .await?;
+            for mention in mention_rows {
+                mentions
+                    .entry(mention.message_id)
+                    .or_default()
+                    .push(mention.user_id);
+            }
+
+            let reaction_rows = MessageReaction::find()
+                .filter(message_reactions::Column::MessageId.is_in(ids))
+                .order_by_asc(message_reactions::Column::CreatedAt)
+                .all(db)
+                .await?;
+            for reaction in reaction_rows {
+                let summary = reactions.entry(reaction.message_id).or_default();
+                match summary.iter_mut().find(|r| r.emoji == reaction.emoji) {
+                    Some(existing) => existing.user_ids.push(reaction.user_id),
+                    None => summary.push(ReactionDto {
+                        emoji: reaction.emoji,
+                        user_ids: vec![reaction.user_id],
+                    }),
+                }
+            }
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|(message, author)| {
+                let author_name = author.map(|a| a.username).unwrap_or_default();
+                let mut dto = message.into_dto(author_name);
+                dto.mentions = mentions.remove(&dto.ulid).unwrap_or_default();
+                dto.reactions = reactions.remove(&dto.ulid).unwrap_or_default();
+                dto
+            })
+            .collect())
+    }
 }
@@ -1,10 +1,23 @@
+pub mod feed_ranking;
+pub mod follow_service;
+pub mod lobby_access_service;
+pub mod lobby_discovery_service;
 pub mod lobby_member_service;
+pub mod lobby_permissions;
 pub mod lobby_service;
 pub mod message_service;
+pub mod moderation_service;
+pub mod photo_hash_service;
+pub mod presence;
+pub mod quest_completion_service;
 pub mod quest_proof_beliefs_service;
 pub mod quest_proof_service;
 pub mod quest_service;
+pub mod rate_limiter;
 pub mod refresh_token_service;
+pub mod report_service;
+pub mod search_service;
+pub mod share_service;
 pub mod tests;
 pub mod user_quest_status_service;
 pub mod user_service;
@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use sea_orm::*;
+use shared::{
+    errors::AppResult,
+    models::report_dto::ReportTargetType,
+    moderation::{
+        ModerationOutcome, TextFilter, Wordlist,
+        wordlists::{self, BUILTIN_LOCALES},
+    },
+};
+
+use crate::config::env_opt;
+use crate::entities::{
+    moderation_flags::{self, FlagReason},
+    prelude::ModerationFlag,
+};
+
+#[derive(Debug, Clone)]
+pub struct ModerationConfig {
+    pub locales: Vec<String>,
+    // `<locale>.json` files in here replace the built-in list of that locale
+    pub wordlist_dir: Option<PathBuf>,
+}
+
+impl Default for ModerationConfig {
+    fn default() -> Self {
+        Self {
+            locales: BUILTIN_LOCALES.iter().map(|l| l.to_string()).collect(),
+            wordlist_dir: None,
+        }
+    }
+}
+
+impl ModerationConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            locales: env_opt::<String>("MODERATION_LOCALES")
+                .map(|v| {
+                    v.split(',')
+                        .map(|l| l.trim().to_lowercase())
+                        .filter(|l| !l.is_empty())
+                        .collect()
+                })
+                .unwrap_or(default.locales),
+            wordlist_dir: env_opt("MODERATION_WORDLIST_DIR"),
+        }
+    }
+
+    fn load_wordlist(&self, locale: &str) -> Option<Wordlist> {
+        if let Some(dir) = &self.wordlist_dir {
+            let path = dir.join(format!("{locale}.json"));
+            match std::fs::read_to_string(&path) {
+                Ok(data) => match serde_json::from_str(&data) {
+                    Ok(list) => return Some(list),
+                    Err(e) => tracing::error!(path = %path.display(), "Invalid wordlist: {e}"),
+                },
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::error!(path = %path.display(), "Failed to read wordlist: {e}"),
+            }
+        }
+
+        wordlists::builtin(locale)
+    }
+
+    pub fn build_filter(&self) -> TextFilter {
+        let lists: Vec<Wordlist> = self
+            .locales
+            .iter()
+            .filter_map(|locale| {
+                let list = self.load_wordlist(locale);
+                if list.is_none() {
+                    tracing::warn!(locale = %locale, "No wordlist for moderation locale");
+                }
+                list
+            })
+            .collect();
+
+        TextFilter::new(&lists)
+    }
+}
+
+pub struct ModerationService;
+
+impl ModerationService {
+    pub fn moderate_opt(
+        filter: &TextFilter,
+        text: Option<String>,
+    ) -> AppResult<Option<ModerationOutcome>> {
+        text.map(|text| filter.moderate(&text)).transpose()
+    }
+
+    // Queues the target for a moderator when any of its fields hit a flag rule. One open flag
+    // per target is enough, later edits only add to the same review.
+    pub async fn flag_for_review<C>(
+        db: &C,
+        target_type: ReportTargetType,
+        target_id: &str,
+        outcomes: &[Option<&ModerationOutcome>],
+    ) -> AppResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        let terms: Vec<&str> = outcomes
+            .iter()
+            .flatten()
+            .flat_map(|outcome| outcome.flagged.iter().map(String::as_str))
+            .collect();
+        if terms.is_empty() {
+            return Ok(());
+        }
+
+        let flagged = ModerationFlag::find()
+            .filter(moderation_flags::Column::TargetType.eq(target_type.clone()))
+            .filter(moderation_flags::Column::TargetId.eq(target_id))
+            .filter(moderation_flags::Column::Reason.eq(FlagReason::OffensiveText))
+            .filter(moderation_flags::Column::ResolvedAt.is_null())
+            .one(db)
+            .await?
+            .is_some();
+
+        if !flagged {
+            moderation_flags::ActiveModel::new_flag(
+                target_type,
+                target_id.to_owned(),
+                FlagReason::OffensiveText,
+                Some(terms.join(", ")),
+            )
+            .insert(db)
+            .await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use chrono::Utc;
+use sea_orm::{
+    Condition, QueryOrder, QuerySelect, TransactionTrait, entity::prelude::*, sea_query::Expr,
+};
+use shared::{
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        report_dto::{PhotoMatchDto, PhotoMatchKind, ReportTargetType},
+    },
+    utils::cursor::Cursor,
+};
+
+use crate::{
+    entities::{
+        moderation_flags::{self, FlagReason},
+        prelude::{ModerationFlag, ProofPhotoHash, ProofPhotoMatch, QuestProof},
+        proof_photo_hashes, proof_photo_matches,
+        quest_proofs::{self, ProofStatus},
+    },
+    file_storage::{
+        perceptual_hash::{self, MATCH_DISTANCE},
+        s3_client::S3Manager,
+    },
+    service::report_service::ReportService,
+};
+
+// A plain or very common image can be close to thousands of others, only the closest are kept
+const MAX_MATCHES_PER_PHOTO: usize = 10;
+// Proofs loaded per query while sweeping
+const SWEEP_BATCH: u64 = 50;
+
+pub struct PhotoHashService;
+
+impl PhotoHashService {
+    // Hashes every confirmed proof that isn't marked yet. Runs on each upload event and after
+    // a lagging receiver, so proofs whose event was missed are still checked.
+    pub async fn sweep(db: &DatabaseConnection, s3: &S3Manager) -> AppResult<usize> {
+        let mut processed = 0;
+        loop {
+            let pending = QuestProof::find()
+                .filter(quest_proofs::Column::PhotosHashedAt.is_null())
+                .filter(quest_proofs::Column::Status.ne(ProofStatus::Uploading))
+                .order_by_asc(quest_proofs::Column::CreatedAt)
+                .order_by_asc(quest_proofs::Column::Ulid)
+                .limit(SWEEP_BATCH)
+                .all(db)
+                .await?;
+            if pending.is_empty() {
+                return Ok(processed);
+            }
+
+            for proof in &pending {
+                Self::process_proof(db, s3, proof).await?;
+                processed += 1;
+            }
+        }
+    }
+
+    // Photos that can't be downloaded or decoded are skipped, a bad photo must not stop the
+    // rest of the proof from being checked. The proof is marked as hashed either way.
+    async fn process_proof(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        proof: &quest_proofs::Model,
+    ) -> AppResult<()> {
+        let proof_id = proof.ulid.as_str();
+        for key in S3Manager::media_keys(proof.photos.as_ref()) {
+            let bytes = match s3.get_file(&key).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    tracing::warn!(proof_id, key, error = %e, "Could not download proof photo");
+                    continue;
+                }
+            };
+
+            // Decoding a full-size photo is CPU heavy, keep it off the async workers
+            let hash =
+                match tokio::task::spawn_blocking(move || perceptual_hash::perceptual_hash(&bytes))
+                    .await
+                {
+                    Ok(Ok(hash)) => hash,
+                    Ok(Err(e)) => {
+                        tracing::warn!(proof_id, key, error = %e, "Could not decode proof photo");
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::error!(proof_id, key, error = %e, "Proof photo hashing panicked");
+                        continue;
+                    }
+                };
+
+            Self::index_photo(db, proof, key, hash).await?;
+        }
+
+        QuestProof::update_many()
+            .col_expr(
+                quest_proofs::Column::PhotosHashedAt,
+                Expr::value(Utc::now()),
+            )
+            .filter(quest_proofs::Column::Ulid.eq(proof_id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    // Stores the hash and records every earlier photo within MATCH_DISTANCE. The proof gets
+    // one flag for reusing its author's photos and one for copying someone else's.
+    #[tracing::instrument(skip(db, proof), fields(proof_id = %proof.ulid))]
+    pub async fn index_photo(
+        db: &DatabaseConnection,
+        proof: &quest_proofs::Model,
+        photo_key: String,
+        hash: u64,
+    ) -> AppResult<Vec<proof_photo_matches::Model>> {
+        let txn = db.begin().await?;
+
+        // Processing the same upload twice must not duplicate matches or flags
+        let already_indexed = ProofPhotoHash::find()
+            .filter(proof_photo_hashes::Column::ProofId.eq(&proof.ulid))
+            .filter(proof_photo_hashes::Column::PhotoKey.eq(&photo_key))
+            .one(&txn)
+            .await?
+            .is_some();
+        if already_indexed {
+            return Ok(Vec::new());
+        }
+
+        let [band0, band1, band2, band3] = perceptual_hash::bands(hash);
+        let candidates = ProofPhotoHash::find()
+            .filter(
+                Condition::any()
+                    .add(proof_photo_hashes::Column::Band0.eq(i32::from(band0)))
+                    .add(proof_photo_hashes::Column::Band1.eq(i32::from(band1)))
+                    .add(proof_photo_hashes::Column::Band2.eq(i32::from(band2)))
+                    .add(proof_photo_hashes::Column::Band3.eq(i32::from(band3))),
+            )
+            .filter(proof_photo_hashes::Column::ProofId.ne(&proof.ulid))
+            .all(&txn)
+            .await?;
+
+        let mut close: Vec<(u32, proof_photo_hashes::Model)> = candidates
+            .into_iter()
+            .map(|c| (perceptual_hash::hamming_distance(c.hash(), hash), c))
+            .filter(|(distance, _)| *distance <= MATCH_DISTANCE)
+            .collect();
+        close.sort_by_key(|(distance, _)| *distance);
+        close.truncate(MAX_MATCHES_PER_PHOTO);
+
+        let photo = proof_photo_hashes::ActiveModel::new_hash(
+            proof.ulid.clone(),
+            proof.user_id.clone(),
+            photo_key,
+            hash,
+        )
+        .insert(&txn)
+        .await?;
+
+        let mut matches = Vec::with_capacity(close.len());
+        for (distance, matched) in &close {
+            matches.push(
+                proof_photo_matches::ActiveModel::new_match(&photo, matched, *distance)
+                    .insert(&txn)
+                    .await?,
+            );
+        }
+
+        if matches.iter().any(|m| m.matched_user_id == proof.user_id) {
+            Self::flag_once(&txn, proof, FlagReason::ReusedPhoto).await?;
+        }
+        if matches.iter().any(|m| m.matched_user_id != proof.user_id) {
+            Self::flag_once(&txn, proof, FlagReason::CopiedPhoto).await?;
+        }
+
+        txn.commit().await?;
+
+        if !matches.is_empty() {
+            tracing::warn!(
+                matches = matches.len(),
+                "Proof photo matches earlier photos"
+            );
+        }
+        Ok(matches)
+    }
+
+    async fn flag_once<C>(db: &C, proof: &quest_proofs::Model, reason: FlagReason) -> AppResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        let flagged = ModerationFlag::find()
+            .filter(moderation_flags::Column::TargetType.eq(ReportTargetType::Proof))
+            .filter(moderation_flags::Column::TargetId.eq(&proof.ulid))
+            .filter(moderation_flags::Column::Reason.eq(reason.clone()))
+            .one(db)
+            .await?
+            .is_some();
+
+        if !flagged {
+            moderation_flags::ActiveModel::new_flag(
+                ReportTargetType::Proof,
+                proof.ulid.clone(),
+                reason,
+                None,
+            )
+            .insert(db)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    // Newest matches first, for moderators only
+    pub async fn get_matches(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        moderator_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<PhotoMatchDto>> {
+        if !ReportService::is_moderator(db, moderator_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        let mut query = ProofPhotoMatch::find();
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query.filter(cursor.after(
+                proof_photo_matches::Column::CreatedAt,
+                proof_photo_matches::Column::Ulid,
+            ));
+        }
+
+        let rows = query
+            .order_by_desc(proof_photo_matches::Column::CreatedAt)
+            .order_by_desc(proof_photo_matches::Column::Ulid)
+            .limit(limit + 1)
+            .all(db)
+            .await?;
+
+        let page = Page::from_rows(rows, limit, |m| Cursor::new(m.created_at, &m.ulid).encode());
+
+        let keys: Vec<String> = page
+            .items
+            .iter()
+            .flat_map(|m| [m.photo_key.clone(), m.matched_photo_key.clone()])
+            .collect();
+        let urls = s3.get_download_urls(&keys, 3600).await;
+
+        let items = page
+            .items
+            .into_iter()
+            .map(|m| PhotoMatchDto {
+                kind: if m.user_id == m.matched_user_id {
+                    PhotoMatchKind::Reused
+                } else {
+                    PhotoMatchKind::Copied
+                },
+                distance: m.distance as u32,
+                photo_url: urls.get(&m.photo_key).cloned(),
+                matched_photo_url: urls.get(&m.matched_photo_key).cloned(),
+                ulid: m.ulid,
+                proof_id: m.proof_id,
+                user_id: m.user_id,
+                matched_proof_id: m.matched_proof_id,
+                matched_user_id: m.matched_user_id,
+                created_at: m.created_at,
+            })
+            .collect();
+
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+}
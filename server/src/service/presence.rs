@@ -0,0 +1,96 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// A client keeps sending Typing while the user types, others only need to hear it this often
+pub const TYPING_THROTTLE: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Default)]
+struct Presence {
+    // lobby -> user -> open sockets
+    sockets: HashMap<String, HashMap<String, usize>>,
+    // (lobby, user) -> when their last Typing went out
+    typing: HashMap<(String, String), Instant>,
+}
+
+// Who has a live socket in which lobby. A user with the app open on several devices is online
+// from their first socket until their last one closes. Kept per process, like `RateLimiter`.
+#[derive(Debug, Clone, Default)]
+pub struct PresenceRegistry {
+    inner: Arc<Mutex<Presence>>,
+}
+
+impl PresenceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Presence> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    // Returns true when this is the user's first socket in the lobby
+    pub fn connect(&self, lobby_id: &str, user_id: &str) -> bool {
+        let mut presence = self.lock();
+        let count = presence
+            .sockets
+            .entry(lobby_id.to_owned())
+            .or_default()
+            .entry(user_id.to_owned())
+            .or_default();
+        *count += 1;
+        *count == 1
+    }
+
+    // Returns true when the user's last socket in the lobby just closed
+    pub fn disconnect(&self, lobby_id: &str, user_id: &str) -> bool {
+        let mut presence = self.lock();
+        let Some(users) = presence.sockets.get_mut(lobby_id) else {
+            return false;
+        };
+        let Some(count) = users.get_mut(user_id) else {
+            return false;
+        };
+
+        *count -= 1;
+        if *count > 0 {
+            return false;
+        }
+
+        users.remove(user_id);
+        if users.is_empty() {
+            presence.sockets.remove(lobby_id);
+        }
+        presence
+            .typing
+            .remove(&(lobby_id.to_owned(), user_id.to_owned()));
+        true
+    }
+
+    pub fn online_users(&self, lobby_id: &str) -> Vec<String> {
+        self.lock()
+            .sockets
+            .get(lobby_id)
+            .map(|users| users.keys().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn should_broadcast_typing(&self, lobby_id: &str, user_id: &str) -> bool {
+        self.should_broadcast_typing_at(lobby_id, user_id, Instant::now())
+    }
+
+    pub fn should_broadcast_typing_at(&self, lobby_id: &str, user_id: &str, now: Instant) -> bool {
+        let mut presence = self.lock();
+        let key = (lobby_id.to_owned(), user_id.to_owned());
+
+        match presence.typing.get(&key) {
+            Some(last) if now.duration_since(*last) < TYPING_THROTTLE => false,
+            _ => {
+                presence.typing.insert(key, now);
+                true
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use chrono::Utc;
+use sea_orm::{ActiveValue::Set, ExprTrait, entity::prelude::*, sea_query::Expr};
+use shared::{errors::AppResult, models::user_quest_status_dto::QuestStatus};
+
+use crate::{
+    entities::{
+        prelude::{User, UserQuestStatus},
+        quest_proofs, quests, user_quest_status, users,
+    },
+    events::{CompletionSource, DomainEvent, QuestCompletion},
+};
+
+pub const AUTOMATIC_VALIDATION: &str = "AUTOMATIC";
+
+// The one place where quest status rows change because of a proof. XP moves in the same
+// transaction as the status row; every method returns the events to publish once the caller's
+// transaction has committed.
+pub struct QuestCompletionService;
+
+impl QuestCompletionService {
+    // A proof belongs to its author's row for its quest on the day the proof was created.
+    // Later edits (confirming the upload, beliefs, moderation) never move it to another day.
+    pub async fn status_row_for_proof<C>(
+        db: &C,
+        proof: &quest_proofs::Model,
+    ) -> AppResult<Option<user_quest_status::Model>>
+    where
+        C: ConnectionTrait,
+    {
+        Ok(UserQuestStatus::find_by_id((
+            proof.user_id.clone(),
+            proof.quest_id.clone(),
+            proof.created_at.date_naive(),
+        ))
+        .one(db)
+        .await?)
+    }
+
+    // Uploaded proofs wait for validation, except for quests that validate themselves
+    pub async fn proof_submitted<C>(
+        db: &C,
+        proof: &quest_proofs::Model,
+        quest: &quests::Model,
+    ) -> AppResult<Vec<DomainEvent>>
+    where
+        C: ConnectionTrait,
+    {
+        if quest.validation_type == AUTOMATIC_VALIDATION {
+            return Self::complete(db, proof, quest, CompletionSource::Automatic).await;
+        }
+
+        let Some(status) = Self::status_row_for_proof(db, proof).await? else {
+            return Ok(Vec::new());
+        };
+
+        if !status.is_completed {
+            let mut active: user_quest_status::ActiveModel = status.into();
+            active.quest_status = Set(QuestStatus::InPending);
+            active.updated_at = Set(Utc::now());
+            active.update(db).await?;
+        }
+
+        Ok(Vec::new())
+    }
+
+    // Completing an already completed row is a no-op and emits nothing
+    pub async fn complete<C>(
+        db: &C,
+        proof: &quest_proofs::Model,
+        quest: &quests::Model,
+        source: CompletionSource,
+    ) -> AppResult<Vec<DomainEvent>>
+    where
+        C: ConnectionTrait,
+    {
+        let Some(status) = Self::status_row_for_proof(db, proof).await? else {
+            tracing::warn!(proof_id = %proof.ulid, "Proof has no assigned quest to complete");
+            return Ok(Vec::new());
+        };
+
+        if status.is_completed {
+            return Ok(Vec::new());
+        }
+
+        let assigned_at = status.assigned_at;
+        let mut active: user_quest_status::ActiveModel = status.into();
+        active.is_completed = Set(true);
+        active.quest_status = Set(QuestStatus::Completed);
+        active.updated_at = Set(Utc::now());
+        active.update(db).await?;
+        Self::grant_xp(db, &proof.user_id, i64::from(quest.xp_reward)).await?;
+
+        tracing::info!(proof_id = %proof.ulid, ?source, "Quest completed");
+
+        Ok(vec![DomainEvent::QuestCompleted(Self::completion(
+            proof,
+            quest,
+            assigned_at,
+            source,
+        ))])
+    }
+
+    // Puts a completed row back to pending, e.g. when beliefs are withdrawn
+    pub async fn revert<C>(
+        db: &C,
+        proof: &quest_proofs::Model,
+        quest: &quests::Model,
+        source: CompletionSource,
+    ) -> AppResult<Vec<DomainEvent>>
+    where
+        C: ConnectionTrait,
+    {
+        let Some(status) = Self::status_row_for_proof(db, proof).await? else {
+            return Ok(Vec::new());
+        };
+
+        if !status.is_completed {
+            return Ok(Vec::new());
+        }
+
+        let assigned_at = status.assigned_at;
+        let mut active: user_quest_status::ActiveModel = status.into();
+        active.is_completed = Set(false);
+        active.quest_status = Set(QuestStatus::InPending);
+        active.updated_at = Set(Utc::now());
+        active.update(db).await?;
+        Self::grant_xp(db, &proof.user_id, -i64::from(quest.xp_reward)).await?;
+
+        tracing::info!(proof_id = %proof.ulid, ?source, "Quest completion reverted");
+
+        Ok(vec![DomainEvent::QuestCompletionReverted(
+            Self::completion(proof, quest, assigned_at, source),
+        )])
+    }
+
+    async fn grant_xp<C>(db: &C, user_id: &str, xp: i64) -> AppResult<()>
+    where
+        C: ConnectionTrait,
+    {
+        User::update_many()
+            .col_expr(
+                users::Column::XpBalance,
+                Expr::col(users::Column::XpBalance).add(xp),
+            )
+            .col_expr(
+                users::Column::TotalXpAccumulated,
+                Expr::col(users::Column::TotalXpAccumulated).add(xp),
+            )
+            .filter(users::Column::Ulid.eq(user_id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    fn completion(
+        proof: &quest_proofs::Model,
+        quest: &quests::Model,
+        assigned_at: Date,
+        source: CompletionSource,
+    ) -> QuestCompletion {
+        QuestCompletion {
+            user_id: proof.user_id.clone(),
+            quest_id: proof.quest_id.clone(),
+            proof_id: proof.ulid.clone(),
+            assigned_at,
+            xp_reward: quest.xp_reward,
+            source,
+        }
+    }
+}
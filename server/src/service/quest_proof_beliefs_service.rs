@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{QuerySelect, entity::prelude::*, sea_query::Query};
+use shared::{errors::AppResult, models::report_dto::ReportTargetType};
+use std::collections::HashSet;
+
+use crate::config::env_or;
+use crate::entities::{
+    moderation_flags::{self, FlagReason},
+    prelude::{QuestProof, QuestProofBeliefs},
+    quest_proof_beliefs, quest_proofs, users,
+};
+
+// Weight of one belief from an established account, weights are stored in thousandths
+pub const FULL_BELIEF_WEIGHT: u32 = 1000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BeliefConfig {
+    // Believers at or above this level give full weight, level 1 gives half
+    pub full_weight_level: u32,
+    // Accounts younger than this give proportionally less weight
+    pub full_weight_account_age_days: u32,
+    // Floor so a brand-new account still counts a little
+    pub min_weight: u32,
+    pub toggles_per_minute: u32,
+    // Toggles of a single proof by one user, stops belief/unbelief spam towards the author
+    pub toggles_per_proof_per_hour: u32,
+    // A completion is flagged when at least this many believers took part...
+    pub cluster_min_believers: usize,
+    // ...and at least this share of them also received beliefs from the author
+    pub cluster_mutual_ratio: f64,
+}
+
+impl Default for BeliefConfig {
+    fn default() -> Self {
+        Self {
+            full_weight_level: 5,
+            full_weight_account_age_days: 14,
+            min_weight: 100,
+            toggles_per_minute: 20,
+            toggles_per_proof_per_hour: 4,
+            cluster_min_believers: 3,
+            cluster_mutual_ratio: 0.6,
+        }
+    }
+}
+
+impl BeliefConfig {
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        Self {
+            full_weight_level: env_or("BELIEF_FULL_WEIGHT_LEVEL", default.full_weight_level),
+            full_weight_account_age_days: env_or(
+                "BELIEF_FULL_WEIGHT_ACCOUNT_AGE_DAYS",
+                default.full_weight_account_age_days,
+            ),
+            min_weight: env_or("BELIEF_MIN_WEIGHT", default.min_weight),
+            toggles_per_minute: env_or("BELIEF_TOGGLES_PER_MINUTE", default.toggles_per_minute),
+            toggles_per_proof_per_hour: env_or(
+                "BELIEF_TOGGLES_PER_PROOF_PER_HOUR",
+                default.toggles_per_proof_per_hour,
+            ),
+            cluster_min_believers: env_or(
+                "BELIEF_CLUSTER_MIN_BELIEVERS",
+                default.cluster_min_believers,
+            ),
+            cluster_mutual_ratio: env_or(
+                "BELIEF_CLUSTER_MUTUAL_RATIO",
+                default.cluster_mutual_ratio,
+            ),
+        }
+    }
+}
+
+pub struct QuestProofBeliefsService;
+
+impl QuestProofBeliefsService {
+    // Weight of a belief given by `believer`, in thousandths. Level scales it from one half
+    // to full, account age from zero to full, and the result never drops below min_weight.
+    pub fn belief_weight(
+        config: &BeliefConfig,
+        believer: &users::Model,
+        now: DateTime<Utc>,
+    ) -> u32 {
+        let level_factor = if config.full_weight_level <= 1 {
+            1.0
+        } else {
+            let progress = f64::from(believer.level.saturating_sub(1))
+                / f64::from(config.full_weight_level - 1);
+            0.5 + 0.5 * progress.min(1.0)
+        };
+
+        let age_factor = if config.full_weight_account_age_days == 0 {
+            1.0
+        } else {
+            let age_hours = (now - believer.created_at).num_hours().max(0) as f64;
+            (age_hours / (f64::from(config.full_weight_account_age_days) * 24.0)).min(1.0)
+        };
+
+        let weight = (f64::from(FULL_BELIEF_WEIGHT) * level_factor * age_factor).round() as u32;
+        weight.clamp(
+            config.min_weight.min(FULL_BELIEF_WEIGHT),
+            FULL_BELIEF_WEIGHT,
+        )
+    }
+
+    // Which of `believer_ids` have a proof the author believed in
+    pub async fn mutual_believers<C>(
+        db: &C,
+        author_id: &str,
+        believer_ids: &[String],
+    ) -> AppResult<HashSet<String>>
+    where
+        C: ConnectionTrait,
+    {
+        if believer_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let believed_by_author = Query::select()
+            .column(quest_proof_beliefs::Column::ProofId)
+            .from(QuestProofBeliefs)
+            .and_where(quest_proof_beliefs::Column::UserId.eq(author_id))
+            .to_owned();
+
+        let mutual: Vec<String> = QuestProof::find()
+            .select_only()
+            .column(quest_proofs::Column::UserId)
+            .distinct()
+            .filter(quest_proofs::Column::UserId.is_in(believer_ids.iter().cloned()))
+            .filter(quest_proofs::Column::Ulid.in_subquery(believed_by_author))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(mutual.into_iter().collect())
+    }
+
+    // Called when beliefs complete a proof. The completion stands, but if most believers are
+    // users the author believes in back it is flagged for moderators.
+    pub async fn flag_if_mutual_cluster<C>(
+        db: &C,
+        config: &BeliefConfig,
+        proof: &quest_proofs::Model,
+    ) -> AppResult<Option<moderation_flags::Model>>
+    where
+        C: ConnectionTrait,
+    {
+        let believer_ids: Vec<String> = QuestProofBeliefs::find()
+            .select_only()
+            .column(quest_proof_beliefs::Column::UserId)
+            .filter(quest_proof_beliefs::Column::ProofId.eq(&proof.ulid))
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        if believer_ids.len() < config.cluster_min_believers.max(1) {
+            return Ok(None);
+        }
+
+        let mutual = Self::mutual_believers(db, &proof.user_id, &believer_ids).await?;
+        let ratio = mutual.len() as f64 / believer_ids.len() as f64;
+        if ratio < config.cluster_mutual_ratio {
+            return Ok(None);
+        }
+
+        let flag = moderation_flags::ActiveModel::new_flag(
+            ReportTargetType::Proof,
+            proof.ulid.clone(),
+            FlagReason::MutualBeliefCluster,
+            Some(format!(
+                "{} of {} believers also received beliefs from the author",
+                mutual.len(),
+                believer_ids.len()
+            )),
+        )
+        .insert(db)
+        .await?;
+
+        tracing::warn!(proof_id = %proof.ulid, "Completion flagged as mutual belief cluster");
+        Ok(Some(flag))
+    }
+}
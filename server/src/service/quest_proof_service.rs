@@ -5,22 +5,34 @@ use serde_json::json;
 use shared::{
     errors::{AppError, AppResult},
     models::{
-        quest_proof_dto::{ProofDetailsResponse, ProofFeedResponse},
-        user_quest_status_dto::QuestStatus,
+        Page, PresignedUpload,
+        quest_proof_dto::{ProofDetailsResponse, ProofVisibility, SubmitProofRequest},
+        report_dto::ReportTargetType,
     },
+    moderation::TextFilter,
+    utils::cursor::Cursor,
 };
-use tracing::info;
+use std::collections::HashSet;
 use ulid::Ulid;
 
 use crate::{
     entities::{
-        prelude::{Quest, QuestProof, QuestProofBeliefs, User, UserQuestStatus},
+        prelude::{Quest, QuestProof, QuestProofBeliefs, User},
         quest_proof_beliefs,
         quest_proofs::{self, ActiveModel, Model, ProofStatus},
-        user_quest_status,
+        quests, users,
+    },
+    events::{CompletionSource, DomainEvent, EventBus},
+    file_storage::{s3_client::S3Manager, upload_policy::UploadKind},
+    service::{
+        feed_ranking::{FeedRanking, FeedRankingConfig, RankCursor, RankingSignals},
+        follow_service::FollowService,
+        lobby_member_service::LobbyMemberService,
+        moderation_service::ModerationService,
+        quest_completion_service::{AUTOMATIC_VALIDATION, QuestCompletionService},
+        quest_proof_beliefs_service::{BeliefConfig, FULL_BELIEF_WEIGHT, QuestProofBeliefsService},
+        report_service::ReportService,
     },
-    file_storage::s3_client::S3Manager,
-    service::user_quest_status_service::UserQuestService,
 };
 
 pub struct DetailedProof {
@@ -51,6 +63,7 @@ impl From<DetailedProof> for ProofDetailsResponse {
             status: format!("{:?}", d.proof.status),
             photo_urls: d.photo_urls,
             voice_urls: d.voice_urls,
+            visibility: d.proof.visibility,
             created_at: d.proof.created_at,
 
             beliefs_count: d.beliefs_count,
@@ -66,50 +79,55 @@ impl QuestProofService {
     pub async fn init_proof_submition(
         db: &DatabaseConnection,
         s3: &S3Manager,
+        filter: &TextFilter,
         user_id: String,
         quest_id: String,
-        proof_text: Option<String>,
-        photo_count: u32,
-        voice_count: u32,
-    ) -> Result<(Model, Vec<String>, Vec<String>), DbErr> {
+        request: SubmitProofRequest,
+    ) -> AppResult<(Model, Vec<PresignedUpload>, Vec<PresignedUpload>)> {
+        let SubmitProofRequest {
+            proof_text,
+            photo_count,
+            voice_count,
+            visibility,
+        } = request;
+
         let has_text = proof_text.as_ref().is_some_and(|t| !t.trim().is_empty());
         if !has_text && photo_count == 0 && voice_count == 0 {
-            return Err(DbErr::Custom("Proof must contain something".into()));
+            return Err(AppError::Custom("Proof must contain something".into()));
+        }
+        let proof_text = ModerationService::moderate_opt(filter, proof_text)?;
+
+        // Nobody can believe in a private proof, so only self-validating quests allow it
+        if visibility == ProofVisibility::Private {
+            let quest = Quest::find_by_id(&quest_id)
+                .one(db)
+                .await?
+                .ok_or(AppError::NotFound)?;
+
+            if quest.validation_type != AUTOMATIC_VALIDATION {
+                return Err(AppError::Custom(
+                    "Private proofs are only allowed for automatically validated quests".into(),
+                ));
+            }
         }
 
         let proof_id = Ulid::new().to_string();
 
-        // Generate pre-signed S3 URLs for photo uploads (expires in 1 hour)
-        let mut photo_urls = Vec::new();
-        let mut photo_keys = Vec::new();
-        for i in 0..photo_count {
-            let key = S3Manager::generate_proof_key(&user_id, &proof_id, i, "jpg", false);
-            let url = s3
-                .get_upload_url(&key, "image/jpeg", 3600)
-                .await
-                .map_err(|e| DbErr::Custom(e.to_string()))?;
-            photo_urls.push(url);
-            photo_keys.push(key);
-        }
+        let photo_keys: Vec<String> = (0..photo_count)
+            .map(|i| S3Manager::generate_proof_key(&user_id, &proof_id, i, "jpg", false))
+            .collect();
+        let voice_keys: Vec<String> = (0..voice_count)
+            .map(|i| S3Manager::generate_proof_key(&user_id, &proof_id, i, "ogg", true))
+            .collect();
 
-        // Generate pre-signed S3 URLs for voice uploads (expires in 1 hour)
-        let mut voice_urls = Vec::new();
-        let mut voice_keys = Vec::new();
-        for i in 0..voice_count {
-            let key = S3Manager::generate_proof_key(&user_id, &proof_id, i, "ogg", true);
-            let url = s3
-                .get_upload_url(&key, "audio/ogg", 3600)
-                .await
-                .map_err(|e| DbErr::Custom(e.to_string()))?;
-            voice_urls.push(url);
-            voice_keys.push(key);
-        }
+        let photo_uploads = Self::upload_forms(s3, &photo_keys, UploadKind::Photo).await?;
+        let voice_uploads = Self::upload_forms(s3, &voice_keys, UploadKind::Voice).await?;
 
         let active_model = ActiveModel {
             ulid: Set(proof_id),
             user_id: Set(user_id),
             quest_id: Set(quest_id),
-            proof_text: Set(proof_text),
+            proof_text: Set(proof_text.as_ref().map(|t| t.text.clone())),
             photos: Set(if photo_keys.is_empty() {
                 None
             } else {
@@ -122,76 +140,201 @@ impl QuestProofService {
             }),
             status: Set(ProofStatus::Uploading),
             beliefs_count: Set(0),
+            belief_weight: Set(0),
+            visibility: Set(visibility),
+            photos_hashed_at: Set(None),
             created_at: Set(Utc::now()),
             updated_at: Set(Utc::now()),
         };
 
         let model = active_model.insert(db).await?;
-        Ok((model, photo_urls, voice_urls))
+        ModerationService::flag_for_review(
+            db,
+            ReportTargetType::Proof,
+            &model.ulid,
+            &[proof_text.as_ref()],
+        )
+        .await?;
+
+        Ok((model, photo_uploads, voice_uploads))
     }
 
-    // Confirm proof record with "InPending" status; files are verified and ready to use
-    pub async fn confirm_proof_upload(
+    // Presigned POST forms for each key (expire in 1 hour)
+    async fn upload_forms(
+        s3: &S3Manager,
+        keys: &[String],
+        kind: UploadKind,
+    ) -> AppResult<Vec<PresignedUpload>> {
+        let mut uploads = Vec::with_capacity(keys.len());
+        for key in keys {
+            let upload = s3
+                .get_upload_form(key, kind, 3600)
+                .await
+                .map_err(|e| DbErr::Custom(e.to_string()))?;
+            uploads.push(upload);
+        }
+        Ok(uploads)
+    }
+
+    // Re-signs the upload forms of a proof that is still Uploading, for clients whose original
+    // forms expired mid-upload. Keys stay the same, so already uploaded files are kept.
+    pub async fn refresh_upload_urls(
         db: &DatabaseConnection,
-        proof_id: String,
-    ) -> Result<Model, DbErr> {
-        let txn = db.begin().await?;
+        s3: &S3Manager,
+        proof_id: &str,
+        user_id: &str,
+    ) -> AppResult<(Model, Vec<PresignedUpload>, Vec<PresignedUpload>)> {
+        let proof = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await?
+            .filter(|p| p.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
 
+        if proof.status != ProofStatus::Uploading {
+            return Err(AppError::Custom(
+                "Proof uploads are already confirmed".into(),
+            ));
+        }
+
+        let photo_keys = S3Manager::media_keys(proof.photos.as_ref());
+        let voice_keys = S3Manager::media_keys(proof.voice_notes.as_ref());
+
+        let photo_uploads = Self::upload_forms(s3, &photo_keys, UploadKind::Photo).await?;
+        let voice_uploads = Self::upload_forms(s3, &voice_keys, UploadKind::Voice).await?;
+
+        Ok((proof, photo_uploads, voice_uploads))
+    }
+
+    // Drops a submission its author abandoned before confirming it. Uploaded files are removed
+    // in the background, a failure there only leaves orphaned objects in the bucket.
+    pub async fn cancel_upload(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        proof_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
         let proof = QuestProof::find_by_id(proof_id)
-            .one(&txn)
+            .one(db)
             .await?
-            .ok_or(DbErr::Custom("Proof not found".into()))?;
+            .filter(|p| p.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
 
-        let mut active_model: ActiveModel = proof.clone().into();
-        active_model.status = Set(ProofStatus::Pending);
-        active_model.updated_at = Set(Utc::now());
+        // The status check is part of the delete, so a concurrent confirm can't be undone
+        let deleted = QuestProof::delete_many()
+            .filter(quest_proofs::Column::Ulid.eq(&proof.ulid))
+            .filter(quest_proofs::Column::Status.eq(ProofStatus::Uploading))
+            .exec(db)
+            .await?;
+        if deleted.rows_affected == 0 {
+            return Err(AppError::Custom(
+                "Proof uploads are already confirmed".into(),
+            ));
+        }
 
-        let updated_proof = active_model.update(&txn).await?;
+        let mut keys = S3Manager::media_keys(proof.photos.as_ref());
+        keys.extend(S3Manager::media_keys(proof.voice_notes.as_ref()));
+        let s3 = s3.clone();
+        tokio::spawn(async move {
+            for key in keys {
+                if let Err(e) = s3.delete_file(&key).await {
+                    tracing::warn!(key, error = %e, "Could not delete cancelled proof media");
+                }
+            }
+        });
 
-        let today = proof.updated_at.date_naive();
+        Ok(())
+    }
 
-        UserQuestStatus::update_many()
+    // Confirm proof record with "InPending" status; files are verified and ready to use.
+    // Only the author's own Uploading proof moves on, so a repeated confirm can't complete the
+    // quest twice or bring back a proof a moderator already decided on.
+    pub async fn confirm_proof_upload(
+        db: &DatabaseConnection,
+        events: &EventBus,
+        proof_id: String,
+        user_id: &str,
+    ) -> AppResult<Model> {
+        let txn = db.begin().await?;
+
+        let updated = QuestProof::update_many()
             .col_expr(
-                crate::entities::user_quest_status::Column::QuestStatus,
-                Expr::value(QuestStatus::InPending),
+                quest_proofs::Column::Status,
+                Expr::value(ProofStatus::Pending),
             )
-            .filter(crate::entities::user_quest_status::Column::QuestId.eq(&proof.quest_id))
-            .filter(crate::entities::user_quest_status::Column::AssignedAt.eq(today))
+            .col_expr(quest_proofs::Column::UpdatedAt, Expr::value(Utc::now()))
+            .filter(quest_proofs::Column::Ulid.eq(&proof_id))
+            .filter(quest_proofs::Column::UserId.eq(user_id))
+            .filter(quest_proofs::Column::Status.eq(ProofStatus::Uploading))
             .exec(&txn)
             .await?;
 
+        let (proof, quest) = QuestProof::find_by_id(proof_id)
+            .find_also_related(Quest)
+            .one(&txn)
+            .await?
+            .filter(|(p, _)| p.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
+        if updated.rows_affected == 0 {
+            return Err(AppError::Custom(
+                "Proof uploads are already confirmed".into(),
+            ));
+        }
+        let quest = quest.ok_or(AppError::NotFound)?;
+
+        let emitted = QuestCompletionService::proof_submitted(&txn, &proof, &quest).await?;
+
         txn.commit().await?;
-        Ok(updated_proof)
+        events.publish(DomainEvent::ProofUploaded {
+            proof_id: proof.ulid.clone(),
+        });
+        events.publish_all(emitted);
+        Ok(proof)
     }
 
+    // Moderator decision. Approving completes the quest, rejecting undoes an earlier completion.
     pub async fn update_status(
         db: &DatabaseConnection,
+        events: &EventBus,
         proof_id: &str,
         new_status: ProofStatus,
-    ) -> Result<Model, DbErr> {
+    ) -> AppResult<Model> {
         let txn = db.begin().await?;
 
-        let proof = QuestProof::find_by_id(proof_id.to_string())
+        let (proof, quest) = QuestProof::find_by_id(proof_id.to_string())
+            .find_also_related(Quest)
             .one(&txn)
             .await?
-            .ok_or(DbErr::RecordNotFound("Proof not found".into()))?;
+            .ok_or(AppError::NotFound)?;
+        let quest = quest.ok_or(AppError::NotFound)?;
 
-        let mut active_model: ActiveModel = proof.clone().into();
+        let mut active_model: ActiveModel = proof.into();
         active_model.status = Set(new_status.clone());
         let updated_proof = active_model.update(&txn).await?;
 
-        if let ProofStatus::Approved = new_status {
-            UserQuestService::complete_quest_internal(
-                &txn,
-                &proof.user_id,
-                &proof.quest_id,
-                proof.updated_at.date_naive(),
-            )
-            .await
-            .map_err(|e| DbErr::Custom(e.to_string()))?;
-        }
+        let emitted = match new_status {
+            ProofStatus::Approved => {
+                QuestCompletionService::complete(
+                    &txn,
+                    &updated_proof,
+                    &quest,
+                    CompletionSource::Moderation,
+                )
+                .await?
+            }
+            ProofStatus::Rejected => {
+                QuestCompletionService::revert(
+                    &txn,
+                    &updated_proof,
+                    &quest,
+                    CompletionSource::Moderation,
+                )
+                .await?
+            }
+            ProofStatus::Uploading | ProofStatus::Pending => Vec::new(),
+        };
 
         txn.commit().await?;
+        events.publish_all(emitted);
         Ok(updated_proof)
     }
 
@@ -202,6 +345,7 @@ impl QuestProofService {
         current_user_id: &str,
     ) -> Result<Option<DetailedProof>, DbErr> {
         let proof_option = QuestProof::find_by_id(proof_id.to_string())
+            .filter(Self::visible_to_condition(current_user_id))
             .find_also_related(User)
             .find_also_related(Quest)
             .one(db)
@@ -210,6 +354,7 @@ impl QuestProofService {
         if let Some((proof, Some(user), Some(quest))) = proof_option {
             let photo_urls = s3.resolve_urls(proof.photos.as_ref()).await;
             let voice_urls = s3.resolve_urls(proof.voice_notes.as_ref()).await;
+            let avatar_url = s3.resolve_url(user.avatar_url.as_deref()).await;
 
             let is_believed = Self::is_believed_by_user(db, current_user_id, &proof.ulid).await;
 
@@ -217,7 +362,7 @@ impl QuestProofService {
                 beliefs_count: proof.beliefs_count,
                 proof,
                 username: user.username,
-                avatar_url: user.avatar_url,
+                avatar_url,
                 quest_title: quest.title,
                 is_believed,
                 quest_description: quest.description,
@@ -234,70 +379,176 @@ impl QuestProofService {
         db: &DatabaseConnection,
         s3: &S3Manager,
         current_user_id: &str,
-        limit: u32,
-        offset: u32,
-    ) -> AppResult<ProofFeedResponse> {
-        let proofs_with_relations = QuestProof::find()
+        limit: u64,
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+        following_only: bool,
+    ) -> AppResult<Page<ProofDetailsResponse>> {
+        let mut query = QuestProof::find()
             .filter(quest_proofs::Column::UserId.ne(current_user_id))
-            .order_by_desc(crate::entities::quest_proofs::Column::CreatedAt)
-            .limit((limit + 1) as u64)
-            .offset(offset as u64)
+            .filter(Self::visible_to_condition(current_user_id))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Proof,
+                quest_proofs::Column::Ulid,
+                report_hide_threshold,
+            ));
+
+        if following_only {
+            query = query.filter(FollowService::followed_by_condition(
+                current_user_id,
+                quest_proofs::Column::UserId,
+            ));
+        }
+
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query
+                .filter(cursor.after(quest_proofs::Column::CreatedAt, quest_proofs::Column::Ulid));
+        }
+
+        let rows = query
+            .order_by_desc(quest_proofs::Column::CreatedAt)
+            .order_by_desc(quest_proofs::Column::Ulid)
+            .limit(limit + 1)
             .find_also_related(User)
             .find_also_related(Quest)
             .all(db)
             .await?;
 
-        let has_more = proofs_with_relations.len() > limit as usize;
-        let proofs_to_process = if has_more {
-            &proofs_with_relations[..limit as usize]
-        } else {
-            &proofs_with_relations
-        };
+        let page = Page::from_rows(rows, limit, |(proof, _, _)| {
+            Cursor::new(proof.created_at, &proof.ulid).encode()
+        });
+        let items = Self::assemble_proofs(db, s3, current_user_id, page.items).await?;
 
-        let mut results = Vec::with_capacity(proofs_to_process.len());
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
 
-        for (proof, user_opt, quest_opt) in proofs_to_process {
-            if let (Some(user), Some(quest)) = (user_opt, quest_opt) {
-                let is_believed = Self::is_believed_by_user(db, current_user_id, &proof.ulid).await;
+    // "For You" feed: the newest candidates are scored in memory and served in score order.
+    pub async fn get_ranked_feed(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        current_user_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+        config: &FeedRankingConfig,
+    ) -> AppResult<Page<ProofDetailsResponse>> {
+        let cursor = cursor.map(RankCursor::decode).transpose()?;
+        let as_of = cursor.as_ref().map_or_else(Utc::now, |c| c.as_of);
+
+        let candidates = QuestProof::find()
+            .filter(quest_proofs::Column::UserId.ne(current_user_id))
+            .filter(quest_proofs::Column::CreatedAt.lte(as_of))
+            .filter(Self::visible_to_condition(current_user_id))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Proof,
+                quest_proofs::Column::Ulid,
+                report_hide_threshold,
+            ))
+            .order_by_desc(quest_proofs::Column::CreatedAt)
+            .order_by_desc(quest_proofs::Column::Ulid)
+            .limit(config.candidate_limit)
+            .find_also_related(User)
+            .find_also_related(Quest)
+            .all(db)
+            .await?;
 
-                let photo_urls = s3.resolve_urls(proof.photos.as_ref()).await;
-                let voice_urls = s3.resolve_urls(proof.voice_notes.as_ref()).await;
+        let author_ids: Vec<String> = candidates
+            .iter()
+            .map(|(p, _, _)| p.user_id.clone())
+            .collect();
+        let lobby_peers = LobbyMemberService::lobby_peers(db, current_user_id, &author_ids).await?;
+        let followed = FollowService::followed_ids(db, current_user_id, &author_ids).await?;
 
-                results.push(ProofDetailsResponse {
-                    ulid: proof.ulid.clone(),
-                    user_id: proof.user_id.clone(),
-                    username: user.username.clone(),
-                    avatar_url: user.avatar_url.clone(),
-                    quest_id: proof.quest_id.clone(),
-                    quest_title: quest.title.clone(),
-                    quest_description: quest.description.clone(),
-                    xp_reward: quest.xp_reward,
-                    proof_text: proof.proof_text.clone(),
-                    status: format!("{:?}", proof.status),
-                    beliefs_count: proof.beliefs_count,
-                    is_believed,
-                    photo_urls,
-                    voice_urls,
+        let mut scored: Vec<_> = candidates
+            .into_iter()
+            .filter_map(|(proof, user, quest)| {
+                let (user, quest) = (user?, quest?);
+                let signals = RankingSignals {
                     created_at: proof.created_at,
-                });
+                    belief_weight: proof.belief_weight,
+                    required_beliefs: quest.complexity.required_beliefs(user.level),
+                    shares_lobby: lobby_peers.contains(&proof.user_id),
+                    follows_author: followed.contains(&proof.user_id),
+                };
+                let score = FeedRanking::score(config, &signals, as_of);
+                Some((score, (proof, Some(user), Some(quest))))
+            })
+            .collect();
+
+        scored.sort_by(|(a_score, (a, _, _)), (b_score, (b, _, _))| {
+            b_score.total_cmp(a_score).then_with(|| b.ulid.cmp(&a.ulid))
+        });
+
+        let rows: Vec<_> = scored
+            .into_iter()
+            .filter(|(score, (proof, _, _))| {
+                cursor
+                    .as_ref()
+                    .is_none_or(|c| c.comes_after(*score, &proof.ulid))
+            })
+            .take(limit as usize + 1)
+            .collect();
+
+        let page = Page::from_rows(rows, limit, |(score, (proof, _, _))| {
+            RankCursor {
+                as_of,
+                score: *score,
+                ulid: proof.ulid.clone(),
             }
-        }
-
-        Ok(ProofFeedResponse {
-            items: results,
-            has_more,
-            next_offset: offset + limit,
+            .encode()
+        });
+        let rows = page.items.into_iter().map(|(_, row)| row).collect();
+        let items = Self::assemble_proofs(db, s3, current_user_id, rows).await?;
+
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
         })
     }
 
+    // Proofs `viewer_id` is allowed to see: their own, public ones, followers-only ones from
+    // authors they follow and lobby-only ones from authors sharing a lobby with them.
+    pub fn visible_to_condition(viewer_id: &str) -> Condition {
+        Condition::any()
+            .add(quest_proofs::Column::UserId.eq(viewer_id))
+            .add(quest_proofs::Column::Visibility.eq(ProofVisibility::Public))
+            .add(
+                Condition::all()
+                    .add(quest_proofs::Column::Visibility.eq(ProofVisibility::Followers))
+                    .add(FollowService::followed_by_condition(
+                        viewer_id,
+                        quest_proofs::Column::UserId,
+                    )),
+            )
+            .add(
+                Condition::all()
+                    .add(quest_proofs::Column::Visibility.eq(ProofVisibility::Lobby))
+                    .add(LobbyMemberService::shares_lobby_condition(
+                        viewer_id,
+                        quest_proofs::Column::UserId,
+                    )),
+            )
+    }
+
+    // Beliefs are weighted by the believer's level and account age, and the quest completes
+    // once the weighted sum reaches the required number of full beliefs.
     pub async fn toggle_belief(
         db: &DatabaseConnection,
+        events: &EventBus,
         proof_ulid: String,
         user_id: String,
+        config: &BeliefConfig,
     ) -> AppResult<bool> {
         let txn = db.begin().await?;
 
+        // Proofs the believer can't see don't exist for them
         let (proof, quest_option, user_option) = QuestProof::find_by_id(&proof_ulid)
+            .filter(Self::visible_to_condition(&user_id))
             .find_also_related(Quest)
             .find_also_related(User)
             .one(&txn)
@@ -312,19 +563,29 @@ impl QuestProofService {
             .one(&txn)
             .await?;
 
-        let diff = if let Some(belief) = existing_belief {
+        // Unbelieving takes back exactly the weight that was added
+        let (diff, weight_diff) = if let Some(belief) = existing_belief {
+            let weight = i64::from(belief.weight);
             belief.delete(&txn).await?;
 
-            -1
+            (-1, -weight)
         } else {
+            let believer = User::find_by_id(&user_id)
+                .one(&txn)
+                .await?
+                .ok_or(AppError::NotFound)?;
+            let weight =
+                QuestProofBeliefsService::belief_weight(config, &believer, chrono::Utc::now());
+
             let new_belief = quest_proof_beliefs::ActiveModel {
                 user_id: Set(user_id),
                 proof_id: Set(proof_ulid.clone()),
+                weight: Set(weight),
                 created_at: Set(chrono::Utc::now()),
             };
             new_belief.insert(&txn).await?;
 
-            1
+            (1, i64::from(weight))
         };
 
         let updated_proofs = QuestProof::update_many()
@@ -332,6 +593,10 @@ impl QuestProofService {
                 quest_proofs::Column::BeliefsCount,
                 Expr::col(quest_proofs::Column::BeliefsCount).add(diff),
             )
+            .col_expr(
+                quest_proofs::Column::BeliefWeight,
+                Expr::col(quest_proofs::Column::BeliefWeight).add(weight_diff),
+            )
             .filter(quest_proofs::Column::Ulid.eq(&proof_ulid))
             .exec_with_returning(&txn)
             .await?;
@@ -341,39 +606,42 @@ impl QuestProofService {
             .next()
             .ok_or(AppError::Custom("Failed to update proof count".into()))?;
 
-        // Check for level up and quest complete
-        if diff > 0
-            && let Some(quest) = quest_option
+        // Beliefs complete the quest once the weighted sum reaches the threshold and undo that
+        // completion when withdrawn below it. Moderator decisions take precedence over both.
+        let mut emitted = Vec::new();
+        if let Some(quest) = quest_option
             && let Some(user) = user_option
         {
-            let current_beliefs = updated_proof.beliefs_count;
-
-            let required_beliefs = quest.complexity.required_beliefs(user.level);
-
-            if current_beliefs >= required_beliefs {
-                let user_quest = UserQuestStatus::find()
-                    .filter(user_quest_status::Column::UserId.eq(&proof.user_id))
-                    .filter(user_quest_status::Column::QuestId.eq(&proof.quest_id))
-                    .one(&txn)
-                    .await?
-                    .ok_or(AppError::Custom("Quest status not found".into()))?;
-
-                if user_quest.quest_status != QuestStatus::Completed {
-                    let mut active_user_quest: user_quest_status::ActiveModel = user_quest.into();
-                    active_user_quest.quest_status = Set(QuestStatus::Completed);
-                    active_user_quest.updated_at = Set(chrono::Utc::now());
-                    active_user_quest.is_completed = Set(true);
-                    active_user_quest.update(&txn).await?;
-
-                    info!(
-                        "Quest {} for user {} COMPLETED by community belief!",
-                        proof.quest_id, proof.user_id
-                    );
+            let required_weight =
+                quest.complexity.required_beliefs(user.level) * FULL_BELIEF_WEIGHT;
+            let reached = updated_proof.belief_weight >= required_weight;
+
+            if diff > 0 && reached && updated_proof.status != ProofStatus::Rejected {
+                emitted = QuestCompletionService::complete(
+                    &txn,
+                    &updated_proof,
+                    &quest,
+                    CompletionSource::CommunityBelief,
+                )
+                .await?;
+
+                if !emitted.is_empty() {
+                    QuestProofBeliefsService::flag_if_mutual_cluster(&txn, config, &updated_proof)
+                        .await?;
                 }
+            } else if diff < 0 && !reached && updated_proof.status != ProofStatus::Approved {
+                emitted = QuestCompletionService::revert(
+                    &txn,
+                    &updated_proof,
+                    &quest,
+                    CompletionSource::CommunityBelief,
+                )
+                .await?;
             }
         }
 
         txn.commit().await?;
+        events.publish_all(emitted);
         Ok(diff > 0)
     }
 
@@ -389,31 +657,69 @@ impl QuestProofService {
             .unwrap_or(false)
     }
 
-    pub async fn get_user_history(
+    // Which of the given proofs the user believes in, answered with a single IN (...) query.
+    pub async fn believed_proof_ids(
         db: &DatabaseConnection,
-        s3: &S3Manager,
         user_id: &str,
-    ) -> Result<Vec<ProofDetailsResponse>, DbErr> {
-        let proofs = QuestProof::find()
-            .filter(quest_proofs::Column::UserId.eq(user_id))
-            .order_by_desc(quest_proofs::Column::CreatedAt)
-            .find_also_related(User)
-            .find_also_related(Quest)
+        proof_ids: &[String],
+    ) -> Result<HashSet<String>, DbErr> {
+        if proof_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+
+        let beliefs = QuestProofBeliefs::find()
+            .filter(quest_proof_beliefs::Column::UserId.eq(user_id))
+            .filter(quest_proof_beliefs::Column::ProofId.is_in(proof_ids.iter().cloned()))
             .all(db)
             .await?;
 
-        let mut results = Vec::with_capacity(proofs.len());
+        Ok(beliefs.into_iter().map(|b| b.proof_id).collect())
+    }
 
-        for (proof, user_opt, quest_opt) in proofs {
-            if let (Some(user), Some(quest)) = (user_opt, quest_opt) {
-                let photo_urls = s3.resolve_urls(proof.photos.as_ref()).await;
-                let voice_urls = s3.resolve_urls(proof.voice_notes.as_ref()).await;
+    // Turns a page of proofs into responses with one belief lookup and one batched presign,
+    // so the number of queries doesn't grow with the page size.
+    async fn assemble_proofs(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        viewer_id: &str,
+        rows: Vec<(Model, Option<users::Model>, Option<quests::Model>)>,
+    ) -> Result<Vec<ProofDetailsResponse>, DbErr> {
+        let proof_ids: Vec<String> = rows
+            .iter()
+            .map(|(proof, _, _)| proof.ulid.clone())
+            .collect();
+
+        let believed = Self::believed_proof_ids(db, viewer_id, &proof_ids).await?;
+
+        let keys: Vec<String> = rows
+            .iter()
+            .flat_map(|(proof, user, _)| {
+                let mut keys = S3Manager::media_keys(proof.photos.as_ref());
+                keys.extend(S3Manager::media_keys(proof.voice_notes.as_ref()));
+                keys.extend(user.as_ref().and_then(|u| u.avatar_url.clone()));
+                keys
+            })
+            .collect();
+        let urls = s3.get_download_urls(&keys, 3600).await;
+        let urls_for = |json: Option<&serde_json::Value>| -> Vec<String> {
+            S3Manager::media_keys(json)
+                .iter()
+                .filter_map(|key| urls.get(key).cloned())
+                .collect()
+        };
 
+        let mut results = Vec::with_capacity(rows.len());
+
+        for (proof, user_opt, quest_opt) in rows {
+            if let (Some(user), Some(quest)) = (user_opt, quest_opt) {
                 results.push(ProofDetailsResponse {
+                    photo_urls: urls_for(proof.photos.as_ref()),
+                    voice_urls: urls_for(proof.voice_notes.as_ref()),
+                    is_believed: believed.contains(&proof.ulid),
                     ulid: proof.ulid,
                     user_id: proof.user_id,
                     username: user.username,
-                    avatar_url: user.avatar_url,
+                    avatar_url: user.avatar_url.and_then(|key| urls.get(&key).cloned()),
                     quest_id: proof.quest_id,
                     quest_title: quest.title,
                     quest_description: quest.description,
@@ -421,13 +727,50 @@ impl QuestProofService {
                     proof_text: proof.proof_text,
                     status: format!("{:?}", proof.status),
                     beliefs_count: proof.beliefs_count,
-                    is_believed: false,
-                    photo_urls,
-                    voice_urls,
+                    visibility: proof.visibility,
                     created_at: proof.created_at,
                 });
             }
         }
+
         Ok(results)
     }
+
+    pub async fn get_user_history(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        user_id: &str,
+        viewer_id: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<ProofDetailsResponse>> {
+        let mut query = QuestProof::find()
+            .filter(quest_proofs::Column::UserId.eq(user_id))
+            .filter(Self::visible_to_condition(viewer_id));
+
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query
+                .filter(cursor.after(quest_proofs::Column::CreatedAt, quest_proofs::Column::Ulid));
+        }
+
+        let rows = query
+            .order_by_desc(quest_proofs::Column::CreatedAt)
+            .order_by_desc(quest_proofs::Column::Ulid)
+            .limit(limit + 1)
+            .find_also_related(User)
+            .find_also_related(Quest)
+            .all(db)
+            .await?;
+
+        let page = Page::from_rows(rows, limit, |(proof, _, _)| {
+            Cursor::new(proof.created_at, &proof.ulid).encode()
+        });
+        let items = Self::assemble_proofs(db, s3, viewer_id, page.items).await?;
+
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
 }
@@ -0,0 +1,57 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use shared::errors::{AppError, AppResult};
+
+// Sweeping idle keys only once the map grows keeps `check` cheap on the hot path
+const SWEEP_THRESHOLD: usize = 10_000;
+
+// In-memory sliding window limiter. Counts are per process, so they reset on restart and
+// aren't shared between server instances.
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    hits: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Records a hit for `key`, or fails with RateLimited when `limit` hits already happened
+    // within `window`. Rejected attempts are not recorded.
+    pub fn check(&self, key: &str, limit: u32, window: Duration) -> AppResult<()> {
+        self.check_at(key, limit, window, Instant::now())
+    }
+
+    pub fn check_at(&self, key: &str, limit: u32, window: Duration, now: Instant) -> AppResult<()> {
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+
+        if hits.len() > SWEEP_THRESHOLD {
+            hits.retain(|_, times| {
+                times
+                    .back()
+                    .is_some_and(|last| now.duration_since(*last) < window)
+            });
+        }
+
+        let times = hits.entry(key.to_owned()).or_default();
+        while times
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= window)
+        {
+            times.pop_front();
+        }
+
+        if times.len() >= limit as usize {
+            tracing::warn!(key, "Rate limit exceeded");
+            return Err(AppError::RateLimited);
+        }
+
+        times.push_back(now);
+        Ok(())
+    }
+}
@@ -0,0 +1,317 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ActiveValue::Set,
+    Condition, ExprTrait, FromQueryResult, QueryOrder, QuerySelect,
+    entity::prelude::*,
+    sea_query::{Alias, Func, Query},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        report_dto::{
+            ModerationQueueItem, ReportDto, ReportReason, ReportResolution, ReportStatus,
+            ReportTargetType,
+        },
+    },
+    utils::cursor::Cursor,
+};
+use std::collections::HashMap;
+
+use crate::config::env_or;
+use crate::entities::{
+    prelude::{Lobby, Message, QuestProof, Report, User},
+    reports,
+};
+
+const DEFAULT_REPORT_HIDE_THRESHOLD: u64 = 3;
+
+#[derive(Debug, Clone)]
+pub struct ReportConfig {
+    // Number of open reports after which a proof or message is hidden until resolved
+    pub hide_threshold: u64,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            hide_threshold: DEFAULT_REPORT_HIDE_THRESHOLD,
+        }
+    }
+}
+
+impl ReportConfig {
+    pub fn from_env() -> Self {
+        Self {
+            hide_threshold: env_or("REPORT_HIDE_THRESHOLD", DEFAULT_REPORT_HIDE_THRESHOLD),
+        }
+    }
+}
+
+#[derive(Debug, FromQueryResult)]
+struct QueueTarget {
+    target_type: ReportTargetType,
+    target_id: String,
+    first_reported_at: DateTime<Utc>,
+    open_reports: i64,
+}
+
+pub struct ReportService;
+
+impl ReportService {
+    // Reporting the same target twice returns the existing report instead of creating a new one.
+    #[tracing::instrument(skip(db, details))]
+    pub async fn create_report(
+        db: &DatabaseConnection,
+        reporter_id: &str,
+        target_type: ReportTargetType,
+        target_id: &str,
+        reason: ReportReason,
+        details: Option<String>,
+    ) -> AppResult<reports::Model> {
+        let owner_id = Self::find_target_owner(db, &target_type, target_id)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        if owner_id == reporter_id {
+            return Err(AppError::Custom("Cannot report your own content".into()));
+        }
+
+        if let Some(existing) =
+            Self::find_existing(db, reporter_id, &target_type, target_id).await?
+        {
+            tracing::debug!(report_id = %existing.ulid, "Duplicate report ignored");
+            return Ok(existing);
+        }
+
+        let new_report = reports::ActiveModel::new_report(
+            reporter_id.to_owned(),
+            target_type.clone(),
+            target_id.to_owned(),
+            reason,
+            details,
+        );
+
+        match new_report.insert(db).await {
+            Ok(model) => {
+                tracing::info!(report_id = %model.ulid, "Report created");
+                Ok(model)
+            }
+            // Lost a race against a concurrent identical report
+            Err(err) if matches!(err.sql_err(), Some(SqlErr::UniqueConstraintViolation(_))) => {
+                Self::find_existing(db, reporter_id, &target_type, target_id)
+                    .await?
+                    .ok_or(AppError::Database(err))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn find_existing(
+        db: &DatabaseConnection,
+        reporter_id: &str,
+        target_type: &ReportTargetType,
+        target_id: &str,
+    ) -> AppResult<Option<reports::Model>> {
+        Report::find()
+            .filter(reports::Column::ReporterId.eq(reporter_id))
+            .filter(reports::Column::TargetType.eq(target_type.clone()))
+            .filter(reports::Column::TargetId.eq(target_id))
+            .one(db)
+            .await
+            .map_err(AppError::from)
+    }
+
+    // Returns the user responsible for the reported target, or None if it doesn't exist.
+    async fn find_target_owner(
+        db: &DatabaseConnection,
+        target_type: &ReportTargetType,
+        target_id: &str,
+    ) -> AppResult<Option<String>> {
+        let owner = match target_type {
+            ReportTargetType::Proof => QuestProof::find_by_id(target_id)
+                .one(db)
+                .await?
+                .map(|p| p.user_id),
+            ReportTargetType::Message => Message::find_by_id(target_id)
+                .one(db)
+                .await?
+                .map(|m| m.author_id),
+            ReportTargetType::User => User::find_by_id(target_id).one(db).await?.map(|u| u.ulid),
+            ReportTargetType::Lobby => Lobby::find_by_id(target_id)
+                .one(db)
+                .await?
+                .map(|l| l.owner_id),
+        };
+
+        Ok(owner)
+    }
+
+    pub async fn is_moderator(db: &DatabaseConnection, user_id: &str) -> bool {
+        User::find_by_id(user_id)
+            .one(db)
+            .await
+            .ok()
+            .flatten()
+            .is_some_and(|u| u.is_moderator)
+    }
+
+    // Open reports grouped by target, oldest first report first so nothing starves in the
+    // queue. Paged by target, the cursor holds the target's first report time and its id.
+    pub async fn get_moderation_queue(
+        db: &DatabaseConnection,
+        moderator_id: &str,
+        config: &ReportConfig,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<ModerationQueueItem>> {
+        if !Self::is_moderator(db, moderator_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        let first_reported_at = || Func::min(Expr::col(reports::Column::CreatedAt));
+
+        let mut query = Report::find()
+            .select_only()
+            .column(reports::Column::TargetType)
+            .column(reports::Column::TargetId)
+            .column_as(first_reported_at(), "first_reported_at")
+            .column_as(
+                Func::count(Expr::col(reports::Column::Ulid)),
+                "open_reports",
+            )
+            .filter(reports::Column::Status.eq(ReportStatus::Open))
+            .group_by(reports::Column::TargetType)
+            .group_by(reports::Column::TargetId);
+
+        // Targets strictly after the cursor in (first_reported_at ASC, target_id ASC) order
+        if let Some(cursor) = Cursor::decode_opt(cursor)? {
+            query = query.having(
+                Condition::any()
+                    .add(first_reported_at().gt(cursor.created_at))
+                    .add(
+                        Condition::all()
+                            .add(first_reported_at().eq(cursor.created_at))
+                            .add(reports::Column::TargetId.gt(cursor.ulid)),
+                    ),
+            );
+        }
+
+        let targets = query
+            .order_by_asc(Expr::col(Alias::new("first_reported_at")))
+            .order_by_asc(reports::Column::TargetId)
+            .limit(limit + 1)
+            .into_model::<QueueTarget>()
+            .all(db)
+            .await?;
+        let page = Page::from_rows(targets, limit, |target| {
+            Cursor::new(target.first_reported_at, &target.target_id).encode()
+        });
+
+        let target_ids: Vec<String> = page.items.iter().map(|t| t.target_id.clone()).collect();
+        let open_reports = Report::find()
+            .filter(reports::Column::Status.eq(ReportStatus::Open))
+            .filter(reports::Column::TargetId.is_in(target_ids))
+            .order_by_asc(reports::Column::CreatedAt)
+            .all(db)
+            .await?;
+
+        let mut by_target: HashMap<(ReportTargetType, String), Vec<ReportDto>> = HashMap::new();
+        for report in open_reports {
+            by_target
+                .entry((report.target_type.clone(), report.target_id.clone()))
+                .or_default()
+                .push(report.into());
+        }
+
+        let items = page
+            .items
+            .into_iter()
+            .map(|target| ModerationQueueItem {
+                open_reports: target.open_reports as u32,
+                is_hidden: target.open_reports as u64 >= config.hide_threshold,
+                reports: by_target
+                    .remove(&(target.target_type.clone(), target.target_id.clone()))
+                    .unwrap_or_default(),
+                target_type: target.target_type,
+                target_id: target.target_id,
+            })
+            .collect();
+
+        Ok(Page {
+            items,
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    // Resolves every open report on the target at once and returns how many were touched.
+    #[tracing::instrument(skip(db))]
+    pub async fn resolve_target(
+        db: &DatabaseConnection,
+        moderator_id: &str,
+        target_type: ReportTargetType,
+        target_id: &str,
+        resolution: ReportResolution,
+    ) -> AppResult<u64> {
+        if !Self::is_moderator(db, moderator_id).await {
+            return Err(AppError::NotFound);
+        }
+
+        let new_status = match resolution {
+            ReportResolution::Dismiss => ReportStatus::Dismissed,
+            ReportResolution::Remove => ReportStatus::Actioned,
+        };
+
+        let result = Report::update_many()
+            .set(reports::ActiveModel {
+                status: Set(new_status),
+                resolved_at: Set(Some(chrono::Utc::now())),
+                resolved_by: Set(Some(moderator_id.to_owned())),
+                ..Default::default()
+            })
+            .filter(reports::Column::TargetType.eq(target_type))
+            .filter(reports::Column::TargetId.eq(target_id))
+            .filter(reports::Column::Status.eq(ReportStatus::Open))
+            .exec(db)
+            .await?;
+
+        if result.rows_affected == 0 {
+            return Err(AppError::NotFound);
+        }
+
+        tracing::info!(rows = result.rows_affected, "Reports resolved");
+        Ok(result.rows_affected)
+    }
+
+    // Condition that keeps only targets that are not auto-hidden: fewer open reports than the
+    // threshold and no report a moderator has confirmed.
+    pub fn not_hidden_condition<C>(
+        target_type: ReportTargetType,
+        column: C,
+        threshold: u64,
+    ) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        let over_threshold = Query::select()
+            .column(reports::Column::TargetId)
+            .from(reports::Entity)
+            .and_where(reports::Column::TargetType.eq(target_type.clone()))
+            .and_where(reports::Column::Status.eq(ReportStatus::Open))
+            .group_by_col(reports::Column::TargetId)
+            .and_having(Func::count(Expr::col(reports::Column::Ulid)).gte(threshold))
+            .to_owned();
+
+        let actioned = Query::select()
+            .column(reports::Column::TargetId)
+            .from(reports::Entity)
+            .and_where(reports::Column::TargetType.eq(target_type))
+            .and_where(reports::Column::Status.eq(ReportStatus::Actioned))
+            .to_owned();
+
+        Condition::all()
+            .add(column.not_in_subquery(over_threshold))
+            .add(column.not_in_subquery(actioned))
+    }
+}
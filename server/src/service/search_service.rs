@@ -0,0 +1,352 @@
+use std::collections::HashMap;
+
+use sea_orm::{
+    Condition, DbBackend, FromQueryResult, QueryOrder, QuerySelect, Select, Statement,
+    entity::prelude::*,
+    sea_query::{Alias, Expr, Query},
+};
+use shared::{
+    errors::{AppError, AppResult},
+    models::{
+        Page,
+        report_dto::ReportTargetType,
+        search_dto::{SearchHit, SnippetPart},
+    },
+};
+
+use crate::entities::{
+    lobbies, messages,
+    prelude::{Lobby, Message, Quest, QuestProof},
+    quest_proofs, quests,
+};
+use crate::service::{
+    lobby_member_service::LobbyMemberService, lobby_service::LobbyService,
+    quest_proof_service::QuestProofService, report_service::ReportService,
+};
+
+// Queries longer than this are cut, every term makes the match more expensive
+const MAX_QUERY_TERMS: usize = 8;
+// Words of context in a snippet
+const SNIPPET_WORDS: u32 = 12;
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+
+// The full-text index behind each kind of search. Everything backend specific lives in
+// `matching` and `snippets`: SQLite answers them with the FTS5 tables of the search index
+// migration, a Postgres version would use a `tsvector` column with `@@` and `ts_headline`.
+#[derive(Debug, Clone, Copy)]
+enum SearchIndex {
+    Messages,
+    Proofs,
+    Lobbies,
+    Quests,
+}
+
+#[derive(Debug, FromQueryResult)]
+struct SnippetRow {
+    ulid: String,
+    snippet: String,
+}
+
+impl SearchIndex {
+    fn table(self) -> &'static str {
+        match self {
+            Self::Messages => "messages_fts",
+            Self::Proofs => "quest_proofs_fts",
+            Self::Lobbies => "lobbies_fts",
+            Self::Quests => "quests_fts",
+        }
+    }
+
+    // Restricts `ulid_column` to rows matching the query
+    fn matching<C>(self, backend: DbBackend, ulid_column: C, query: &str) -> AppResult<Condition>
+    where
+        C: ColumnTrait,
+    {
+        if backend != DbBackend::Sqlite {
+            return Err(AppError::Custom("Search is not available".into()));
+        }
+
+        let table = self.table();
+        let matches = Query::select()
+            .column(Alias::new("ulid"))
+            .from(Alias::new(table))
+            .and_where(Expr::cust_with_values(format!("{table} MATCH ?"), [query]))
+            .to_owned();
+
+        Ok(Condition::all().add(ulid_column.in_subquery(matches)))
+    }
+
+    // Highlighted snippet of each of the given rows
+    async fn snippets(
+        self,
+        db: &DatabaseConnection,
+        query: &str,
+        ulids: &[String],
+    ) -> AppResult<HashMap<String, Vec<SnippetPart>>> {
+        if ulids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let table = self.table();
+        let placeholders = vec!["?"; ulids.len()].join(", ");
+        // Column -1 lets FTS5 pick the column that matched best, e.g. a lobby's name or topic
+        let sql = format!(
+            "SELECT ulid, snippet({table}, -1, char(2), char(3), '…', {SNIPPET_WORDS}) AS snippet \
+             FROM {table} WHERE {table} MATCH ? AND ulid IN ({placeholders})"
+        );
+        let values = std::iter::once(Value::from(query))
+            .chain(ulids.iter().map(|ulid| Value::from(ulid.as_str())));
+
+        let rows = SnippetRow::find_by_statement(Statement::from_sql_and_values(
+            db.get_database_backend(),
+            sql,
+            values,
+        ))
+        .all(db)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.ulid, snippet_parts(&row.snippet)))
+            .collect())
+    }
+}
+
+// Turns what the user typed into an FTS5 query. Terms are quoted so operators and stray quotes
+// can't break the syntax, every term must match and the last one may be unfinished.
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<&str> = input
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .take(MAX_QUERY_TERMS)
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+
+    let quoted: Vec<String> = terms.iter().map(|term| format!("\"{term}\"")).collect();
+    Some(format!("{}*", quoted.join(" ")))
+}
+
+pub fn snippet_parts(raw: &str) -> Vec<SnippetPart> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+    let mut highlighted = false;
+
+    for c in raw.chars() {
+        if c != HIGHLIGHT_START && c != HIGHLIGHT_END {
+            text.push(c);
+            continue;
+        }
+        if !text.is_empty() {
+            parts.push(SnippetPart {
+                text: std::mem::take(&mut text),
+                highlighted,
+            });
+        }
+        highlighted = c == HIGHLIGHT_START;
+    }
+    if !text.is_empty() {
+        parts.push(SnippetPart { text, highlighted });
+    }
+
+    parts
+}
+
+pub struct SearchService;
+
+impl SearchService {
+    // Lobbies whose name or topic match `q`, None when it has no terms to match
+    pub fn lobbies_matching(backend: DbBackend, q: &str) -> AppResult<Option<Condition>> {
+        fts_query(q)
+            .map(|query| SearchIndex::Lobbies.matching(backend, lobbies::Column::Ulid, &query))
+            .transpose()
+    }
+
+    // Messages of the user's lobbies, or of one of them
+    pub async fn search_messages(
+        db: &DatabaseConnection,
+        user_id: &str,
+        q: &str,
+        lobby_id: Option<&str>,
+        limit: u64,
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<SearchHit>> {
+        let mut select = Message::find()
+            .filter(messages::Column::DeletedAt.is_null())
+            .filter(LobbyMemberService::member_of_condition(
+                user_id,
+                messages::Column::LobbyId,
+            ))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Message,
+                messages::Column::Ulid,
+                report_hide_threshold,
+            ));
+
+        if let Some(lobby_id) = lobby_id {
+            if !LobbyMemberService::is_member(db, lobby_id, user_id).await {
+                return Err(AppError::NotFound);
+            }
+            select = select.filter(messages::Column::LobbyId.eq(lobby_id));
+        }
+
+        Self::search(
+            db,
+            SearchIndex::Messages,
+            select,
+            messages::Column::Ulid,
+            q,
+            limit,
+            cursor,
+            |message| (message.ulid, Some(message.lobby_id)),
+        )
+        .await
+    }
+
+    // Proofs the user may see, by the same rules as the feed
+    pub async fn search_proofs(
+        db: &DatabaseConnection,
+        user_id: &str,
+        q: &str,
+        limit: u64,
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<SearchHit>> {
+        let select = QuestProof::find()
+            .filter(QuestProofService::visible_to_condition(user_id))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Proof,
+                quest_proofs::Column::Ulid,
+                report_hide_threshold,
+            ));
+
+        Self::search(
+            db,
+            SearchIndex::Proofs,
+            select,
+            quest_proofs::Column::Ulid,
+            q,
+            limit,
+            cursor,
+            |proof| (proof.ulid, None),
+        )
+        .await
+    }
+
+    // Public lobbies, and unlisted or private ones the user is in
+    pub async fn search_lobbies(
+        db: &DatabaseConnection,
+        user_id: &str,
+        q: &str,
+        limit: u64,
+        cursor: Option<&str>,
+        report_hide_threshold: u64,
+    ) -> AppResult<Page<SearchHit>> {
+        let select = Lobby::find()
+            .filter(LobbyService::listed_condition(user_id))
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Lobby,
+                lobbies::Column::Ulid,
+                report_hide_threshold,
+            ));
+
+        Self::search(
+            db,
+            SearchIndex::Lobbies,
+            select,
+            lobbies::Column::Ulid,
+            q,
+            limit,
+            cursor,
+            |lobby| (lobby.ulid, None),
+        )
+        .await
+    }
+
+    // Global quests, and lobby quests of the user's lobbies
+    pub async fn search_quests(
+        db: &DatabaseConnection,
+        user_id: &str,
+        q: &str,
+        limit: u64,
+        cursor: Option<&str>,
+    ) -> AppResult<Page<SearchHit>> {
+        let select =
+            Quest::find().filter(Condition::any().add(quests::Column::LobbyId.is_null()).add(
+                LobbyMemberService::member_of_condition(user_id, quests::Column::LobbyId),
+            ));
+
+        Self::search(
+            db,
+            SearchIndex::Quests,
+            select,
+            quests::Column::Ulid,
+            q,
+            limit,
+            cursor,
+            |quest| (quest.ulid, quest.lobby_id),
+        )
+        .await
+    }
+
+    // Newest first: ULIDs sort by creation time, so the last ULID of a page is the cursor
+    #[expect(clippy::too_many_arguments)]
+    async fn search<E, C>(
+        db: &DatabaseConnection,
+        index: SearchIndex,
+        select: Select<E>,
+        ulid_column: C,
+        q: &str,
+        limit: u64,
+        cursor: Option<&str>,
+        hit_of: impl Fn(E::Model) -> (String, Option<String>),
+    ) -> AppResult<Page<SearchHit>>
+    where
+        E: EntityTrait,
+        C: ColumnTrait,
+    {
+        let Some(query) = fts_query(q) else {
+            return Ok(Page {
+                items: Vec::new(),
+                has_more: false,
+                next_cursor: None,
+            });
+        };
+
+        let mut select =
+            select.filter(index.matching(db.get_database_backend(), ulid_column, &query)?);
+        if let Some(cursor) = cursor {
+            select = select.filter(ulid_column.lt(cursor));
+        }
+
+        let rows: Vec<(String, Option<String>)> = select
+            .order_by_desc(ulid_column)
+            .limit(limit + 1)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(hit_of)
+            .collect();
+        let page = Page::from_rows(rows, limit, |(ulid, _)| ulid.clone());
+
+        let ulids: Vec<String> = page.items.iter().map(|(ulid, _)| ulid.clone()).collect();
+        let mut snippets = index.snippets(db, &query, &ulids).await?;
+
+        Ok(Page {
+            items: page
+                .items
+                .into_iter()
+                .map(|(ulid, lobby_id)| SearchHit {
+                    snippet: snippets.remove(&ulid).unwrap_or_default(),
+                    ulid,
+                    lobby_id,
+                })
+                .collect(),
+            has_more: page.has_more,
+            next_cursor: page.next_cursor,
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use sea_orm::*;
+use shared::{
+    errors::{AppError, AppResult},
+    models::{quest_proof_dto::ProofVisibility, report_dto::ReportTargetType},
+};
+use ulid::Ulid;
+
+use crate::{
+    entities::{
+        prelude::{ProofShare, Quest, QuestProof},
+        proof_shares,
+        quest_proofs::{self, ProofStatus},
+    },
+    file_storage::s3_client::S3Manager,
+    service::report_service::ReportService,
+};
+
+const DESCRIPTION_MAX_CHARS: usize = 200;
+
+pub struct SharedProofPage {
+    pub quest_title: String,
+    pub proof_text: Option<String>,
+    pub thumbnail_url: Option<String>,
+    pub beliefs_count: u32,
+}
+
+pub struct ShareService;
+
+impl ShareService {
+    // 128 random bits, hex encoded. Only the random part of each ULID is used so the token
+    // doesn't leak when it was created.
+    fn generate_token() -> String {
+        format!(
+            "{:020x}{:012x}",
+            Ulid::new().random(),
+            Ulid::new().random() >> 32
+        )
+    }
+
+    // Only public proofs can be shared. Sharing twice returns the existing token, so a link
+    // handed out earlier keeps working until it is revoked.
+    pub async fn create_share(
+        db: &DatabaseConnection,
+        proof_id: &str,
+        user_id: &str,
+    ) -> AppResult<proof_shares::Model> {
+        let proof = QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await?
+            .filter(|p| p.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
+
+        if proof.visibility != ProofVisibility::Public {
+            return Err(AppError::Custom("Only public proofs can be shared".into()));
+        }
+        if proof.status == ProofStatus::Uploading {
+            return Err(AppError::Custom(
+                "Proof uploads are not confirmed yet".into(),
+            ));
+        }
+
+        if let Some(existing) = ProofShare::find()
+            .filter(proof_shares::Column::ProofId.eq(proof_id))
+            .one(db)
+            .await?
+        {
+            return Ok(existing);
+        }
+
+        let share = proof_shares::ActiveModel::new_share(Self::generate_token(), proof.ulid)
+            .insert(db)
+            .await?;
+
+        Ok(share)
+    }
+
+    pub async fn revoke_share(
+        db: &DatabaseConnection,
+        proof_id: &str,
+        user_id: &str,
+    ) -> AppResult<()> {
+        QuestProof::find_by_id(proof_id)
+            .one(db)
+            .await?
+            .filter(|p| p.user_id == user_id)
+            .ok_or(AppError::NotFound)?;
+
+        ProofShare::delete_many()
+            .filter(proof_shares::Column::ProofId.eq(proof_id))
+            .exec(db)
+            .await?;
+
+        Ok(())
+    }
+
+    // The page is re-checked on every view: a proof that became non-public, got rejected or
+    // was hidden by reports disappears even while its token still exists.
+    pub async fn get_shared_proof(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        token: &str,
+        report_hide_threshold: u64,
+    ) -> AppResult<SharedProofPage> {
+        let share = ProofShare::find_by_id(token)
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        let (proof, quest) = QuestProof::find_by_id(share.proof_id)
+            .filter(quest_proofs::Column::Visibility.eq(ProofVisibility::Public))
+            .filter(
+                quest_proofs::Column::Status.is_in([ProofStatus::Pending, ProofStatus::Approved]),
+            )
+            .filter(ReportService::not_hidden_condition(
+                ReportTargetType::Proof,
+                quest_proofs::Column::Ulid,
+                report_hide_threshold,
+            ))
+            .find_also_related(Quest)
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+        let quest = quest.ok_or(AppError::NotFound)?;
+
+        let thumbnail_url = match S3Manager::media_keys(proof.photos.as_ref()).first() {
+            Some(key) => s3.get_short_lived_url(key).await,
+            None => None,
+        };
+
+        Ok(SharedProofPage {
+            quest_title: quest.title,
+            proof_text: proof.proof_text,
+            thumbnail_url,
+            beliefs_count: proof.beliefs_count,
+        })
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl SharedProofPage {
+    pub fn render_html(&self) -> String {
+        let title = escape_html(&self.quest_title);
+        let text = escape_html(self.proof_text.as_deref().unwrap_or_default());
+        let description: String = match self.proof_text.as_deref() {
+            Some(text) if !text.is_empty() => text.chars().take(DESCRIPTION_MAX_CHARS).collect(),
+            _ => format!("{} people believe in this proof", self.beliefs_count),
+        };
+        let description = escape_html(&description);
+
+        let (image_meta, image_tag) = match &self.thumbnail_url {
+            Some(url) => {
+                let url = escape_html(url);
+                (
+                    format!(
+                        "<meta property=\"og:image\" content=\"{url}\">\n\
+                         <meta name=\"twitter:card\" content=\"summary_large_image\">"
+                    ),
+                    format!("<img src=\"{url}\" alt=\"{title}\">"),
+                )
+            }
+            None => (
+                "<meta name=\"twitter:card\" content=\"summary\">".to_owned(),
+                String::new(),
+            ),
+        };
+
+        format!(
+            "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">
+<title>{title}</title>
+<meta property=\"og:type\" content=\"article\">
+<meta property=\"og:title\" content=\"{title}\">
+<meta property=\"og:description\" content=\"{description}\">
+{image_meta}
+</head>
+<body>
+<h1>{title}</h1>
+{image_tag}
+<p>{text}</p>
+<p>{beliefs} believe</p>
+</body>
+</html>
+",
+            beliefs = self.beliefs_count,
+        )
+    }
+}
@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration as StdDuration, Instant};
+
+    use chrono::{Duration, Utc};
+    use shared::errors::AppError;
+
+    use crate::entities::users;
+    use crate::service::{
+        quest_proof_beliefs_service::{BeliefConfig, FULL_BELIEF_WEIGHT, QuestProofBeliefsService},
+        rate_limiter::RateLimiter,
+    };
+
+    fn believer(level: u32, age_days: i64) -> users::Model {
+        users::Model {
+            ulid: "BELIEVER".into(),
+            username: "believer".into(),
+            email: "believer@test.com".into(),
+            password_hash: "hash".into(),
+            xp_balance: 0,
+            total_xp_accumulated: 0,
+            level,
+            created_at: Utc::now() - Duration::days(age_days),
+            last_active_at: Utc::now(),
+            avatar_url: None,
+            bio: None,
+            is_moderator: false,
+        }
+    }
+
+    fn weight(level: u32, age_days: i64) -> u32 {
+        QuestProofBeliefsService::belief_weight(
+            &BeliefConfig::default(),
+            &believer(level, age_days),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_established_account_gives_full_weight() {
+        assert_eq!(weight(5, 30), FULL_BELIEF_WEIGHT);
+        assert_eq!(weight(40, 365), FULL_BELIEF_WEIGHT);
+    }
+
+    #[test]
+    fn test_weight_scales_with_level_and_account_age() {
+        assert_eq!(weight(1, 30), 500);
+        assert_eq!(weight(3, 30), 750);
+        assert_eq!(weight(5, 7), 500);
+    }
+
+    #[test]
+    fn test_brand_new_account_gets_minimum_weight() {
+        assert_eq!(weight(1, 0), BeliefConfig::default().min_weight);
+    }
+
+    #[test]
+    fn test_rate_limiter_uses_sliding_window() {
+        let limiter = RateLimiter::new();
+        let window = StdDuration::from_secs(60);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("key", 2, window, start).is_ok());
+        assert!(limiter.check_at("key", 2, window, start).is_ok());
+        assert!(matches!(
+            limiter.check_at("key", 2, window, start),
+            Err(AppError::RateLimited)
+        ));
+
+        // Other keys have their own budget
+        assert!(limiter.check_at("other", 2, window, start).is_ok());
+
+        assert!(limiter.check_at("key", 2, window, start + window).is_ok());
+    }
+}
@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, Utc};
+    use ulid::Ulid;
+
+    use crate::service::{
+        feed_ranking::{FeedRanking, FeedRankingConfig, RankCursor, RankingSignals},
+        quest_proof_beliefs_service::FULL_BELIEF_WEIGHT,
+    };
+
+    fn signals(age_hours: i64, beliefs: u32, required_beliefs: u32) -> RankingSignals {
+        RankingSignals {
+            created_at: Utc::now() - Duration::hours(age_hours),
+            belief_weight: beliefs * FULL_BELIEF_WEIGHT,
+            required_beliefs,
+            shares_lobby: false,
+            follows_author: false,
+        }
+    }
+
+    #[test]
+    fn test_recency_halves_every_half_life() {
+        let config = FeedRankingConfig::default();
+        let now = Utc::now();
+
+        let fresh = FeedRanking::recency(&config, now, now);
+        let one_half_life = FeedRanking::recency(&config, now - Duration::hours(24), now);
+
+        assert!((fresh - 1.0).abs() < 1e-9);
+        assert!((one_half_life - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_belief_progress_prefers_nearly_complete_proofs() {
+        assert_eq!(FeedRanking::belief_progress(4 * FULL_BELIEF_WEIGHT, 5), 0.8);
+        assert_eq!(FeedRanking::belief_progress(FULL_BELIEF_WEIGHT, 5), 0.2);
+        // Already complete, nothing left to push
+        assert_eq!(FeedRanking::belief_progress(5 * FULL_BELIEF_WEIGHT, 5), 0.0);
+        assert_eq!(FeedRanking::belief_progress(7 * FULL_BELIEF_WEIGHT, 5), 0.0);
+    }
+
+    #[test]
+    fn test_belief_progress_counts_weight_not_believers() {
+        // Four beliefs from brand-new accounts are far from the 4 full beliefs needed
+        let light = FeedRanking::belief_progress(4 * 100, 4);
+        assert_eq!(light, 0.1);
+        assert!(light < FeedRanking::belief_progress(3 * FULL_BELIEF_WEIGHT, 4));
+
+        let now = Utc::now();
+        let config = FeedRankingConfig::default();
+        let mut s = signals(0, 0, 4);
+        s.belief_weight = 4 * 100;
+        let light_score = FeedRanking::score(&config, &s, now);
+        s.belief_weight = 3 * FULL_BELIEF_WEIGHT;
+        assert!(FeedRanking::score(&config, &s, now) > light_score);
+    }
+
+    #[test]
+    fn test_nearly_complete_proof_outranks_newer_one() {
+        let config = FeedRankingConfig::default();
+        let now = Utc::now();
+
+        let needs_one_more = FeedRanking::score(&config, &signals(12, 1, 2), now);
+        let brand_new = FeedRanking::score(&config, &signals(0, 0, 10), now);
+
+        assert!(needs_one_more > brand_new);
+    }
+
+    #[test]
+    fn test_social_signals_use_their_weights() {
+        let config = FeedRankingConfig {
+            recency_weight: 0.0,
+            belief_progress_weight: 0.0,
+            shared_lobby_weight: 2.0,
+            followed_author_weight: 3.0,
+            ..FeedRankingConfig::default()
+        };
+        let now = Utc::now();
+
+        let mut s = signals(1, 0, 2);
+        assert_eq!(FeedRanking::score(&config, &s, now), 0.0);
+
+        s.shares_lobby = true;
+        assert_eq!(FeedRanking::score(&config, &s, now), 2.0);
+
+        s.follows_author = true;
+        assert_eq!(FeedRanking::score(&config, &s, now), 5.0);
+    }
+
+    #[test]
+    fn test_rank_cursor_roundtrip_and_order() {
+        let cursor = RankCursor {
+            as_of: Utc::now(),
+            score: 1.2345,
+            ulid: Ulid::new().to_string(),
+        };
+
+        let decoded = RankCursor::decode(&cursor.encode()).unwrap();
+        assert_eq!(decoded, cursor);
+
+        assert!(decoded.comes_after(1.0, &Ulid::new().to_string()));
+        assert!(!decoded.comes_after(2.0, "00000000000000000000000000"));
+        assert!(RankCursor::decode("garbage").is_err());
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use crate::service::lobby_discovery_service::DiscoveryCursor;
+    use shared::endpoints::lobby_endpoints::LobbySort;
+
+    const ULID: &str = "01ARZ3NDEKTSV4RRFFQ69G5FAV";
+    const OTHER_ULID: &str = "01BX5ZZKBKACTAV9WEVGEMMVRZ";
+
+    #[test]
+    fn test_cursor_roundtrips_for_each_sort() {
+        let cursors = [
+            (
+                LobbySort::Members,
+                DiscoveryCursor::Members {
+                    members_count: 42,
+                    ulid: ULID.into(),
+                },
+            ),
+            (
+                LobbySort::Activity,
+                DiscoveryCursor::Activity {
+                    last_activity: OTHER_ULID.into(),
+                    ulid: ULID.into(),
+                },
+            ),
+            (
+                LobbySort::Newest,
+                DiscoveryCursor::Newest { ulid: ULID.into() },
+            ),
+        ];
+
+        for (sort, cursor) in cursors {
+            assert_eq!(
+                DiscoveryCursor::decode(sort, &cursor.encode()).unwrap(),
+                cursor
+            );
+        }
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage_and_other_sorts() {
+        let members = DiscoveryCursor::Members {
+            members_count: 3,
+            ulid: ULID.into(),
+        }
+        .encode();
+
+        assert!(DiscoveryCursor::decode(LobbySort::Newest, &members).is_err());
+        assert!(DiscoveryCursor::decode(LobbySort::Activity, &members).is_err());
+        assert!(DiscoveryCursor::decode(LobbySort::Members, ULID).is_err());
+        assert!(DiscoveryCursor::decode(LobbySort::Members, &format!("zz-{ULID}")).is_err());
+        assert!(DiscoveryCursor::decode(LobbySort::Newest, "garbage").is_err());
+    }
+}
@@ -1,12 +1,12 @@
 #[cfg(test)]
 mod tests {
 
-    use crate::entities::{lobbies, lobbies_members};
+    use crate::entities::{lobbies, lobbies_members, lobby_bans};
     use crate::service::lobby_member_service::LobbyMemberService;
     use chrono::Utc;
     use sea_orm::{DatabaseBackend, MockDatabase};
     use shared::errors::AppError;
-    use shared::models::lobby_dto::Role;
+    use shared::models::lobby_dto::{LobbyVisibility, Role};
 
     // Помогатор для создания дефолтной модели лобби
     fn mock_lobby_model(id: &str) -> lobbies::Model {
@@ -16,6 +16,7 @@ mod tests {
             description: None,
             topic: "Rust".into(),
             owner_id: "OWNER".into(),
+            visibility: LobbyVisibility::Public,
             created_at: Utc::now(),
         }
     }
@@ -27,6 +28,7 @@ mod tests {
             user_id: user_id.to_owned(),
             joined_at: Utc::now(),
             role,
+            last_read_message_id: None,
         }
     }
 
@@ -39,6 +41,9 @@ mod tests {
 
         let db = MockDatabase::new(DatabaseBackend::Sqlite)
             .append_query_results(vec![vec![mock_lobby_model(lobby_id)]])
+            // Нет бана, нет членства, нет сообщений
+            .append_query_results(vec![Vec::<lobby_bans::Model>::new()])
+            .append_query_results(vec![Vec::<lobbies::Model>::new()])
             .append_query_results(vec![Vec::<lobbies::Model>::new()])
             .append_query_results(vec![vec![mock_member_model(
                 lobby_id,
@@ -81,8 +86,9 @@ mod tests {
             .append_query_results(vec![
                 // 1. Лобби существует
                 vec![mock_lobby_model(lobby_id)],
-                // 2. Юзер уже есть (возвращаем модель)
             ])
+            .append_query_results(vec![Vec::<lobby_bans::Model>::new()])
+            // 2. Юзер уже есть (возвращаем модель)
             .append_query_results(vec![vec![mock_member_model(
                 lobby_id,
                 user_id,
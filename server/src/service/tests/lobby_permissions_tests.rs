@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use crate::service::lobby_permissions::LobbyRank;
+    use shared::models::lobby_dto::Role;
+
+    #[test]
+    fn test_removals_follow_the_ranks() {
+        let owner = LobbyRank::of(&Role::Admin, true);
+        let admin = LobbyRank::of(&Role::Admin, false);
+        let moderator = LobbyRank::of(&Role::Moderator, false);
+        let member = LobbyRank::of(&Role::Member, false);
+
+        assert!(moderator.can_remove(member));
+        assert!(!moderator.can_remove(moderator));
+        assert!(admin.can_remove(moderator));
+        assert!(!admin.can_remove(admin));
+        assert!(owner.can_remove(admin));
+        assert!(!member.can_remove(LobbyRank::Stranger));
+    }
+
+    #[test]
+    fn test_only_the_owner_makes_admins() {
+        let owner = LobbyRank::Owner;
+        let admin = LobbyRank::Admin;
+        let member = LobbyRank::Member;
+
+        assert!(admin.can_assign(member, &Role::Moderator));
+        assert!(!admin.can_assign(member, &Role::Admin));
+        assert!(owner.can_assign(member, &Role::Admin));
+        assert!(owner.can_assign(admin, &Role::Member));
+        assert!(!LobbyRank::Moderator.can_assign(member, &Role::Helper));
+        assert!(!owner.can_assign(LobbyRank::Stranger, &Role::Member));
+        assert!(!owner.can_assign(member, &Role::Stranger));
+    }
+}
@@ -2,10 +2,11 @@
 mod tests {
     use crate::entities::{lobbies, lobbies_members};
     use crate::service::lobby_service::LobbyService;
+    use crate::service::moderation_service::ModerationConfig;
     use chrono::Utc;
     use sea_orm::{DatabaseBackend, MockDatabase, MockExecResult};
     use shared::errors::AppError;
-    use shared::models::lobby_dto::Role;
+    use shared::models::lobby_dto::{LobbyVisibility, Role};
 
     fn mock_lobby_model(id: &str, owner_id: &str) -> lobbies::Model {
         lobbies::Model {
@@ -14,6 +15,7 @@ mod tests {
             description: Some("Description".into()),
             topic: "Programming".into(),
             owner_id: owner_id.to_owned(),
+            visibility: LobbyVisibility::Public,
             created_at: Utc::now(),
         }
     }
@@ -24,6 +26,7 @@ mod tests {
             user_id: user_id.to_owned(),
             joined_at: Utc::now(),
             role,
+            last_read_message_id: None,
         }
     }
 
@@ -43,10 +46,12 @@ mod tests {
 
         let result = LobbyService::create_lobby(
             &db,
+            &ModerationConfig::default().build_filter(),
             owner_id.into(),
             lobby_name.into(),
             "Rust".into(),
             None,
+            LobbyVisibility::Public,
         )
         .await;
 
@@ -1,4 +1,14 @@
+pub mod belief_weight_tests;
+pub mod feed_ranking_tests;
+pub mod lobby_discovery_tests;
 pub mod lobby_member_tests;
+pub mod lobby_permissions_tests;
 pub mod lobby_tests;
+pub mod moderation_tests;
+pub mod photo_hash_tests;
+pub mod presence_tests;
+pub mod quest_proof_tests;
 pub mod quest_tests;
+pub mod search_tests;
+pub mod upload_policy_tests;
 pub mod user_tests;
@@ -0,0 +1,141 @@
+#[cfg(test)]
+mod tests {
+    use migration::{Migrator, MigratorTrait};
+    use sea_orm::{ConnectOptions, Database, DatabaseConnection, EntityTrait};
+    use shared::{
+        errors::AppError,
+        models::{lobby_dto::LobbyVisibility, report_dto::ReportTargetType},
+        moderation::{ModerationAction, ModerationRule, TextFilter, Wordlist},
+    };
+
+    use crate::entities::{moderation_flags::FlagReason, prelude::ModerationFlag};
+    use crate::service::{
+        lobby_service::LobbyService,
+        moderation_service::{ModerationConfig, ModerationService},
+        user_service::UserService,
+    };
+
+    async fn setup_db() -> DatabaseConnection {
+        let mut opt = ConnectOptions::new("sqlite::memory:");
+        opt.sqlx_logging(false);
+        let db = Database::connect(opt)
+            .await
+            .expect("Failed to connect to test DB");
+
+        Migrator::up(&db, None)
+            .await
+            .expect("Failed to run migrations");
+        db
+    }
+
+    async fn create_user(db: &DatabaseConnection, name: &str) -> String {
+        UserService::create_user(
+            db,
+            name.into(),
+            format!("{name}@test.com"),
+            "password123".into(),
+        )
+        .await
+        .unwrap()
+        .ulid
+    }
+
+    #[test]
+    fn test_config_falls_back_to_builtin_lists() {
+        let config = ModerationConfig {
+            locales: vec!["sr".into(), "xx".into()],
+            wordlist_dir: Some("/nonexistent".into()),
+        };
+        let filter = config.build_filter();
+
+        assert_eq!(filter.check("govnar").masked.len(), 1);
+        assert!(filter.check("fuck").masked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blocked_lobby_name_is_rejected() {
+        let db = setup_db().await;
+        let owner = create_user(&db, "owner").await;
+        let filter = ModerationConfig::default().build_filter();
+
+        let result = LobbyService::create_lobby(
+            &db,
+            &filter,
+            owner,
+            "p3d3r club".into(),
+            "Fun".into(),
+            None,
+            LobbyVisibility::Public,
+        )
+        .await;
+
+        assert!(matches!(result, Err(AppError::Custom(_))));
+    }
+
+    #[tokio::test]
+    async fn test_masked_and_flagged_text_is_stored() {
+        let db = setup_db().await;
+        let owner = create_user(&db, "owner").await;
+        let filter = ModerationConfig::default().build_filter();
+
+        let lobby = LobbyService::create_lobby(
+            &db,
+            &filter,
+            owner.clone(),
+            "Talk club".into(),
+            "Fun".into(),
+            Some("No sh1t talking, you idiot".into()),
+            LobbyVisibility::Public,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            lobby.description.as_deref(),
+            Some("No **** talking, you idiot")
+        );
+
+        let user =
+            UserService::update_profile(&db, &filter, &owner, None, Some("Ја сам кретен".into()))
+                .await
+                .unwrap();
+        assert_eq!(user.bio.as_deref(), Some("Ја сам кретен"));
+
+        let flags = ModerationFlag::find().all(&db).await.unwrap();
+        assert_eq!(flags.len(), 2);
+        assert!(flags.iter().all(|f| f.reason == FlagReason::OffensiveText));
+        assert!(
+            flags
+                .iter()
+                .any(|f| f.target_type == ReportTargetType::Lobby && f.target_id == lobby.ulid)
+        );
+        assert!(
+            flags
+                .iter()
+                .any(|f| f.target_type == ReportTargetType::User && f.target_id == owner)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flag_is_raised_once_per_target() {
+        let db = setup_db().await;
+        let user = create_user(&db, "user").await;
+        let filter = TextFilter::new(&[Wordlist {
+            locale: "test".into(),
+            rules: vec![ModerationRule::new("hülye", ModerationAction::Flag, true)],
+        }]);
+
+        for bio in ["hülye vagyok", "HULYE"] {
+            let outcome = filter.moderate(bio).unwrap();
+            ModerationService::flag_for_review(
+                &db,
+                ReportTargetType::User,
+                &user,
+                &[Some(&outcome)],
+            )
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(ModerationFlag::find().all(&db).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+    use image::{DynamicImage, ImageFormat, RgbImage, imageops::FilterType};
+    use std::io::Cursor;
+
+    use crate::file_storage::perceptual_hash::{
+        HASH_BANDS, MATCH_DISTANCE, bands, hamming_distance, hash_image, perceptual_hash,
+    };
+
+    fn landscape() -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(256, 192, |x, y| {
+            let sky = (y * 255 / 192) as u8;
+            let hill = if y > 120 + (x / 8) % 24 { 60 } else { 0 };
+            image::Rgb([sky / 2, sky.saturating_sub(hill), 255 - sky])
+        }))
+    }
+
+    fn checkerboard() -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(256, 192, |x, y| {
+            if (x / 32 + y / 32) % 2 == 0 {
+                image::Rgb([240, 240, 240])
+            } else {
+                image::Rgb([20, 20, 20])
+            }
+        }))
+    }
+
+    fn encode(image: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, format).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_reencoded_and_resized_photo_stays_close() {
+        let original = hash_image(&landscape());
+
+        let jpeg = perceptual_hash(&encode(&landscape(), ImageFormat::Jpeg)).unwrap();
+        assert!(hamming_distance(original, jpeg) <= MATCH_DISTANCE);
+
+        let smaller = landscape().resize_exact(128, 96, FilterType::Lanczos3);
+        let resized = perceptual_hash(&encode(&smaller, ImageFormat::Png)).unwrap();
+        assert!(hamming_distance(original, resized) <= MATCH_DISTANCE);
+    }
+
+    #[test]
+    fn test_different_photo_is_far() {
+        let distance = hamming_distance(hash_image(&landscape()), hash_image(&checkerboard()));
+        assert!(distance > MATCH_DISTANCE * 4, "distance was {distance}");
+    }
+
+    #[test]
+    fn test_close_hashes_share_a_band() {
+        let hash = hash_image(&landscape());
+        // Flip one bit in each of three different bands, the fourth must still match
+        let changed = hash ^ 1 ^ (1 << 20) ^ (1 << 40);
+        assert_eq!(hamming_distance(hash, changed), MATCH_DISTANCE);
+
+        let shared = bands(hash)
+            .iter()
+            .zip(bands(changed))
+            .filter(|(a, b)| **a == *b)
+            .count();
+        assert_eq!(shared, HASH_BANDS - MATCH_DISTANCE as usize);
+    }
+
+    #[test]
+    fn test_undecodable_bytes_are_an_error() {
+        assert!(perceptual_hash(b"not an image").is_err());
+    }
+}
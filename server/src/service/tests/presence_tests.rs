@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::service::presence::{PresenceRegistry, TYPING_THROTTLE};
+
+    #[test]
+    fn test_join_and_leave_only_on_first_and_last_socket() {
+        let presence = PresenceRegistry::new();
+
+        assert!(presence.connect("lobby", "alice"));
+        assert!(!presence.connect("lobby", "alice"));
+        assert!(presence.connect("lobby", "bob"));
+        assert!(presence.connect("other", "alice"));
+
+        let mut online = presence.online_users("lobby");
+        online.sort();
+        assert_eq!(online, vec!["alice", "bob"]);
+
+        assert!(!presence.disconnect("lobby", "alice"));
+        assert!(presence.disconnect("lobby", "alice"));
+        assert_eq!(presence.online_users("lobby"), vec!["bob"]);
+        assert_eq!(presence.online_users("other"), vec!["alice"]);
+
+        // A socket that was never registered doesn't count as leaving
+        assert!(!presence.disconnect("lobby", "carol"));
+        assert!(presence.disconnect("lobby", "bob"));
+        assert!(presence.online_users("lobby").is_empty());
+    }
+
+    #[test]
+    fn test_typing_is_throttled_per_user() {
+        let presence = PresenceRegistry::new();
+        let start = Instant::now();
+
+        assert!(presence.should_broadcast_typing_at("lobby", "alice", start));
+        assert!(!presence.should_broadcast_typing_at(
+            "lobby",
+            "alice",
+            start + Duration::from_secs(1)
+        ));
+        assert!(presence.should_broadcast_typing_at(
+            "lobby",
+            "bob",
+            start + Duration::from_secs(1)
+        ));
+        assert!(presence.should_broadcast_typing_at("lobby", "alice", start + TYPING_THROTTLE));
+    }
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::Utc;
+    use sea_orm::{
+        DatabaseBackend, EntityTrait, IdenStatic, Iterable, MockDatabase, ModelTrait, Value,
+    };
+    use shared::models::{quest_dto::Complexity, quest_proof_dto::ProofVisibility};
+
+    use crate::{
+        entities::{quest_proof_beliefs, quest_proofs, quests, users},
+        file_storage::s3_client::S3Manager,
+        service::quest_proof_service::QuestProofService,
+    };
+
+    const VIEWER_ID: &str = "VIEWER";
+
+    fn mock_user_model(id: &str) -> users::Model {
+        users::Model {
+            ulid: id.to_owned(),
+            username: format!("user_{id}"),
+            email: format!("{id}@test.com"),
+            password_hash: "hash".into(),
+            xp_balance: 0,
+            total_xp_accumulated: 0,
+            level: 1,
+            created_at: Utc::now(),
+            last_active_at: Utc::now(),
+            avatar_url: None,
+            bio: None,
+            is_moderator: false,
+        }
+    }
+
+    fn mock_quest_model(id: &str) -> quests::Model {
+        quests::Model {
+            ulid: id.to_owned(),
+            lobby_id: None,
+            title: "Quest".into(),
+            description: None,
+            complexity: Complexity::Easy,
+            xp_reward: 10,
+            validation_type: "COMMUNITY".into(),
+            target_value: 1,
+        }
+    }
+
+    fn mock_proof_model(id: &str) -> quest_proofs::Model {
+        quest_proofs::Model {
+            ulid: id.to_owned(),
+            user_id: format!("AUTHOR_{id}"),
+            quest_id: "Q1".into(),
+            proof_text: Some("Done".into()),
+            photos: None,
+            voice_notes: None,
+            status: quest_proofs::ProofStatus::Pending,
+            beliefs_count: 0,
+            belief_weight: 0,
+            visibility: ProofVisibility::Public,
+            photos_hashed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    // MockDatabase has no IntoMockRow for three-way joins, so the row is built by hand
+    fn joined_row<M: ModelTrait>(row: &mut BTreeMap<String, Value>, prefix: &str, model: &M) {
+        for column in <M::Entity as EntityTrait>::Column::iter() {
+            row.insert(format!("{prefix}{}", column.as_str()), model.get(column));
+        }
+    }
+
+    fn feed_row(proof_id: &str) -> BTreeMap<String, Value> {
+        let proof = mock_proof_model(proof_id);
+        let mut row = BTreeMap::new();
+        joined_row(&mut row, "A_", &proof);
+        joined_row(&mut row, "B_", &mock_user_model(&proof.user_id));
+        joined_row(&mut row, "C_", &mock_quest_model(&proof.quest_id));
+        row
+    }
+
+    fn belief(proof_id: &str) -> quest_proof_beliefs::Model {
+        quest_proof_beliefs::Model {
+            user_id: VIEWER_ID.into(),
+            proof_id: proof_id.to_owned(),
+            weight: 1000,
+            created_at: Utc::now(),
+        }
+    }
+
+    async fn feed_query_count(page_size: usize) -> usize {
+        let proof_ids: Vec<String> = (0..page_size).map(|i| format!("P{i}")).collect();
+
+        let db = MockDatabase::new(DatabaseBackend::Sqlite)
+            .append_query_results(vec![
+                proof_ids.iter().map(|id| feed_row(id)).collect::<Vec<_>>(),
+            ])
+            .append_query_results(vec![vec![belief(&proof_ids[0])]])
+            .into_connection();
+
+        let s3 = S3Manager::new(
+            "test-bucket".into(),
+            "mock-endpoint".into(),
+            "mock-region".into(),
+        )
+        .await;
+
+        let feed =
+            QuestProofService::get_feed(&db, &s3, VIEWER_ID, page_size as u64, None, 3, false)
+                .await
+                .unwrap();
+
+        assert_eq!(feed.items.len(), page_size);
+        assert!(feed.items[0].is_believed);
+        assert!(feed.items[1..].iter().all(|p| !p.is_believed));
+
+        db.into_transaction_log().len()
+    }
+
+    #[tokio::test]
+    async fn test_feed_query_count_does_not_grow_with_page_size() {
+        // One feed select plus one belief lookup for the whole page
+        assert_eq!(feed_query_count(2).await, 2);
+        assert_eq!(feed_query_count(20).await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_believed_proof_ids_skips_query_for_empty_page() {
+        let db = MockDatabase::new(DatabaseBackend::Sqlite).into_connection();
+
+        let believed = QuestProofService::believed_proof_ids(&db, VIEWER_ID, &[])
+            .await
+            .unwrap();
+
+        assert!(believed.is_empty());
+        assert!(db.into_transaction_log().is_empty());
+    }
+}
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::service::search_service::{fts_query, snippet_parts};
+
+    #[test]
+    fn test_fts_query_quotes_terms() {
+        assert_eq!(
+            fts_query("Šabac \"market\" OR"),
+            Some("\"Šabac\" \"market\" \"OR\"*".into())
+        );
+        assert_eq!(fts_query("  *** \" "), None);
+    }
+
+    #[test]
+    fn test_snippet_parts_split_highlights() {
+        let parts = snippet_parts("…at the \u{2}market\u{3} today");
+        let parts: Vec<_> = parts
+            .iter()
+            .map(|part| (part.text.as_str(), part.highlighted))
+            .collect();
+        assert_eq!(
+            parts,
+            vec![("…at the ", false), ("market", true), (" today", false)]
+        );
+    }
+}
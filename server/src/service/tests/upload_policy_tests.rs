@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod tests {
+    use aws_credential_types::Credentials;
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use chrono::{TimeZone, Utc};
+    use serde_json::{Value, json};
+
+    use crate::file_storage::upload_policy::{
+        PostPolicy, UploadKind, UploadLimits, sign_post_policy, signing_key,
+    };
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn policy(max_bytes: u64) -> PostPolicy<'static> {
+        PostPolicy {
+            bucket: "speak-please",
+            region: "us-east-1",
+            key: "users/U1/proofs/P1/photos/photo_0.jpg",
+            content_type: UploadKind::Photo.content_type(),
+            max_bytes,
+            expires_in: 3600,
+        }
+    }
+
+    #[test]
+    fn test_signing_key_matches_aws_reference() {
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+
+        assert_eq!(
+            hex(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_policy_pins_key_content_type_and_size() {
+        let credentials = Credentials::new("AKID", "SECRET", None, None, "test");
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+        let limits = UploadLimits::default();
+
+        let fields = sign_post_policy(
+            &policy(limits.max_bytes(UploadKind::Photo)),
+            &credentials,
+            now,
+        );
+
+        assert_eq!(fields["Content-Type"], "image/jpeg");
+        assert_eq!(fields["x-amz-date"], "20261018T120000Z");
+        assert_eq!(
+            fields["x-amz-credential"],
+            "AKID/20261018/us-east-1/s3/aws4_request"
+        );
+        assert_eq!(fields["x-amz-signature"].len(), 64);
+        assert!(!fields.contains_key("x-amz-security-token"));
+
+        let document: Value =
+            serde_json::from_slice(&STANDARD.decode(&fields["policy"]).unwrap()).unwrap();
+        assert_eq!(document["expiration"], "2026-10-18T13:00:00.000Z");
+
+        let conditions = document["conditions"].as_array().unwrap();
+        assert!(conditions.contains(&json!(["eq", "$key", fields["key"]])));
+        assert!(conditions.contains(&json!(["eq", "$Content-Type", "image/jpeg"])));
+        assert!(conditions.contains(&json!(["content-length-range", 1, limits.photo_max_bytes])));
+    }
+
+    #[test]
+    fn test_signature_depends_on_limits() {
+        let credentials = Credentials::new("AKID", "SECRET", Some("TOKEN".into()), None, "test");
+        let now = Utc.with_ymd_and_hms(2026, 10, 18, 12, 0, 0).unwrap();
+
+        let small = sign_post_policy(&policy(1024), &credentials, now);
+        let large = sign_post_policy(&policy(2048), &credentials, now);
+
+        assert_eq!(small["x-amz-security-token"], "TOKEN");
+        assert_ne!(small["x-amz-signature"], large["x-amz-signature"]);
+    }
+}
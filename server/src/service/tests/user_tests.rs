@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use crate::{entities::users, service::user_service::UserService};
+    use crate::{
+        entities::users,
+        service::{moderation_service::ModerationConfig, user_service::UserService},
+    };
     use chrono::Utc;
     use sea_orm::{DatabaseBackend, MockDatabase};
     use shared::{
@@ -21,6 +24,7 @@ mod tests {
             last_active_at: Utc::now(),
             avatar_url: None,
             bio: None,
+            is_moderator: false,
         }
     }
 
@@ -103,14 +107,22 @@ mod tests {
         let db = MockDatabase::new(DatabaseBackend::Sqlite)
             .append_query_results(vec![
                 vec![mock_user_model("U1", "old_name", "test@test.com")],
+                // Nobody else uses the new name
+                vec![],
                 vec![mock_user_model("U1", "new_name", "test@test.com")],
             ])
             .into_connection();
 
-        let updated =
-            UserService::update_profile(&db, "U1", Some("new_name".into()), Some("New Bio".into()))
-                .await
-                .unwrap();
+        let filter = ModerationConfig::default().build_filter();
+        let updated = UserService::update_profile(
+            &db,
+            &filter,
+            "U1",
+            Some("new_name".into()),
+            Some("New Bio".into()),
+        )
+        .await
+        .unwrap();
 
         assert_eq!(updated.username, "new_name");
     }
@@ -1,7 +1,4 @@
-use sea_orm::{
-    ActiveValue::Set, Order, QueryOrder, QuerySelect, TransactionTrait, entity::prelude::*,
-    sqlx::types::chrono,
-};
+use sea_orm::{Order, QueryOrder, QuerySelect, entity::prelude::*, sqlx::types::chrono};
 use shared::{
     errors::{AppError, AppResult},
     models::{quest_dto::Complexity, user_quest_status_dto::QuestStatus},
@@ -150,43 +147,4 @@ impl UserQuestService {
             .await
             .map_err(AppError::from)
     }
-
-    pub async fn complete_quest_internal<C>(
-        db: &C,
-        user_id: &str,
-        quest_id: &str,
-        date: Date,
-    ) -> AppResult<user_quest_status::Model>
-    where
-        C: ConnectionTrait,
-    {
-        let status = UserQuestStatus::find_by_id((user_id.to_owned(), quest_id.to_owned(), date))
-            .one(db)
-            .await?
-            .ok_or(AppError::NotFound)?;
-
-        if status.is_completed {
-            return Ok(status);
-        }
-
-        let mut active_status: user_quest_status::ActiveModel = status.into();
-        active_status.is_completed = Set(true);
-        active_status.quest_status = Set(QuestStatus::Completed);
-        active_status.updated_at = Set(chrono::Utc::now());
-
-        Ok(active_status.update(db).await?)
-    }
-
-    // 8. Публичный метод с транзакцией
-    pub async fn complete_quest(
-        db: &DatabaseConnection,
-        user_id: &str,
-        quest_id: &str,
-        date: Date,
-    ) -> AppResult<user_quest_status::Model> {
-        let txn = db.begin().await?;
-        let res = Self::complete_quest_internal(&txn, user_id, quest_id, date).await?;
-        txn.commit().await?;
-        Ok(res)
-    }
 }
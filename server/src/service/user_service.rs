@@ -6,16 +6,22 @@
 //    └── profile.rs       # Получение данных профиля, смена аватара, статуса
 // TODO: THINK ABOUT IT
 
-use sea_orm::Set;
 use sea_orm::entity::prelude::*;
+use sea_orm::{Set, SqlErr};
 use shared::{
     errors::{AppError, AppResult, DbResultExt, auth_errors::AuthError},
+    models::{
+        PresignedUpload, message_dto::LobbyUserDto, report_dto::ReportTargetType,
+        user_dto::ProfileResponse,
+    },
+    moderation::TextFilter,
     utils::hashing::{hash, verify_hash},
 };
 
 use crate::{
     entities::{prelude::*, users},
-    file_storage::s3_client::S3Manager,
+    file_storage::{s3_client::S3Manager, upload_policy::UploadKind},
+    service::moderation_service::ModerationService,
 };
 
 pub struct UserService;
@@ -107,30 +113,101 @@ impl UserService {
 
     pub async fn update_profile(
         db: &DatabaseConnection,
+        filter: &TextFilter,
         user_id: &str,
         new_username: Option<String>,
         new_bio: Option<String>,
     ) -> AppResult<users::Model> {
+        let new_bio = ModerationService::moderate_opt(filter, new_bio)?;
+
         let user = User::find_by_id(user_id)
             .one(db)
             .await?
             .ok_or(AppError::NotFound)?;
 
+        let new_username = new_username.filter(|username| *username != user.username);
+        if let Some(username) = &new_username {
+            let taken = User::find()
+                .filter(users::Column::Username.eq(username))
+                .one(db)
+                .await?
+                .is_some();
+            if taken {
+                return Err(AuthError::UsernameTaken.into());
+            }
+        }
+
         let mut active_user: users::ActiveModel = user.into();
 
         if let Some(username) = new_username {
             active_user.username = Set(username);
         }
-        if let Some(bio) = new_bio {
-            active_user.bio = Set(Some(bio));
+        if let Some(bio) = &new_bio {
+            active_user.bio = Set(Some(bio.text.clone()));
+        }
+
+        // The check above can race with another rename, the unique index has the final say
+        let user = active_user
+            .update(db)
+            .await
+            .map_err(|e| match e.sql_err() {
+                Some(SqlErr::UniqueConstraintViolation(_)) => AuthError::UsernameTaken.into(),
+                _ => AppError::from(e),
+            })?;
+        ModerationService::flag_for_review(
+            db,
+            ReportTargetType::User,
+            &user.ulid,
+            &[new_bio.as_ref()],
+        )
+        .await?;
+
+        Ok(user)
+    }
+
+    pub async fn get_profile(
+        db: &DatabaseConnection,
+        s3: &S3Manager,
+        user_id: &str,
+    ) -> AppResult<ProfileResponse> {
+        let user = User::find_by_id(user_id)
+            .one(db)
+            .await?
+            .ok_or(AppError::NotFound)?;
+
+        Ok(Self::to_profile(s3, user).await)
+    }
+
+    // The avatar column holds the storage key, clients only ever get a signed URL
+    pub async fn to_profile(s3: &S3Manager, user: users::Model) -> ProfileResponse {
+        ProfileResponse {
+            avatar_url: s3.resolve_url(user.avatar_url.as_deref()).await,
+            ulid: user.ulid,
+            username: user.username,
+            email: user.email,
+            bio: user.bio,
+            level: user.level,
+            xp_balance: user.xp_balance,
+            total_xp_accumulated: user.total_xp_accumulated,
+            created_at: user.created_at,
         }
+    }
 
-        Ok(active_user.update(db).await?)
+    pub async fn to_lobby_user(s3: &S3Manager, user: users::Model) -> LobbyUserDto {
+        LobbyUserDto {
+            avatar_url: s3.resolve_url(user.avatar_url.as_deref()).await,
+            ulid: user.ulid,
+            username: user.username,
+            level: user.level,
+        }
     }
 
-    pub async fn get_avatar_upload_url(user_id: &str, s3: &S3Manager) -> AppResult<String> {
+    pub async fn get_avatar_upload_url(
+        user_id: &str,
+        s3: &S3Manager,
+    ) -> AppResult<PresignedUpload> {
         let key = format!("users/{}/avatar.jpg", user_id);
-        s3.get_upload_url(&key, "image/jpeg", 3600)
+        s3.get_upload_form(&key, UploadKind::Avatar, 3600)
             .await
             .map_err(|e| AppError::Custom(e.to_string()))
     }
@@ -0,0 +1,38 @@
+use crate::endpoints::{API, UserUlid};
+
+pub enum FollowEndpoints {
+    // POST /users/{user_id}/follow
+    Follow(UserUlid),
+    // DELETE /users/{user_id}/follow
+    Unfollow(UserUlid),
+    // GET /users/{user_id}/follows/counts
+    GetCounts(UserUlid),
+    // GET /users/{user_id}/followers
+    GetFollowers(UserUlid),
+    // GET /users/{user_id}/following
+    GetFollowing(UserUlid),
+}
+
+impl API for FollowEndpoints {
+    fn path(&self) -> String {
+        match self {
+            Self::Follow(user_id) | Self::Unfollow(user_id) => format!("/users/{user_id}/follow"),
+            Self::GetCounts(user_id) => format!("/users/{user_id}/follows/counts"),
+            Self::GetFollowers(user_id) => format!("/users/{user_id}/followers"),
+            Self::GetFollowing(user_id) => format!("/users/{user_id}/following"),
+        }
+    }
+
+    fn template(&self) -> &'static str {
+        match self {
+            Self::Follow(_) | Self::Unfollow(_) => "/users/{user_id}/follow",
+            Self::GetCounts(_) => "/users/{user_id}/follows/counts",
+            Self::GetFollowers(_) => "/users/{user_id}/followers",
+            Self::GetFollowing(_) => "/users/{user_id}/following",
+        }
+    }
+
+    fn is_auth_endpoint(&self) -> bool {
+        false
+    }
+}
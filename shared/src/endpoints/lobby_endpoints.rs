@@ -1,11 +1,84 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use validator::Validate;
+
 use crate::endpoints::{API, LobbyUlid, UserUlid};
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum LobbySort {
+    // Most members first
+    #[default]
+    Members,
+    // Latest message first, lobbies nobody wrote in yet count from their creation
+    Activity,
+    Newest,
+}
+
+// `cursor` is the previous page's `next_cursor`, only valid with the same filters and sort
+#[derive(Debug, Default, Serialize, Deserialize, Validate, Type)]
+pub struct DiscoverQuery {
+    // Matches the name and topic
+    #[validate(length(max = 100))]
+    pub q: Option<String>,
+    #[validate(length(min = 2, max = 30))]
+    pub topic: Option<String>,
+    #[serde(default)]
+    pub sort: LobbySort,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+// Private lobbies only show their details to members and to holders of a usable invite
+#[derive(Debug, Default, Serialize, Deserialize, Type)]
+pub struct LobbyDetailsQuery {
+    pub invite: Option<String>,
+}
+
 pub enum LobbyEndpoints {
     Create,
     GetAll(UserUlid),
+    // GET with DiscoverQuery, listed lobbies the user isn't banned from
+    Discover,
+    // GET, topics of the listed lobbies, most used first
+    GetTopics,
     GetDetails(LobbyUlid),
     Join(LobbyUlid, UserUlid),
     GetMembersCount(LobbyUlid),
+    GetOnlineMembers(LobbyUlid),
+    // POST, the owner has to transfer the lobby first
+    Leave(LobbyUlid),
+    // DELETE
+    KickMember(LobbyUlid, UserUlid),
+    // PUT with SetRoleRequest
+    SetRole(LobbyUlid, UserUlid),
+    // PUT with BanMemberRequest
+    Ban(LobbyUlid, UserUlid),
+    // DELETE
+    Unban(LobbyUlid, UserUlid),
+    // POST with TransferOwnershipRequest
+    TransferOwnership(LobbyUlid),
+    // PUT with SetVisibilityRequest
+    SetVisibility(LobbyUlid),
+    // POST with CreateInviteRequest
+    CreateInvite(LobbyUlid),
+    // GET, the lobby's invites for its moderators
+    GetInvites(LobbyUlid),
+    // DELETE
+    RevokeInvite(LobbyUlid, String),
+    // GET, the lobby behind an invite code
+    GetInvite(String),
+    // POST
+    AcceptInvite(String),
+    // POST with CreateJoinRequest, private lobbies only
+    RequestToJoin(LobbyUlid),
+    // GET, pending requests for the lobby's admins
+    GetJoinRequests(LobbyUlid),
+    // POST
+    ApproveJoinRequest(LobbyUlid, UserUlid),
+    // DELETE, rejects the request, or cancels it when it's the user's own
+    DeleteJoinRequest(LobbyUlid, UserUlid),
 }
 
 impl API for LobbyEndpoints {
@@ -13,9 +86,37 @@ impl API for LobbyEndpoints {
         match self {
             Self::Create => "/lobbies".to_string(),
             Self::GetAll(user_ulid) => format!("/users/{user_ulid}/lobbies"),
+            Self::Discover => "/lobbies/discover".to_string(),
+            Self::GetTopics => "/lobbies/topics".to_string(),
             Self::GetDetails(id) => format!("/lobbies/{id}"),
             Self::Join(lobby_id, user_id) => format!("/lobbies/{lobby_id}/join/{user_id}"),
             Self::GetMembersCount(id) => format!("/lobbies/{id}/members/count"),
+            Self::GetOnlineMembers(id) => format!("/lobbies/{id}/members/online"),
+            Self::Leave(id) => format!("/lobbies/{id}/leave"),
+            Self::KickMember(lobby_id, user_id) => {
+                format!("/lobbies/{lobby_id}/members/{user_id}")
+            }
+            Self::SetRole(lobby_id, user_id) => {
+                format!("/lobbies/{lobby_id}/members/{user_id}/role")
+            }
+            Self::Ban(lobby_id, user_id) | Self::Unban(lobby_id, user_id) => {
+                format!("/lobbies/{lobby_id}/bans/{user_id}")
+            }
+            Self::TransferOwnership(id) => format!("/lobbies/{id}/owner"),
+            Self::SetVisibility(id) => format!("/lobbies/{id}/visibility"),
+            Self::CreateInvite(id) | Self::GetInvites(id) => format!("/lobbies/{id}/invites"),
+            Self::RevokeInvite(lobby_id, code) => format!("/lobbies/{lobby_id}/invites/{code}"),
+            Self::GetInvite(code) => format!("/invites/{code}"),
+            Self::AcceptInvite(code) => format!("/invites/{code}/accept"),
+            Self::RequestToJoin(id) | Self::GetJoinRequests(id) => {
+                format!("/lobbies/{id}/requests")
+            }
+            Self::ApproveJoinRequest(lobby_id, user_id) => {
+                format!("/lobbies/{lobby_id}/requests/{user_id}/approve")
+            }
+            Self::DeleteJoinRequest(lobby_id, user_id) => {
+                format!("/lobbies/{lobby_id}/requests/{user_id}")
+            }
         }
     }
 
@@ -23,9 +124,25 @@ impl API for LobbyEndpoints {
         match self {
             Self::Create => "/lobbies",
             Self::GetAll(_) => "/users/{user_ulid}/lobbies",
+            Self::Discover => "/lobbies/discover",
+            Self::GetTopics => "/lobbies/topics",
             Self::GetDetails(_) => "/lobbies/{id}",
             Self::Join(_, _) => "/lobbies/{lobby_id}/join/{user_id}",
             Self::GetMembersCount(_) => "/lobbies/{id}/members/count",
+            Self::GetOnlineMembers(_) => "/lobbies/{id}/members/online",
+            Self::Leave(_) => "/lobbies/{id}/leave",
+            Self::KickMember(_, _) => "/lobbies/{lobby_id}/members/{user_id}",
+            Self::SetRole(_, _) => "/lobbies/{lobby_id}/members/{user_id}/role",
+            Self::Ban(_, _) | Self::Unban(_, _) => "/lobbies/{lobby_id}/bans/{user_id}",
+            Self::TransferOwnership(_) => "/lobbies/{id}/owner",
+            Self::SetVisibility(_) => "/lobbies/{id}/visibility",
+            Self::CreateInvite(_) | Self::GetInvites(_) => "/lobbies/{id}/invites",
+            Self::RevokeInvite(_, _) => "/lobbies/{lobby_id}/invites/{code}",
+            Self::GetInvite(_) => "/invites/{code}",
+            Self::AcceptInvite(_) => "/invites/{code}/accept",
+            Self::RequestToJoin(_) | Self::GetJoinRequests(_) => "/lobbies/{id}/requests",
+            Self::ApproveJoinRequest(_, _) => "/lobbies/{lobby_id}/requests/{user_id}/approve",
+            Self::DeleteJoinRequest(_, _) => "/lobbies/{lobby_id}/requests/{user_id}",
         }
     }
 
@@ -3,20 +3,66 @@ use crate::endpoints::LobbyUlid;
 use super::API;
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use validator::Validate;
 
-#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Validate)]
 pub struct SendMessageRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub content: String,
+    // A message of the same lobby
+    pub reply_to: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Validate)]
+pub struct EditMessageRequest {
+    #[validate(length(min = 1, max = 2000))]
     pub content: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Type)]
+pub struct MarkReadRequest {
+    pub message_id: String,
+    // Move the read marker without sending others a read receipt
+    #[serde(default)]
+    pub silent: bool,
+}
+
+// Messages newer than `since`, oldest first. `next_cursor` of the page is the next `since`.
+// Edits, deletions and reactions on messages up to `since` are not part of it.
+#[derive(Serialize, Deserialize, Debug, Clone, Type, Validate)]
+pub struct SyncQuery {
+    pub since: String,
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+}
+
+// Query of the socket handshake. With `since` the socket first replays the messages that
+// were missed, with the same limits as the sync endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Type)]
+pub struct SocketQuery {
+    pub since: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Type)]
 pub enum MessageEndpoints {
-    /// GET /lobbies/{lobby_id}/messages?page=X&per_page=Y
+    /// GET /lobbies/{lobby_id}/messages?limit=X&cursor=Y
     GetLobbyMessages(LobbyUlid),
     /// POST /lobbies/{lobby_id}/messages
     SendMessage(LobbyUlid),
+    /// PATCH /lobbies/{lobby_id}/messages/{message_id}
+    EditMessage(LobbyUlid, String),
     /// DELETE /lobbies/{lobby_id}/messages/{message_id}
     DeleteMessage(LobbyUlid, String),
+    /// PUT /lobbies/{lobby_id}/messages/{message_id}/reactions/{emoji}
+    AddReaction(LobbyUlid, String, String),
+    /// DELETE /lobbies/{lobby_id}/messages/{message_id}/reactions/{emoji}
+    RemoveReaction(LobbyUlid, String, String),
+    /// GET /lobbies/{lobby_id}/messages/sync?since=X&limit=Y
+    SyncMessages(LobbyUlid),
+    /// POST /lobbies/{lobby_id}/read
+    MarkRead(LobbyUlid),
+    /// GET /lobbies/{lobby_id}/ws (WebSocket upgrade)
+    LobbySocket(LobbyUlid),
 }
 
 impl API for MessageEndpoints {
@@ -28,9 +74,22 @@ impl API for MessageEndpoints {
             MessageEndpoints::SendMessage(lobby_id) => {
                 format!("/lobbies/{}/messages", lobby_id)
             }
-            MessageEndpoints::DeleteMessage(lobby_id, message_id) => {
+            MessageEndpoints::EditMessage(lobby_id, message_id)
+            | MessageEndpoints::DeleteMessage(lobby_id, message_id) => {
                 format!("/lobbies/{}/messages/{}", lobby_id, message_id)
             }
+            MessageEndpoints::AddReaction(lobby_id, message_id, emoji)
+            | MessageEndpoints::RemoveReaction(lobby_id, message_id, emoji) => {
+                format!(
+                    "/lobbies/{}/messages/{}/reactions/{}",
+                    lobby_id, message_id, emoji
+                )
+            }
+            MessageEndpoints::SyncMessages(lobby_id) => {
+                format!("/lobbies/{}/messages/sync", lobby_id)
+            }
+            MessageEndpoints::MarkRead(lobby_id) => format!("/lobbies/{}/read", lobby_id),
+            MessageEndpoints::LobbySocket(lobby_id) => format!("/lobbies/{}/ws", lobby_id),
         }
     }
 
@@ -38,7 +97,15 @@ impl API for MessageEndpoints {
         match self {
             MessageEndpoints::GetLobbyMessages(_) => "/lobbies/{lobby_id}/messages",
             MessageEndpoints::SendMessage(_) => "/lobbies/{lobby_id}/messages",
-            MessageEndpoints::DeleteMessage(_, _) => "/lobbies/{lobby_id}/messages/{message_id}",
+            MessageEndpoints::EditMessage(_, _) | MessageEndpoints::DeleteMessage(_, _) => {
+                "/lobbies/{lobby_id}/messages/{message_id}"
+            }
+            MessageEndpoints::AddReaction(_, _, _) | MessageEndpoints::RemoveReaction(_, _, _) => {
+                "/lobbies/{lobby_id}/messages/{message_id}/reactions/{emoji}"
+            }
+            MessageEndpoints::SyncMessages(_) => "/lobbies/{lobby_id}/messages/sync",
+            MessageEndpoints::MarkRead(_) => "/lobbies/{lobby_id}/read",
+            MessageEndpoints::LobbySocket(_) => "/lobbies/{lobby_id}/ws",
         }
     }
 
@@ -45,9 +45,13 @@ pub trait API {
     fn is_auth_endpoint(&self) -> bool;
 }
 
+pub mod follow_endpoints;
 pub mod lobby_endpoints;
 pub mod message_endpoints;
 pub mod quest_proof_endpoints;
 pub mod refresh_token_endpoints;
+pub mod report_endpoints;
+pub mod search_endpoints;
+pub mod share_endpoints;
 pub mod user_endpoints;
 pub mod user_quest_status_endpoints;
@@ -6,10 +6,18 @@ pub enum QuestProofEndpoints {
     InitSubmission(UserUlid, QuestUlid),
     // PATCH /proofs/{proof_id}/confirm
     ConfirmSubmission(QuestProofUlid),
+    // POST /proofs/{proof_id}/upload-urls
+    RefreshUploadUrls(QuestProofUlid),
     // GET /proofs/{proof_id}
     GetDetails(QuestProofUlid),
+    // DELETE /proofs/{proof_id}
+    CancelSubmission(QuestProofUlid),
     // GET /users/{user_id}/feed
     GetFeed(UserUlid),
+    // GET /users/{user_id}/feed/for-you
+    GetRankedFeed(UserUlid),
+    // GET /users/{user_id}/feed/following
+    GetFollowingFeed(UserUlid),
     // POST /proofs/{proof_id}/likes/{user_id}
     BeliefProof(QuestProofUlid, UserUlid),
 
@@ -26,10 +34,18 @@ impl API for QuestProofEndpoints {
 
             Self::ConfirmSubmission(proof_id) => format!("/proofs/{proof_id}/confirm"),
 
+            Self::RefreshUploadUrls(proof_id) => format!("/proofs/{proof_id}/upload-urls"),
+
             Self::GetDetails(proof_id) => format!("/proofs/{proof_id}"),
 
+            Self::CancelSubmission(proof_id) => format!("/proofs/{proof_id}"),
+
             Self::GetFeed(user_id) => format!("/users/{user_id}/feed"),
 
+            Self::GetRankedFeed(user_id) => format!("/users/{user_id}/feed/for-you"),
+
+            Self::GetFollowingFeed(user_id) => format!("/users/{user_id}/feed/following"),
+
             Self::BeliefProof(proof_id, user_id) => {
                 format!("/proofs/{proof_id}/likes/{user_id}")
             }
@@ -44,8 +60,12 @@ impl API for QuestProofEndpoints {
         match self {
             Self::InitSubmission(_, _) => "/users/{user_id}/quests/{quest_id}/proofs",
             Self::ConfirmSubmission(_) => "/proofs/{proof_id}/confirm",
+            Self::RefreshUploadUrls(_) => "/proofs/{proof_id}/upload-urls",
             Self::GetDetails(_) => "/proofs/{proof_id}",
+            Self::CancelSubmission(_) => "/proofs/{proof_id}",
             Self::GetFeed(_) => "/users/{user_id}/feed",
+            Self::GetRankedFeed(_) => "/users/{user_id}/feed/for-you",
+            Self::GetFollowingFeed(_) => "/users/{user_id}/feed/following",
             Self::BeliefProof(_, _) => "/proofs/{proof_id}/likes/{user_id}",
             Self::GetUserJournal(_) => "/users/{user_id}/journal",
         }
@@ -0,0 +1,36 @@
+use crate::endpoints::API;
+
+pub enum ReportEndpoints {
+    // POST /reports
+    Create,
+    // GET /moderation/reports with PageQuery
+    GetQueue,
+    // POST /moderation/reports/resolve
+    Resolve,
+    // GET /moderation/photo-matches
+    GetPhotoMatches,
+}
+
+impl API for ReportEndpoints {
+    fn path(&self) -> String {
+        match self {
+            Self::Create => "/reports".to_string(),
+            Self::GetQueue => "/moderation/reports".to_string(),
+            Self::Resolve => "/moderation/reports/resolve".to_string(),
+            Self::GetPhotoMatches => "/moderation/photo-matches".to_string(),
+        }
+    }
+
+    fn template(&self) -> &'static str {
+        match self {
+            Self::Create => "/reports",
+            Self::GetQueue => "/moderation/reports",
+            Self::Resolve => "/moderation/reports/resolve",
+            Self::GetPhotoMatches => "/moderation/photo-matches",
+        }
+    }
+
+    fn is_auth_endpoint(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use validator::Validate;
+
+use crate::endpoints::API;
+
+// Results come newest first, `cursor` is the previous page's `next_cursor`
+#[derive(Debug, Default, Serialize, Deserialize, Validate, Type)]
+pub struct SearchQuery {
+    #[validate(length(min = 1, max = 100))]
+    pub q: String,
+    // Messages only: search a single lobby instead of all of the user's lobbies
+    pub lobby_id: Option<String>,
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+pub enum SearchEndpoints {
+    // GET /search/messages?q=X&lobby_id=Y
+    Messages,
+    // GET /search/proofs?q=X
+    Proofs,
+    // GET /search/lobbies?q=X
+    Lobbies,
+    // GET /search/quests?q=X
+    Quests,
+}
+
+impl API for SearchEndpoints {
+    fn path(&self) -> String {
+        self.template().to_string()
+    }
+
+    fn template(&self) -> &'static str {
+        match self {
+            Self::Messages => "/search/messages",
+            Self::Proofs => "/search/proofs",
+            Self::Lobbies => "/search/lobbies",
+            Self::Quests => "/search/quests",
+        }
+    }
+
+    fn is_auth_endpoint(&self) -> bool {
+        false
+    }
+}
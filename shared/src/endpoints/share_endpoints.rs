@@ -0,0 +1,32 @@
+use crate::endpoints::{API, QuestProofUlid};
+
+pub enum ShareEndpoints {
+    // POST /proofs/{proof_id}/share
+    CreateShare(QuestProofUlid),
+    // DELETE /proofs/{proof_id}/share
+    RevokeShare(QuestProofUlid),
+    // GET /share/{token}, public HTML page
+    PublicPage(String),
+}
+
+impl API for ShareEndpoints {
+    fn path(&self) -> String {
+        match self {
+            Self::CreateShare(proof_id) | Self::RevokeShare(proof_id) => {
+                format!("/proofs/{proof_id}/share")
+            }
+            Self::PublicPage(token) => format!("/share/{token}"),
+        }
+    }
+
+    fn template(&self) -> &'static str {
+        match self {
+            Self::CreateShare(_) | Self::RevokeShare(_) => "/proofs/{proof_id}/share",
+            Self::PublicPage(_) => "/share/{token}",
+        }
+    }
+
+    fn is_auth_endpoint(&self) -> bool {
+        false
+    }
+}
@@ -3,24 +3,32 @@ use crate::endpoints::API;
 pub enum UserEndpoints {
     RegisterUserEndpoint,
     LoginUserEndpoint,
+    // GET /users/me
+    GetMyProfile,
+    // PATCH /users/me
+    UpdateMyProfile,
+    // POST /users/me/avatar
+    GetAvatarUploadUrl,
+    // POST /users/me/avatar/confirm
+    ConfirmAvatarUpload,
 }
 
 impl API for UserEndpoints {
     fn path(&self) -> String {
-        match self {
-            Self::RegisterUserEndpoint => "/register".to_string(),
-            Self::LoginUserEndpoint => "/login".to_string(),
-        }
+        self.template().to_string()
     }
 
     fn template(&self) -> &'static str {
         match self {
             Self::RegisterUserEndpoint => "/register",
             Self::LoginUserEndpoint => "/login",
+            Self::GetMyProfile | Self::UpdateMyProfile => "/users/me",
+            Self::GetAvatarUploadUrl => "/users/me/avatar",
+            Self::ConfirmAvatarUpload => "/users/me/avatar/confirm",
         }
     }
 
     fn is_auth_endpoint(&self) -> bool {
-        true
+        matches!(self, Self::RegisterUserEndpoint | Self::LoginUserEndpoint)
     }
 }
@@ -10,6 +10,9 @@ pub enum AuthError {
     #[error("User already exists")]
     UserAlreadyExists,
 
+    #[error("Username is already taken")]
+    UsernameTaken,
+
     #[error("Validation failed: {0}")]
     ValidationError(String),
 
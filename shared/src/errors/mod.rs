@@ -50,6 +50,9 @@ pub enum AppError {
 
     #[error("Not found")]
     NotFound,
+
+    #[error("Too many requests")]
+    RateLimited,
 }
 
 impl AppError {
@@ -65,6 +68,11 @@ impl AppError {
                 ErrorCode::UserExists,
                 self.to_string(),
             ),
+            Self::Auth(AuthError::UsernameTaken) => (
+                StatusCode::CONFLICT,
+                ErrorCode::UsernameTaken,
+                self.to_string(),
+            ),
             Self::Auth(AuthError::ValidationError(msg)) => (
                 StatusCode::BAD_REQUEST,
                 ErrorCode::ValidationError,
@@ -81,6 +89,11 @@ impl AppError {
                 ErrorCode::NotFound,
                 "Resource not found".to_string(),
             ),
+            Self::RateLimited => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorCode::RateLimited,
+                "Too many requests, try again later".to_string(),
+            ),
             Self::Database(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 ErrorCode::DatabaseError,
@@ -110,6 +123,7 @@ impl From<ErrorBody> for AppError {
         match error_body.error_type {
             ErrorCode::AuthInvalid => Self::Auth(AuthError::InvalidCredentials),
             ErrorCode::UserExists => Self::Auth(AuthError::UserAlreadyExists),
+            ErrorCode::UsernameTaken => Self::Auth(AuthError::UsernameTaken),
             ErrorCode::ValidationError => {
                 Self::Auth(AuthError::ValidationError(error_body.message))
             }
@@ -117,6 +131,7 @@ impl From<ErrorBody> for AppError {
             ErrorCode::NotFound => Self::NotFound,
             ErrorCode::DatabaseError => Self::Database(sea_orm::DbErr::Custom(error_body.message)),
             ErrorCode::ServerError => Self::Server(error_body.message),
+            ErrorCode::RateLimited => Self::RateLimited,
         }
     }
 }
@@ -191,11 +206,13 @@ impl From<AppError> for ErrorBody {
 pub enum ErrorCode {
     AuthInvalid,
     UserExists,
+    UsernameTaken,
     ValidationError,
     NotFound,
     DatabaseError,
     ServerError,
     CustomError,
+    RateLimited,
 }
 
 pub type AppResult<T> = Result<T, AppError>;
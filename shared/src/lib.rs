@@ -1,4 +1,5 @@
 pub mod endpoints;
 pub mod errors;
 pub mod models;
+pub mod moderation;
 pub mod utils;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FollowCountsResponse {
+    pub followers: u64,
+    pub following: u64,
+    // Whether the caller follows this user
+    pub is_following: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FollowUserDto {
+    pub ulid: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub level: u32,
+    pub followed_at: chrono::DateTime<chrono::Utc>,
+}
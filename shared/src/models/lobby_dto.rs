@@ -5,6 +5,37 @@ use validator::Validate;
 
 use sea_orm::prelude::StringLen;
 
+use crate::models::message_dto::LobbyUserDto;
+
+// Who can find and enter a lobby. Non-public lobbies are left out of the feed and discovery
+// for everyone but their members.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Type,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+#[serde(rename_all = "lowercase")]
+pub enum LobbyVisibility {
+    #[default]
+    #[sea_orm(string_value = "public")]
+    Public,
+    // Anyone with the lobby's link can join
+    #[sea_orm(string_value = "unlisted")]
+    Unlisted,
+    // Only through an invite or an approved join request
+    #[sea_orm(string_value = "private")]
+    Private,
+}
+
 #[derive(Debug, Serialize, Deserialize, Type)]
 pub struct LobbyDto {
     pub ulid: String,
@@ -12,6 +43,7 @@ pub struct LobbyDto {
     pub topic: String,
     pub description: Option<String>,
     pub owner_id: String,
+    pub visibility: LobbyVisibility,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -19,6 +51,8 @@ pub struct LobbyDto {
 pub struct LobbyFeedItem {
     pub lobby: LobbyDto,
     pub is_member: bool,
+    // Always 0 for lobbies the user isn't in
+    pub unread_count: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
@@ -37,7 +71,8 @@ pub struct CreateLobbyRequest {
     #[validate(length(max = 500))]
     pub description: Option<String>,
 
-    pub owner_id: String,
+    #[serde(default)]
+    pub visibility: LobbyVisibility,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
@@ -48,6 +83,102 @@ pub struct LobbyMemberDto {
     pub joined_at: chrono::DateTime<chrono::Utc>,
 }
 
+// Only admins and the owner can hand out roles, and only roles below their own
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SetRoleRequest {
+    pub role: Role,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Type)]
+pub struct BanMemberRequest {
+    // Up to a year, no duration bans for good
+    #[validate(range(min = 1, max = 8760))]
+    pub duration_hours: Option<u32>,
+
+    #[validate(length(max = 200))]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct TransferOwnershipRequest {
+    // Must already be a member of the lobby
+    pub user_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct LobbyBanDto {
+    pub lobby_id: String,
+    pub user_id: String,
+    pub banned_by: String,
+    pub reason: Option<String>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Admins only
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SetVisibilityRequest {
+    pub visibility: LobbyVisibility,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Validate, Type)]
+pub struct CreateInviteRequest {
+    // Up to 30 days, no expiry keeps the invite until it is revoked
+    #[validate(range(min = 1, max = 720))]
+    pub expires_in_hours: Option<u32>,
+
+    #[validate(range(min = 1, max = 1000))]
+    pub max_uses: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct LobbyInviteDto {
+    pub code: String,
+    pub lobby_id: String,
+    pub created_by: String,
+    pub max_uses: Option<u32>,
+    pub uses: u32,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// What an invite link shows before it is accepted
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct InvitePreview {
+    pub code: String,
+    pub lobby: LobbyDto,
+    pub members_count: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Validate, Type)]
+pub struct CreateJoinRequest {
+    #[validate(length(max = 200))]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct JoinRequestDto {
+    pub lobby_id: String,
+    pub user: LobbyUserDto,
+    pub message: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct DiscoveredLobby {
+    pub lobby: LobbyDto,
+    pub members_count: u32,
+    pub is_member: bool,
+    // Time of the latest message, None while nobody wrote in the lobby
+    pub last_activity_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct TopicCount {
+    pub topic: String,
+    pub lobbies_count: u32,
+}
+
 #[derive(Debug, Serialize, Deserialize, Type)]
 pub struct LobbyDetailsResponse {
     pub lobby: LobbyDto,
@@ -1,22 +1,150 @@
 use sea_orm::prelude::DateTimeUtc;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
 
-#[derive(Serialize, Clone, Debug, Type)]
+use crate::{errors::ErrorBody, models::lobby_dto::Role, utils::monotonic_ulid::next_ulid};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 #[serde(tag = "type", content = "data")]
 pub enum LobbyEvent {
     MessageCreated(MessageDto),
+    // Carries the whole message, mentions may have changed with the text
+    MessageEdited(MessageDto),
+    // The message stays in the history as a placeholder, so replies to it keep their parent
     MessageDeleted { id: String },
-    UserJoined { user_id: String }, // TODO: Make UserDTO for this
+    ReactionAdded(ReactionChange),
+    ReactionRemoved(ReactionChange),
+    // UserJoined and UserLeft follow who has the lobby open, the Member events follow who
+    // belongs to it. A removed member's socket is closed right after their event.
+    UserJoined { user: LobbyUserDto },
     UserLeft { user_id: String },
+    MemberJoined { user_id: String },
+    MemberLeft { user_id: String },
+    MemberKicked { user_id: String, by: String },
+    MemberBanned(MemberBan),
+    RoleChanged { user_id: String, role: Role },
+    OwnerChanged { owner_id: String },
     Typing { user_id: String },
+    // `user_id` has read everything up to `message_id`
+    ReadReceipt { user_id: String, message_id: String },
+    // Sent only to one socket: events after `since` were dropped, fetch the missed messages
+    // through the sync endpoint and reload anything else shown for the lobby. Sync only returns
+    // messages newer than `since`, in their current state. MessageEdited, MessageDeleted and
+    // reaction events for older messages are not replayed, so reload those pages as well.
+    Resync { since: Option<String> },
+}
+
+// What actually goes over the socket. `id` sorts in the order events happened, for
+// MessageCreated it is the message's own ULID.
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct LobbyEventFrame {
+    pub id: String,
+    #[serde(flatten)]
+    pub event: LobbyEvent,
+}
+
+impl From<LobbyEvent> for LobbyEventFrame {
+    fn from(event: LobbyEvent) -> Self {
+        let id = match &event {
+            LobbyEvent::MessageCreated(message) => message.ulid.clone(),
+            _ => next_ulid().to_string(),
+        };
+        Self { id, event }
+    }
+}
+
+impl LobbyEvent {
+    // The member this event took out of the lobby
+    pub fn removed_member(&self) -> Option<&str> {
+        match self {
+            Self::MemberLeft { user_id } | Self::MemberKicked { user_id, .. } => Some(user_id),
+            Self::MemberBanned(ban) => Some(&ban.user_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct MemberBan {
+    pub user_id: String,
+    pub by: String,
+    // None for a ban without an end
+    pub expires_at: Option<DateTimeUtc>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct ReactionChange {
+    pub message_id: String,
+    pub user_id: String,
+    pub emoji: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct LobbyUserDto {
+    pub ulid: String,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub level: u32,
 }
 
-#[derive(Serialize, Clone, Debug, Type)]
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
 pub struct MessageDto {
     pub ulid: String,
     pub author_id: String,
     pub author_name: String,
+    // Empty once the message is deleted
     pub content: String,
     pub created_at: DateTimeUtc,
+    pub edited_at: Option<DateTimeUtc>,
+    pub reply_to_id: Option<String>,
+    pub deleted: bool,
+    // IDs of the mentioned lobby members
+    pub mentions: Vec<String>,
+    pub reactions: Vec<ReactionDto>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+pub struct ReactionDto {
+    pub emoji: String,
+    // In the order the reactions were added
+    pub user_ids: Vec<String>,
+}
+
+// Frames a client sends over the lobby socket
+#[derive(Serialize, Deserialize, Clone, Debug, Type)]
+#[serde(tag = "type", content = "data")]
+pub enum LobbyCommand {
+    // `client_id` comes back in the reply, so the client can match it to its pending message
+    SendMessage {
+        content: String,
+        client_id: Option<String>,
+        reply_to: Option<String>,
+    },
+    Typing,
+    // The newest message the client has received
+    Ack {
+        message_id: String,
+    },
+}
+
+// Sent only to the socket that issued the command, everyone else gets the `LobbyEvent`
+#[derive(Serialize, Deserialize, Debug, Type)]
+#[serde(tag = "type", content = "data")]
+pub enum CommandReply {
+    Sent {
+        client_id: Option<String>,
+        message_id: String,
+    },
+    Rejected {
+        client_id: Option<String>,
+        error: ErrorBody,
+    },
+}
+
+// Browsers can't set headers on a WebSocket handshake, so the access token travels as the
+// second subprotocol: `new WebSocket(ticket.url, ticket.protocols)`
+#[derive(Serialize, Deserialize, Debug, Type)]
+pub struct LobbySocketTicket {
+    pub url: String,
+    pub protocols: Vec<String>,
 }
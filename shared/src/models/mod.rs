@@ -1,16 +1,60 @@
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use specta::Type;
+use validator::Validate;
 
+pub mod follow_dto;
 pub mod lobby_dto;
 pub mod message_dto;
 pub mod quest_dto;
 pub mod quest_proof_dto;
 pub mod refresh_token_dto;
+pub mod report_dto;
+pub mod search_dto;
 pub mod user_dto;
 pub mod user_quest_status_dto;
 
+// Query string for cursor-paginated lists; pass the previous page's next_cursor to continue
+#[derive(Debug, Default, Serialize, Deserialize, Validate, Type)]
+pub struct PageQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+}
+
+// Presigned POST: send every field as multipart form data, followed by the file as "file".
+// The storage rejects uploads over the size limit or with a different Content-Type.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct PresignedUpload {
+    pub url: String,
+    pub fields: BTreeMap<String, String>,
+    pub max_bytes: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize, Type)]
-pub struct Pagination {
-    pub limit: u32,
-    pub offset: u32,
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub has_more: bool,
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    // Expects up to limit + 1 rows, the extra row only tells that another page exists.
+    pub fn from_rows(mut rows: Vec<T>, limit: u64, cursor_of: impl Fn(&T) -> String) -> Self {
+        let has_more = rows.len() as u64 > limit;
+        rows.truncate(limit as usize);
+
+        let next_cursor = if has_more {
+            rows.last().map(cursor_of)
+        } else {
+            None
+        };
+
+        Self {
+            items: rows,
+            has_more,
+            next_cursor,
+        }
+    }
 }
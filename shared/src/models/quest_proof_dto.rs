@@ -1,7 +1,41 @@
+use sea_orm::prelude::StringLen;
+use sea_orm::{DeriveActiveEnum, EnumIter};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use validator::Validate;
 
+use crate::models::PresignedUpload;
+
+// Who besides the author can see a proof. Private proofs are only allowed on quests with
+// AUTOMATIC validation, since nobody else could ever believe in them.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    Type,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+#[serde(rename_all = "lowercase")]
+pub enum ProofVisibility {
+    #[default]
+    #[sea_orm(string_value = "public")]
+    Public,
+    #[sea_orm(string_value = "followers")]
+    Followers,
+    // Users sharing at least one lobby with the author
+    #[sea_orm(string_value = "lobby")]
+    Lobby,
+    #[sea_orm(string_value = "private")]
+    Private,
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate, Type)]
 pub struct SubmitProofRequest {
     pub proof_text: Option<String>,
@@ -11,14 +45,17 @@ pub struct SubmitProofRequest {
 
     #[validate(range(min = 0, max = 3))]
     pub voice_count: u32,
+
+    #[serde(default)]
+    pub visibility: ProofVisibility,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
 pub struct SubmitProofResponse {
     pub proof_ulid: String,
     pub status: String,
-    pub photo_upload_urls: Vec<String>,
-    pub voice_upload_urls: Vec<String>,
+    pub photo_uploads: Vec<PresignedUpload>,
+    pub voice_uploads: Vec<PresignedUpload>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
@@ -37,19 +74,22 @@ pub struct ProofDetailsResponse {
     pub voice_urls: Vec<String>,
     pub beliefs_count: u32,
     pub is_believed: bool,
+    pub visibility: ProofVisibility,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Type)]
-pub struct ProofFeedResponse {
-    pub items: Vec<ProofDetailsResponse>,
-    pub has_more: bool,
-    pub next_offset: u32,
+pub struct ProofShareResponse {
+    pub token: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Deserialize, Validate, Type)]
-pub struct PaginationQuery {
-    #[validate(range(min = 1, max = 100))]
-    pub limit: Option<u64>,
-    pub offset: Option<u64>,
+// Chronological is served by GetFeed, ForYou by GetRankedFeed, Following by GetFollowingFeed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedMode {
+    #[default]
+    Chronological,
+    ForYou,
+    Following,
 }
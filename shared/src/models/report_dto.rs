@@ -0,0 +1,138 @@
+use sea_orm::prelude::StringLen;
+use sea_orm::{DeriveActiveEnum, EnumIter};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use validator::Validate;
+
+use crate::utils::ulid_validation::validate_ulid;
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, EnumIter, DeriveActiveEnum, Serialize, Deserialize, Type,
+)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+#[serde(rename_all = "lowercase")]
+pub enum ReportTargetType {
+    #[sea_orm(string_value = "proof")]
+    Proof,
+    #[sea_orm(string_value = "message")]
+    Message,
+    #[sea_orm(string_value = "user")]
+    User,
+    #[sea_orm(string_value = "lobby")]
+    Lobby,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, Type)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(16))")]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReportReason {
+    #[sea_orm(string_value = "SPAM")]
+    Spam,
+    #[sea_orm(string_value = "HARASSMENT")]
+    Harassment,
+    #[sea_orm(string_value = "HATE_SPEECH")]
+    HateSpeech,
+    #[sea_orm(string_value = "SEXUAL_CONTENT")]
+    SexualContent,
+    #[sea_orm(string_value = "VIOLENCE")]
+    Violence,
+    #[sea_orm(string_value = "SELF_HARM")]
+    SelfHarm,
+    #[sea_orm(string_value = "IMPERSONATION")]
+    Impersonation,
+    #[sea_orm(string_value = "OTHER")]
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, Type)]
+#[sea_orm(rs_type = "String", db_type = "String(StringLen::N(10))")]
+#[serde(rename_all = "lowercase")]
+pub enum ReportStatus {
+    // Waiting for a moderator; counts towards the auto-hide threshold
+    #[sea_orm(string_value = "open")]
+    Open,
+    // Moderator decided the content is fine, the target becomes visible again
+    #[sea_orm(string_value = "dismissed")]
+    Dismissed,
+    // Moderator confirmed the report, the target stays hidden
+    #[sea_orm(string_value = "actioned")]
+    Actioned,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportResolution {
+    Dismiss,
+    Remove,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Type)]
+pub struct CreateReportRequest {
+    pub target_type: ReportTargetType,
+
+    #[validate(custom(function = "validate_ulid"))]
+    pub target_id: String,
+
+    pub reason: ReportReason,
+
+    #[validate(length(max = 1000))]
+    pub details: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ReportDto {
+    pub ulid: String,
+    pub reporter_id: String,
+    pub target_type: ReportTargetType,
+    pub target_id: String,
+    pub reason: ReportReason,
+    pub details: Option<String>,
+    pub status: ReportStatus,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub resolved_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub resolved_by: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct ModerationQueueItem {
+    pub target_type: ReportTargetType,
+    pub target_id: String,
+    pub open_reports: u32,
+    pub is_hidden: bool,
+    pub reports: Vec<ReportDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, Type)]
+pub struct ResolveReportsRequest {
+    pub target_type: ReportTargetType,
+
+    #[validate(custom(function = "validate_ulid"))]
+    pub target_id: String,
+
+    pub resolution: ReportResolution,
+}
+
+// Which earlier photo a newly uploaded one was matched against
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum PhotoMatchKind {
+    // Both photos come from the same author
+    Reused,
+    // The earlier photo belongs to another user
+    Copied,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct PhotoMatchDto {
+    pub ulid: String,
+    pub kind: PhotoMatchKind,
+    // Bits that differ between the two perceptual hashes, 0 means visually identical
+    pub distance: u32,
+    pub proof_id: String,
+    pub user_id: String,
+    pub photo_url: Option<String>,
+    pub matched_proof_id: String,
+    pub matched_user_id: String,
+    pub matched_photo_url: Option<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
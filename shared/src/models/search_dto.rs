@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+// Snippets come in parts instead of markup, user text is never rendered as HTML
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct SnippetPart {
+    pub text: String,
+    // Part of the text that matched the query
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SearchHit {
+    // ULID of the message, proof, lobby or quest
+    pub ulid: String,
+    // The lobby of a message or of a lobby quest
+    pub lobby_id: Option<String>,
+    pub snippet: Vec<SnippetPart>,
+}
@@ -46,3 +46,31 @@ pub struct LoginResponse {
     pub level: u32,
     pub avatar_url: Option<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ProfileResponse {
+    pub ulid: String,
+    pub username: String,
+    pub email: String,
+    pub bio: Option<String>,
+    // Signed download URL, never the raw storage key
+    pub avatar_url: Option<String>,
+    pub level: u32,
+    pub xp_balance: i32,
+    pub total_xp_accumulated: i32,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+// Fields left out stay unchanged
+#[derive(Debug, Serialize, Deserialize, Validate, Type)]
+pub struct UpdateProfileRequest {
+    #[validate(length(
+        min = 5,
+        max = 20,
+        message = "Username must be between 5 and 20 characters long",
+    ))]
+    pub username: Option<String>,
+
+    #[validate(length(max = 300, message = "Bio must be at most 300 characters long"))]
+    pub bio: Option<String>,
+}
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::{AppError, AppResult};
+
+pub mod normalize;
+pub mod wordlists;
+
+use normalize::{Token, normalize_word, squeeze, tokenize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModerationAction {
+    // Reject the whole text
+    Block,
+    // Store the text with the matched word replaced by asterisks
+    Mask,
+    // Store the text as is and queue it for a moderator
+    Flag,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModerationRule {
+    pub term: String,
+    pub action: ModerationAction,
+    // Also match words starting with the term, for inflected languages like Serbian and Hungarian
+    #[serde(default)]
+    pub prefix: bool,
+}
+
+impl ModerationRule {
+    pub fn new(term: &str, action: ModerationAction, prefix: bool) -> Self {
+        Self {
+            term: term.to_owned(),
+            action,
+            prefix,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Wordlist {
+    pub locale: String,
+    pub rules: Vec<ModerationRule>,
+}
+
+#[derive(Debug)]
+struct CompiledRule {
+    term: String,
+    action: ModerationAction,
+    prefix: bool,
+}
+
+impl CompiledRule {
+    fn matches(&self, spellings: &[String]) -> bool {
+        spellings.iter().any(|spelling| {
+            if self.prefix {
+                spelling.starts_with(&self.term)
+            } else {
+                *spelling == self.term
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModerationOutcome {
+    // The text to store, with masked words already replaced
+    pub text: String,
+    pub blocked: Vec<String>,
+    pub masked: Vec<String>,
+    pub flagged: Vec<String>,
+}
+
+impl ModerationOutcome {
+    pub fn is_blocked(&self) -> bool {
+        !self.blocked.is_empty()
+    }
+
+    pub fn needs_review(&self) -> bool {
+        !self.flagged.is_empty()
+    }
+}
+
+// Every enabled wordlist applies to every text, users freely mix languages in one message.
+// Cheap to clone, the compiled rules are shared.
+#[derive(Debug, Clone, Default)]
+pub struct TextFilter {
+    rules: Arc<[CompiledRule]>,
+}
+
+impl TextFilter {
+    pub fn new(wordlists: &[Wordlist]) -> Self {
+        let rules = wordlists
+            .iter()
+            .flat_map(|list| &list.rules)
+            .map(|rule| CompiledRule {
+                term: normalize_word(&rule.term),
+                action: rule.action,
+                prefix: rule.prefix,
+            })
+            .filter(|rule| !rule.term.is_empty())
+            .collect();
+
+        Self { rules }
+    }
+
+    // The most severe rule wins when several match the same word
+    fn action_for(&self, token: &Token) -> Option<ModerationAction> {
+        // Stretched spellings: "fuuuck" and "basssza" are compared as "fuck" and "bassza"
+        let spellings = [
+            token.text.clone(),
+            squeeze(&token.text, 2),
+            squeeze(&token.text, 1),
+        ];
+
+        let mut action = None;
+        for rule in self.rules.iter().filter(|rule| rule.matches(&spellings)) {
+            action = match (action, rule.action) {
+                (Some(ModerationAction::Block), _) | (_, ModerationAction::Block) => {
+                    Some(ModerationAction::Block)
+                }
+                (Some(ModerationAction::Mask), _) | (_, ModerationAction::Mask) => {
+                    Some(ModerationAction::Mask)
+                }
+                _ => Some(ModerationAction::Flag),
+            };
+        }
+        action
+    }
+
+    pub fn check(&self, text: &str) -> ModerationOutcome {
+        let mut outcome = ModerationOutcome {
+            text: String::with_capacity(text.len()),
+            blocked: Vec::new(),
+            masked: Vec::new(),
+            flagged: Vec::new(),
+        };
+        let mut copied_up_to = 0;
+
+        for token in tokenize(text) {
+            let original = &text[token.start..token.end];
+            match self.action_for(&token) {
+                Some(ModerationAction::Block) => outcome.blocked.push(original.to_owned()),
+                Some(ModerationAction::Flag) => outcome.flagged.push(original.to_owned()),
+                Some(ModerationAction::Mask) => {
+                    outcome.text.push_str(&text[copied_up_to..token.start]);
+                    outcome.text.extend(
+                        original
+                            .chars()
+                            .map(|c| if c.is_whitespace() { c } else { '*' }),
+                    );
+                    copied_up_to = token.end;
+                    outcome.masked.push(original.to_owned());
+                }
+                None => {}
+            }
+        }
+        outcome.text.push_str(&text[copied_up_to..]);
+
+        outcome
+    }
+
+    // `check` for write paths: blocked text becomes an error, anything else is let through
+    pub fn moderate(&self, text: &str) -> AppResult<ModerationOutcome> {
+        let outcome = self.check(text);
+        if outcome.is_blocked() {
+            return Err(AppError::Custom(
+                "Text contains language that is not allowed".into(),
+            ));
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter() -> TextFilter {
+        TextFilter::new(&[Wordlist {
+            locale: "test".into(),
+            rules: vec![
+                ModerationRule::new("fuck", ModerationAction::Mask, true),
+                ModerationRule::new("kurva", ModerationAction::Mask, false),
+                ModerationRule::new("peder", ModerationAction::Block, true),
+                ModerationRule::new("idiot", ModerationAction::Flag, false),
+            ],
+        }])
+    }
+
+    #[test]
+    fn test_clean_text_is_untouched() {
+        let outcome = filter().check("Talked to a stranger today, felt great");
+
+        assert_eq!(outcome.text, "Talked to a stranger today, felt great");
+        assert!(!outcome.is_blocked());
+        assert!(!outcome.needs_review());
+        assert!(outcome.masked.is_empty());
+    }
+
+    #[test]
+    fn test_masks_obfuscated_spellings() {
+        let outcome = filter().check("what the FUUUCKING hell, f.u.c.k and kůrva");
+
+        assert_eq!(outcome.text, "what the ********* hell, ******* and *****");
+        assert_eq!(outcome.masked.len(), 3);
+    }
+
+    #[test]
+    fn test_whole_word_rule_ignores_longer_words() {
+        let outcome = filter().check("idiotic kurvaaa");
+
+        assert!(!outcome.needs_review());
+        assert_eq!(outcome.text, "idiotic *******");
+    }
+
+    #[test]
+    fn test_block_wins_over_mask() {
+        let filter = TextFilter::new(&[Wordlist {
+            locale: "test".into(),
+            rules: vec![
+                ModerationRule::new("pederu", ModerationAction::Mask, false),
+                ModerationRule::new("peder", ModerationAction::Block, true),
+            ],
+        }]);
+
+        assert!(filter.check("пЕдеру").is_blocked());
+        assert!(filter.moderate("ti si pederu").is_err());
+    }
+
+    #[test]
+    fn test_flag_keeps_text() {
+        let outcome = filter().moderate("You 1d10t").unwrap();
+
+        assert_eq!(outcome.text, "You 1d10t");
+        assert_eq!(outcome.flagged, vec!["1d10t"]);
+    }
+}
@@ -0,0 +1,212 @@
+// Folds text to plain lowercase latin so a single wordlist entry catches its accented,
+// Cyrillic and leetspeak spellings alike.
+
+// A word of the normalized text and the byte range it came from in the original
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+// Runs of at least this many single characters ("f u c k", "f.u.c.k") are read as one word
+const SPELLED_OUT_MIN_LEN: usize = 3;
+
+fn fold_letter(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' | 'а' => "a",
+        'é' | 'è' | 'ê' | 'ë' | 'ě' | 'ē' | 'е' => "e",
+        'í' | 'ì' | 'î' | 'ï' | 'ī' | 'и' => "i",
+        'ó' | 'ò' | 'ô' | 'ö' | 'ő' | 'õ' | 'ø' | 'ō' | 'о' => "o",
+        'ú' | 'ù' | 'û' | 'ü' | 'ű' | 'ů' | 'ū' | 'у' => "u",
+        'ý' | 'ÿ' => "y",
+        'č' | 'ć' | 'ç' | 'ч' | 'ћ' | 'ц' => "c",
+        'š' | 'ś' | 'ш' | 'с' => "s",
+        'ž' | 'ź' | 'ż' | 'ж' | 'з' => "z",
+        'ñ' | 'ń' | 'ň' | 'н' => "n",
+        'ř' | 'р' => "r",
+        'ť' | 'т' => "t",
+        'ď' | 'д' => "d",
+        'ľ' | 'ł' | 'л' => "l",
+        'đ' | 'ђ' => "dj",
+        'ß' => "ss",
+        // Serbian Cyrillic without a diacritic counterpart
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ј' => "j",
+        'к' => "k",
+        'љ' => "lj",
+        'м' => "m",
+        'њ' => "nj",
+        'п' => "p",
+        'ф' => "f",
+        'х' => "h",
+        'џ' => "dz",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+fn fold_leet(c: char) -> Option<char> {
+    let folded = match c {
+        '0' => 'o',
+        '1' => 'i',
+        '3' => 'e',
+        '4' => 'a',
+        '5' => 's',
+        '7' => 't',
+        '8' => 'b',
+        '9' => 'g',
+        '@' => 'a',
+        '$' => 's',
+        _ => return None,
+    };
+    Some(folded)
+}
+
+// Appends the folded form of `c` to `out`, returns false for separators
+fn fold_char(c: char, out: &mut String) -> bool {
+    for lower in c.to_lowercase() {
+        if let Some(folded) = fold_letter(lower) {
+            out.push_str(folded);
+        } else if let Some(folded) = fold_leet(lower) {
+            out.push(folded);
+        } else if lower.is_alphanumeric() {
+            out.push(lower);
+        } else {
+            return false;
+        }
+    }
+    true
+}
+
+pub fn normalize_word(word: &str) -> String {
+    let mut out = String::with_capacity(word.len());
+    for c in word.chars() {
+        fold_char(c, &mut out);
+    }
+    out
+}
+
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+
+    for (index, c) in text.char_indices() {
+        let mut folded = String::new();
+        if fold_char(c, &mut folded) {
+            let token = current.get_or_insert_with(|| Token {
+                text: String::new(),
+                start: index,
+                end: index,
+            });
+            token.text.push_str(&folded);
+            token.end = index + c.len_utf8();
+        } else if let Some(token) = current.take() {
+            tokens.push(token);
+        }
+    }
+    tokens.extend(current);
+
+    join_spelled_out(tokens)
+}
+
+fn join_spelled_out(tokens: Vec<Token>) -> Vec<Token> {
+    let mut joined = Vec::with_capacity(tokens.len());
+    let mut run: Vec<Token> = Vec::new();
+
+    let flush = |run: &mut Vec<Token>, joined: &mut Vec<Token>| {
+        if run.len() >= SPELLED_OUT_MIN_LEN {
+            joined.push(Token {
+                text: run.iter().map(|t| t.text.as_str()).collect(),
+                start: run[0].start,
+                end: run[run.len() - 1].end,
+            });
+            run.clear();
+        } else {
+            joined.append(run);
+        }
+    };
+
+    for token in tokens {
+        if token.text.chars().count() == 1 {
+            run.push(token);
+        } else {
+            flush(&mut run, &mut joined);
+            joined.push(token);
+        }
+    }
+    flush(&mut run, &mut joined);
+
+    joined
+}
+
+// Shortens every run of a repeated letter to at most `max` letters ("fuuuck" -> "fuck")
+pub fn squeeze(word: &str, max: usize) -> String {
+    let mut out = String::with_capacity(word.len());
+    let mut last = None;
+    let mut repeats = 0;
+
+    for c in word.chars() {
+        if Some(c) == last {
+            repeats += 1;
+        } else {
+            last = Some(c);
+            repeats = 1;
+        }
+        if repeats <= max {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(text: &str) -> Vec<String> {
+        tokenize(text).into_iter().map(|t| t.text).collect()
+    }
+
+    #[test]
+    fn test_folds_diacritics_and_cyrillic() {
+        assert_eq!(normalize_word("Pička"), "picka");
+        assert_eq!(normalize_word("köcsög"), "kocsog");
+        assert_eq!(normalize_word("őrült"), "orult");
+        assert_eq!(normalize_word("ђубре"), "djubre");
+        assert_eq!(normalize_word("đubre"), "djubre");
+        assert_eq!(normalize_word("љубав"), "ljubav");
+    }
+
+    #[test]
+    fn test_folds_leetspeak() {
+        assert_eq!(normalize_word("sh1t"), "shit");
+        assert_eq!(normalize_word("@$$"), "ass");
+        assert_eq!(normalize_word("b1tch"), "bitch");
+    }
+
+    #[test]
+    fn test_tokenize_keeps_original_ranges() {
+        let text = "Hello, wörld!";
+        let tokens = tokenize(text);
+
+        assert_eq!(texts(text), vec!["hello", "world"]);
+        assert_eq!(&text[tokens[1].start..tokens[1].end], "wörld");
+    }
+
+    #[test]
+    fn test_tokenize_joins_spelled_out_words() {
+        assert_eq!(texts("you f u c k"), vec!["you", "fuck"]);
+        assert_eq!(texts("s.h.i.t happens"), vec!["shit", "happens"]);
+        assert_eq!(texts("a b nice"), vec!["a", "b", "nice"]);
+    }
+
+    #[test]
+    fn test_squeeze() {
+        assert_eq!(squeeze("fuuuuck", 1), "fuck");
+        assert_eq!(squeeze("basssza", 2), "bassza");
+        assert_eq!(squeeze("book", 2), "book");
+    }
+}
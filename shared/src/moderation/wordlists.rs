@@ -0,0 +1,131 @@
+use super::{ModerationAction, ModerationRule, Wordlist};
+
+// Built-in lists are a small baseline. Deployments are expected to replace them with their
+// own, see `ModerationConfig` on the server.
+pub const BUILTIN_LOCALES: &[&str] = &["en", "sr", "hu"];
+
+// Terms are written in plain Latin, Cyrillic and accented spellings fold onto them
+const EN: &[(&str, ModerationAction, bool)] = &[
+    ("fuck", ModerationAction::Mask, true),
+    ("shit", ModerationAction::Mask, true),
+    ("bitch", ModerationAction::Mask, true),
+    ("cunt", ModerationAction::Mask, true),
+    ("asshole", ModerationAction::Mask, true),
+    ("dick", ModerationAction::Mask, false),
+    ("bastard", ModerationAction::Mask, true),
+    ("faggot", ModerationAction::Block, true),
+    // Whole words only, a prefix would also catch "retardant" and "retardation"
+    ("retard", ModerationAction::Block, false),
+    ("retards", ModerationAction::Block, false),
+    ("retarded", ModerationAction::Block, false),
+    ("kys", ModerationAction::Block, false),
+    ("idiot", ModerationAction::Flag, false),
+    ("loser", ModerationAction::Flag, false),
+    ("suicide", ModerationAction::Flag, false),
+];
+
+const SR: &[(&str, ModerationAction, bool)] = &[
+    ("jebem", ModerationAction::Mask, true),
+    ("jebote", ModerationAction::Mask, false),
+    ("jebi", ModerationAction::Mask, true),
+    ("picka", ModerationAction::Mask, false),
+    ("picku", ModerationAction::Mask, false),
+    ("picke", ModerationAction::Mask, false),
+    ("kurac", ModerationAction::Mask, false),
+    ("kurc", ModerationAction::Mask, true),
+    ("kurv", ModerationAction::Mask, true),
+    ("govn", ModerationAction::Mask, true),
+    ("sranj", ModerationAction::Mask, true),
+    ("peder", ModerationAction::Block, true),
+    ("debil", ModerationAction::Flag, true),
+    ("kreten", ModerationAction::Flag, true),
+    ("glupa", ModerationAction::Flag, true),
+];
+
+const HU: &[(&str, ModerationAction, bool)] = &[
+    ("bassz", ModerationAction::Mask, true),
+    ("baszd", ModerationAction::Mask, true),
+    ("baszom", ModerationAction::Mask, true),
+    ("kurva", ModerationAction::Mask, true),
+    ("geci", ModerationAction::Mask, true),
+    ("picsa", ModerationAction::Mask, true),
+    ("fasz", ModerationAction::Mask, false),
+    ("faszom", ModerationAction::Mask, true),
+    ("faszfej", ModerationAction::Mask, true),
+    ("szar", ModerationAction::Mask, false),
+    ("szaros", ModerationAction::Mask, false),
+    ("köcsög", ModerationAction::Mask, true),
+    ("buzi", ModerationAction::Block, true),
+    ("nyomorék", ModerationAction::Flag, true),
+    ("hülye", ModerationAction::Flag, true),
+    ("anyád", ModerationAction::Flag, false),
+];
+
+pub fn builtin(locale: &str) -> Option<Wordlist> {
+    let terms = match locale {
+        "en" => EN,
+        "sr" => SR,
+        "hu" => HU,
+        _ => return None,
+    };
+
+    Some(Wordlist {
+        locale: locale.to_owned(),
+        rules: terms
+            .iter()
+            .map(|&(term, action, prefix)| ModerationRule::new(term, action, prefix))
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moderation::TextFilter;
+
+    use super::*;
+
+    fn filter() -> TextFilter {
+        let lists: Vec<Wordlist> = BUILTIN_LOCALES
+            .iter()
+            .filter_map(|locale| builtin(locale))
+            .collect();
+        TextFilter::new(&lists)
+    }
+
+    #[test]
+    fn test_every_builtin_locale_has_rules() {
+        for locale in BUILTIN_LOCALES {
+            assert!(!builtin(locale).unwrap().rules.is_empty());
+        }
+        assert!(builtin("xx").is_none());
+    }
+
+    #[test]
+    fn test_serbian_latin_and_cyrillic() {
+        let filter = filter();
+
+        assert_eq!(filter.check("ma jebote").text, "ma ******");
+        assert_eq!(filter.check("ма јеботе").text, "ма ******");
+        assert!(filter.check("ти си кретен").needs_review());
+    }
+
+    #[test]
+    fn test_hungarian_inflections() {
+        let filter = filter();
+
+        assert_eq!(filter.check("a picsába").text, "a *******");
+        assert!(filter.check("Hülyeség").needs_review());
+        // Whole-word rules leave innocent words alone
+        assert_eq!(filter.check("szarvas és faszén").text, "szarvas és faszén");
+    }
+
+    #[test]
+    fn test_english_slur_spares_longer_words() {
+        let filter = filter();
+
+        assert!(filter.check("what a retard").is_blocked());
+        assert!(filter.check("so r3tarded").is_blocked());
+        assert!(!filter.check("a fire retardant coating").is_blocked());
+        assert!(!filter.check("growth retardation").is_blocked());
+    }
+}
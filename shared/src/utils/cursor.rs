@@ -0,0 +1,83 @@
+use chrono::{DateTime, Utc};
+use sea_orm::{ColumnTrait, Condition};
+
+use crate::errors::{AppError, AppResult};
+
+// Position in a list ordered by (created_at DESC, ulid DESC). Clients only see the encoded form
+// and must pass it back untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    pub created_at: DateTime<Utc>,
+    pub ulid: String,
+}
+
+impl Cursor {
+    pub fn new(created_at: DateTime<Utc>, ulid: &str) -> Self {
+        Self {
+            created_at,
+            ulid: ulid.to_owned(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        format!(
+            "{:x}.{:x}-{}",
+            self.created_at.timestamp(),
+            self.created_at.timestamp_subsec_nanos(),
+            self.ulid
+        )
+    }
+
+    pub fn decode(raw: &str) -> AppResult<Self> {
+        let invalid = || AppError::Custom("Invalid cursor".into());
+
+        let (timestamp, ulid) = raw.split_once('-').ok_or_else(invalid)?;
+        let (secs, nanos) = timestamp.split_once('.').ok_or_else(invalid)?;
+
+        let secs = i64::from_str_radix(secs, 16).map_err(|_| invalid())?;
+        let nanos = u32::from_str_radix(nanos, 16).map_err(|_| invalid())?;
+        let created_at = DateTime::from_timestamp(secs, nanos).ok_or_else(invalid)?;
+
+        ulid::Ulid::from_string(ulid).map_err(|_| invalid())?;
+
+        Ok(Self::new(created_at, ulid))
+    }
+
+    pub fn decode_opt(raw: Option<&str>) -> AppResult<Option<Self>> {
+        raw.map(Self::decode).transpose()
+    }
+
+    // Rows strictly after this cursor in (created_at DESC, ulid DESC) order
+    pub fn after<C>(&self, created_at: C, ulid: C) -> Condition
+    where
+        C: ColumnTrait,
+    {
+        Condition::any().add(created_at.lt(self.created_at)).add(
+            Condition::all()
+                .add(created_at.eq(self.created_at))
+                .add(ulid.lt(self.ulid.clone())),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ulid::Ulid;
+
+    #[test]
+    fn test_cursor_roundtrip_keeps_nanoseconds() {
+        let cursor = Cursor::new(Utc::now(), &Ulid::new().to_string());
+
+        let decoded = Cursor::decode(&cursor.encode()).unwrap();
+
+        assert_eq!(decoded, cursor);
+    }
+
+    #[test]
+    fn test_cursor_decode_rejects_garbage() {
+        assert!(Cursor::decode("").is_err());
+        assert!(Cursor::decode("not-a-cursor").is_err());
+        assert!(Cursor::decode("18f3a.0-invalid_ulid").is_err());
+    }
+}
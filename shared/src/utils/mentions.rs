@@ -0,0 +1,63 @@
+// More mentions in one message than this are ignored, a message shouldn't ping a whole lobby
+pub const MAX_MENTIONS: usize = 20;
+
+fn is_username_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '-')
+}
+
+// Usernames written as `@name`, in order of first appearance and without duplicates. An `@`
+// inside a word, like in an email address, is not a mention. Trailing dots and dashes are
+// punctuation: "thanks @mila." mentions "mila".
+pub fn parse_mentions(text: &str) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    let mut previous: Option<char> = None;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        let starts_mention = c == '@' && !previous.is_some_and(is_username_char);
+        previous = Some(c);
+        if !starts_mention {
+            continue;
+        }
+
+        let start = i + c.len_utf8();
+        let mut end = start;
+        while let Some(&(j, next)) = chars.peek() {
+            if !is_username_char(next) {
+                break;
+            }
+            end = j + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+
+        let name = text[start..end].trim_end_matches(['.', '-']);
+        if !name.is_empty() && !mentions.iter().any(|m| m == name) {
+            mentions.push(name.to_owned());
+            if mentions.len() == MAX_MENTIONS {
+                break;
+            }
+        }
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_mentions_in_order() {
+        assert_eq!(
+            parse_mentions("@mila, ask @night_owl. And @mila again"),
+            vec!["mila", "night_owl"]
+        );
+        assert_eq!(parse_mentions("@Đorđe-"), vec!["Đorđe"]);
+    }
+
+    #[test]
+    fn test_ignores_emails_and_lone_at() {
+        assert!(parse_mentions("write to mila@test.com @ noon").is_empty());
+    }
+}
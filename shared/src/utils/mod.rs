@@ -1,5 +1,8 @@
+pub mod cursor;
 pub mod hashing;
 pub mod jwt;
+pub mod mentions;
+pub mod monotonic_ulid;
 pub mod time;
 pub mod ulid_validation;
 pub mod validation;
@@ -0,0 +1,25 @@
+use std::sync::{LazyLock, Mutex};
+
+use ulid::{Generator, Ulid};
+
+static GENERATOR: LazyLock<Mutex<Generator>> = LazyLock::new(|| Mutex::new(Generator::new()));
+
+// `Ulid::new()` values made within the same millisecond sort randomly. Chat messages and lobby
+// events are ordered by their ID, so they come from one generator that keeps counting up.
+pub fn next_ulid() -> Ulid {
+    let mut generator = GENERATOR.lock().unwrap_or_else(|e| e.into_inner());
+    // Only fails after 2^80 IDs within one millisecond
+    generator.generate().unwrap_or_else(|_| Ulid::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_strictly_increase() {
+        let ids: Vec<String> = (0..1000).map(|_| next_ulid().to_string()).collect();
+
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -56,17 +56,65 @@ async getDailyQuests() : Promise<Result<UserQuestStatusResponse[], ErrorBody>> {
     else return { status: "error", error: e  as any };
 }
 },
-async submitQuestProof(questUlid: string, payload: SubmitProofRequest, imageList: number[][] | null, audioList: number[][] | null) : Promise<Result<SubmitProofResponse, ErrorBody>> {
+async submitQuestProof(questUlid: string, proofText: string | null, visibility: ProofVisibility, photoPaths: string[], voicePaths: string[]) : Promise<Result<ProofDraft, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("submit_quest_proof", { questUlid, payload, imageList, audioList }) };
+    return { status: "ok", data: await TAURI_INVOKE("submit_quest_proof", { questUlid, proofText, visibility, photoPaths, voicePaths }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getProofFeed(limit: number | null, offset: number | null) : Promise<Result<ProofFeedResponse, ErrorBody>> {
+async saveProofDraft(draftId: string | null, questUlid: string, proofText: string | null, visibility: ProofVisibility) : Promise<Result<ProofDraft, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_proof_feed", { limit, offset }) };
+    return { status: "ok", data: await TAURI_INVOKE("save_proof_draft", { draftId, questUlid, proofText, visibility }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addDraftMedia(draftId: string, kind: DraftMediaKind, data: number[] | null, sourcePath: string | null) : Promise<Result<ProofDraft, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_draft_media", { draftId, kind, data, sourcePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeDraftMedia(draftId: string, kind: DraftMediaKind, index: number) : Promise<Result<ProofDraft, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_draft_media", { draftId, kind, index }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async listProofDrafts() : Promise<Result<ProofDraft[], ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("list_proof_drafts") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async resumeProofDraft(draftId: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resume_proof_draft", { draftId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async discardProofDraft(draftId: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discard_proof_draft", { draftId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getProofFeed(limit: number | null, cursor: string | null, mode: FeedMode | null) : Promise<Result<Page<ProofDetailsResponse>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_proof_feed", { limit, cursor, mode }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -88,25 +136,25 @@ async toggleProofBelief(proofUlid: string) : Promise<Result<null, ErrorBody>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getSomeoneJournal(userUlid: string) : Promise<Result<ProofDetailsResponse[], ErrorBody>> {
+async getSomeoneJournal(userUlid: string, cursor: string | null) : Promise<Result<Page<ProofDetailsResponse>, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_someone_journal", { userUlid }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_someone_journal", { userUlid, cursor }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getMyJournal() : Promise<Result<ProofDetailsResponse[], ErrorBody>> {
+async getMyJournal(cursor: string | null) : Promise<Result<Page<ProofDetailsResponse>, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_my_journal") };
+    return { status: "ok", data: await TAURI_INVOKE("get_my_journal", { cursor }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async createLobby(name: string, topic: string, description: string | null) : Promise<Result<LobbyDto, ErrorBody>> {
+async createLobby(name: string, topic: string, description: string | null, visibility: LobbyVisibility) : Promise<Result<LobbyDto, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("create_lobby", { name, topic, description }) };
+    return { status: "ok", data: await TAURI_INVOKE("create_lobby", { name, topic, description, visibility }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -120,9 +168,25 @@ async getAllLobbies() : Promise<Result<LobbyFeedResponse, ErrorBody>> {
     else return { status: "error", error: e  as any };
 }
 },
-async getLobbyDetail(lobbyUlid: string) : Promise<Result<LobbyDetailsResponse, ErrorBody>> {
+async discoverLobbies(q: string | null, topic: string | null, sort: LobbySort, cursor: string | null) : Promise<Result<Page<DiscoveredLobby>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("discover_lobbies", { q, topic, sort, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLobbyTopics() : Promise<Result<TopicCount[], ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_lobby_topics") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLobbyDetail(lobbyUlid: string, invite: string | null) : Promise<Result<LobbyDetailsResponse, ErrorBody>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_lobby_detail", { lobbyUlid }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_lobby_detail", { lobbyUlid, invite }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -143,12 +207,356 @@ async joinLobby(lobbyUlid: string) : Promise<Result<LobbyMemberDto, ErrorBody>>
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async leaveLobby(lobbyUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("leave_lobby", { lobbyUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async kickLobbyMember(lobbyUlid: string, userUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("kick_lobby_member", { lobbyUlid, userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setLobbyMemberRole(lobbyUlid: string, userUlid: string, role: Role) : Promise<Result<LobbyMemberDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_lobby_member_role", { lobbyUlid, userUlid, role }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async banLobbyMember(lobbyUlid: string, userUlid: string, durationHours: number | null, reason: string | null) : Promise<Result<LobbyBanDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("ban_lobby_member", { lobbyUlid, userUlid, durationHours, reason }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async unbanLobbyMember(lobbyUlid: string, userUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unban_lobby_member", { lobbyUlid, userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async transferLobbyOwnership(lobbyUlid: string, userUlid: string) : Promise<Result<LobbyDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("transfer_lobby_ownership", { lobbyUlid, userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async setLobbyVisibility(lobbyUlid: string, visibility: LobbyVisibility) : Promise<Result<LobbyDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_lobby_visibility", { lobbyUlid, visibility }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async createLobbyInvite(lobbyUlid: string, expiresInHours: number | null, maxUses: number | null) : Promise<Result<LobbyInviteDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("create_lobby_invite", { lobbyUlid, expiresInHours, maxUses }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLobbyInvites(lobbyUlid: string) : Promise<Result<LobbyInviteDto[], ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_lobby_invites", { lobbyUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async revokeLobbyInvite(lobbyUlid: string, code: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revoke_lobby_invite", { lobbyUlid, code }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getInvitePreview(code: string) : Promise<Result<InvitePreview, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_invite_preview", { code }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async acceptLobbyInvite(code: string) : Promise<Result<LobbyMemberDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("accept_lobby_invite", { code }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async takeOpenedInvite() : Promise<Result<string | null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("take_opened_invite") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async requestToJoinLobby(lobbyUlid: string, message: string | null) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("request_to_join_lobby", { lobbyUlid, message }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getJoinRequests(lobbyUlid: string) : Promise<Result<JoinRequestDto[], ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_join_requests", { lobbyUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async approveJoinRequest(lobbyUlid: string, userUlid: string) : Promise<Result<LobbyMemberDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("approve_join_request", { lobbyUlid, userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteJoinRequest(lobbyUlid: string, userUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_join_request", { lobbyUlid, userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getOnlineMembers(lobbyUlid: string) : Promise<Result<LobbyUserDto[], ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_online_members", { lobbyUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLobbyMessages(lobbyUlid: string, limit: number | null, cursor: string | null) : Promise<Result<Page<MessageDto>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_lobby_messages", { lobbyUlid, limit, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async sendLobbyMessage(lobbyUlid: string, content: string, replyTo: string | null) : Promise<Result<MessageDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("send_lobby_message", { lobbyUlid, content, replyTo }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async editLobbyMessage(lobbyUlid: string, messageUlid: string, content: string) : Promise<Result<MessageDto, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("edit_lobby_message", { lobbyUlid, messageUlid, content }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async deleteLobbyMessage(lobbyUlid: string, messageUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("delete_lobby_message", { lobbyUlid, messageUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async addMessageReaction(lobbyUlid: string, messageUlid: string, emoji: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("add_message_reaction", { lobbyUlid, messageUlid, emoji }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async removeMessageReaction(lobbyUlid: string, messageUlid: string, emoji: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("remove_message_reaction", { lobbyUlid, messageUlid, emoji }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async markLobbyRead(lobbyUlid: string, messageUlid: string, silent: boolean) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("mark_lobby_read", { lobbyUlid, messageUlid, silent }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getLobbySocketTicket(lobbyUlid: string, since: string | null) : Promise<Result<LobbySocketTicket, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_lobby_socket_ticket", { lobbyUlid, since }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async syncLobbyMessages(lobbyUlid: string, since: string) : Promise<Result<Page<MessageDto>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("sync_lobby_messages", { lobbyUlid, since }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getPhotoMatches(cursor: string | null) : Promise<Result<Page<PhotoMatchDto>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_photo_matches", { cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async followUser(userUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("follow_user", { userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async unfollowUser(userUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("unfollow_user", { userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFollowCounts(userUlid: string) : Promise<Result<FollowCountsResponse, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_follow_counts", { userUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFollowers(userUlid: string, cursor: string | null) : Promise<Result<Page<FollowUserDto>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_followers", { userUlid, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async getFollowing(userUlid: string, cursor: string | null) : Promise<Result<Page<FollowUserDto>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_following", { userUlid, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async shareProof(proofUlid: string) : Promise<Result<string, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("share_proof", { proofUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async revokeProofShare(proofUlid: string) : Promise<Result<null, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("revoke_proof_share", { proofUlid }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+}
+},
+async getMyProfile() : Promise<Result<ProfileResponse, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_my_profile") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async updateMyProfile(payload: UpdateProfileRequest) : Promise<Result<ProfileResponse, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("update_my_profile", { payload }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async uploadAvatar(sourcePath: string) : Promise<Result<ProfileResponse, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("upload_avatar", { sourcePath }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchMessages(q: string, lobbyUlid: string | null, cursor: string | null) : Promise<Result<Page<SearchHit>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_messages", { q, lobbyUlid, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchProofs(q: string, cursor: string | null) : Promise<Result<Page<SearchHit>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_proofs", { q, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchLobbies(q: string, cursor: string | null) : Promise<Result<Page<SearchHit>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_lobbies", { q, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async searchQuests(q: string, cursor: string | null) : Promise<Result<Page<SearchHit>, ErrorBody>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_quests", { q, cursor }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
 /** user-defined events **/
 
 
+export const events = __makeEvents__<{
+proofUploadProgress: ProofUploadProgress,
+inviteLinkOpened: InviteLinkOpened
+}>({
+proofUploadProgress: "proof-upload-progress",
+inviteLinkOpened: "invite-link-opened"
+})
 
 /** user-defined constants **/
 
@@ -157,24 +565,52 @@ async joinLobby(lobbyUlid: string) : Promise<Result<LobbyMemberDto, ErrorBody>>
 /** user-defined types **/
 
 export type Complexity = "easy" | "medium" | "hard"
+export type DiscoveredLobby = { lobby: LobbyDto; members_count: number; is_member: boolean; last_activity_at: string | null }
+export type DraftMediaKind = "photo" | "voice"
 export type ErrorBody = { error_type: ErrorCode; message: string }
-export type ErrorCode = "AUTH_INVALID" | "USER_EXISTS" | "VALIDATION_ERROR" | "NOT_FOUND" | "DATABASE_ERROR" | "SERVER_ERROR" | "CUSTOM_ERROR"
+export type ErrorCode = "AUTH_INVALID" | "USER_EXISTS" | "USERNAME_TAKEN" | "VALIDATION_ERROR" | "NOT_FOUND" | "DATABASE_ERROR" | "SERVER_ERROR" | "CUSTOM_ERROR" | "RATE_LIMITED"
+export type FeedMode = "chronological" | "for_you" | "following"
+export type FollowCountsResponse = { followers: number; following: number; is_following: boolean }
+export type FollowUserDto = { ulid: string; username: string; avatar_url: string | null; level: number; followed_at: string }
+export type InviteLinkOpened = { code: string }
+export type InvitePreview = { code: string; lobby: LobbyDto; members_count: number }
+export type JoinRequestDto = { lobby_id: string; user: LobbyUserDto; message: string | null; created_at: string }
+export type LobbyBanDto = { lobby_id: string; user_id: string; banned_by: string; reason: string | null; expires_at: string | null; created_at: string }
 export type LobbyDetailsResponse = { lobby: LobbyDto; members_count: number }
-export type LobbyDto = { ulid: string; name: string; topic: string; description: string | null; owner_id: string; created_at: string }
-export type LobbyFeedItem = { lobby: LobbyDto; is_member: boolean }
+export type LobbyDto = { ulid: string; name: string; topic: string; description: string | null; owner_id: string; visibility: LobbyVisibility; created_at: string }
+export type LobbyFeedItem = { lobby: LobbyDto; is_member: boolean; unread_count: number }
 export type LobbyFeedResponse = { items: LobbyFeedItem[] }
+export type LobbyInviteDto = { code: string; lobby_id: string; created_by: string; max_uses: number | null; uses: number; expires_at: string | null; created_at: string }
 export type LobbyMemberDto = { lobby_id: string; user_id: string; role: Role; joined_at: string }
+export type LobbySocketTicket = { url: string; protocols: string[] }
+export type LobbySort = "members" | "activity" | "newest"
+export type LobbyUserDto = { ulid: string; username: string; avatar_url: string | null; level: number }
+export type LobbyVisibility = "public" | "unlisted" | "private"
+export type MessageDto = { ulid: string; author_id: string; author_name: string; content: string; created_at: string; edited_at: string | null; reply_to_id: string | null; deleted: boolean; mentions: string[]; reactions: ReactionDto[] }
 export type LoginRequest = { password: string; email: string }
 export type LoginResponse = { ulid: string; username: string; email: string; refresh_token: string; level: number; avatar_url: string | null }
-export type ProofDetailsResponse = { ulid: string; user_id: string; username: string; avatar_url: string | null; quest_id: string; quest_title: string; quest_description: string | null; xp_reward: number; proof_text: string | null; status: string; photo_urls: string[]; voice_urls: string[]; beliefs_count: number; is_believed: boolean; created_at: string }
-export type ProofFeedResponse = { items: ProofDetailsResponse[]; has_more: boolean; next_offset: number }
+export type Page<T> = { items: T[]; has_more: boolean; next_cursor: string | null }
+export type PhotoMatchDto = { ulid: string; kind: PhotoMatchKind; distance: number; proof_id: string; user_id: string; photo_url: string | null; matched_proof_id: string; matched_user_id: string; matched_photo_url: string | null; created_at: string }
+export type PhotoMatchKind = "reused" | "copied"
+export type PresignedUpload = { url: string; fields: Partial<{ [key in string]: string }>; max_bytes: number }
+export type ProofDetailsResponse = { ulid: string; user_id: string; username: string; avatar_url: string | null; quest_id: string; quest_title: string; quest_description: string | null; xp_reward: number; proof_text: string | null; status: string; photo_urls: string[]; voice_urls: string[]; beliefs_count: number; is_believed: boolean; visibility: ProofVisibility; created_at: string }
+export type ProfileResponse = { ulid: string; username: string; email: string; bio: string | null; avatar_url: string | null; level: number; xp_balance: number; total_xp_accumulated: number; created_at: string }
+export type ProofDraft = { draft_id: string; user_ulid: string; quest_ulid: string; proof_text: string | null; visibility: ProofVisibility; photo_paths: string[]; voice_paths: string[]; proof_ulid: string | null; photo_uploads?: PresignedUpload[]; voice_uploads?: PresignedUpload[]; uploaded_photos: number; uploaded_voices: number; queued?: boolean }
+export type ProofUploadProgress = { draft_id: string; proof_ulid: string | null; stage: UploadStage; uploaded_files: number; total_files: number; error: string | null }
+export type ProofVisibility = "public" | "followers" | "lobby" | "private"
 export type QuestDto = { ulid: string; title: string; description: string | null; complexity: Complexity; xp_reward: number; validation_type: string; target_value: number }
 export type QuestStatus = "IN_PROGRESS" | "COMPLETED" | "IN_PENDING" | "FAILED"
+export type ReactionDto = { emoji: string; user_ids: string[] }
 export type RegisterRequest = { username: string; email: string; password: string }
 export type RegisterResponse = { ulid: string; username: string; email: string; created_at: string; refresh_token: string; level: number; avatar_url: string | null }
 export type Role = "STRANGER" | "MEMBER" | "HELPER" | "MODERATOR" | "ADMIN"
-export type SubmitProofRequest = { proof_text: string | null; photo_count: number; voice_count: number }
-export type SubmitProofResponse = { proof_ulid: string; status: string; photo_upload_urls: string[]; voice_upload_urls: string[] }
+export type SearchHit = { ulid: string; lobby_id: string | null; snippet: SnippetPart[] }
+export type SnippetPart = { text: string; highlighted: boolean }
+export type SubmitProofRequest = { proof_text: string | null; photo_count: number; voice_count: number; visibility?: ProofVisibility }
+export type SubmitProofResponse = { proof_ulid: string; status: string; photo_uploads: PresignedUpload[]; voice_uploads: PresignedUpload[] }
+export type TopicCount = { topic: string; lobbies_count: number }
+export type UpdateProfileRequest = { username: string | null; bio: string | null }
+export type UploadStage = "queued" | "initializing" | "uploading" | "confirming" | "done" | "failed"
 export type UserQuestStatusResponse = { user_ulid: string; quest: QuestDto; status: QuestStatus; current_value: number; is_completed: boolean; completed_at: string | null }
 export type UserSession = { access_token: string | null; user_ulid: string; email: string; username: string; level: number; avatar_url: string | null }
 
@@ -19,12 +19,17 @@ const Archive: React.FC = () => {
   const [isDrawerOpen, setIsDrawerOpen] = useState(false);
   const [proofs, setProofs] = useState<ProofDetailsResponse[]>([]);
   const [isLoading, setIsLoading] = useState(true);
+  const [nextCursor, setNextCursor] = useState<string | null>(null);
+  const [isLoadingMore, setIsLoadingMore] = useState(false);
 
   const fetchMyArchive = useCallback(async () => {
     setIsLoading(true);
     try {
-      const res = await commands.getMyJournal();
-      if (res.status === 'ok') setProofs(res.data);
+      const res = await commands.getMyJournal(null);
+      if (res.status === 'ok') {
+        setProofs(res.data.items);
+        setNextCursor(res.data.has_more ? res.data.next_cursor : null);
+      }
     } catch (err) {
       console.error("Failed to fetch journal", err);
     } finally {
@@ -32,6 +37,22 @@ const Archive: React.FC = () => {
     }
   }, []);
 
+  const loadMore = async () => {
+    if (!nextCursor || isLoadingMore) return;
+    setIsLoadingMore(true);
+    try {
+      const res = await commands.getMyJournal(nextCursor);
+      if (res.status === 'ok') {
+        setProofs(prev => [...prev, ...res.data.items]);
+        setNextCursor(res.data.has_more ? res.data.next_cursor : null);
+      }
+    } catch (err) {
+      console.error("Failed to fetch more of the journal", err);
+    } finally {
+      setIsLoadingMore(false);
+    }
+  };
+
   useEffect(() => {
     fetchMyArchive();
   }, [fetchMyArchive]);
@@ -138,6 +159,16 @@ const Archive: React.FC = () => {
                 </div>
               </div>
             ))}
+
+            {nextCursor && (
+              <button
+                onClick={loadMore}
+                disabled={isLoadingMore}
+                className="mt-4 py-4 bg-white/5 border border-white/10 rounded-2xl text-[10px] font-black uppercase tracking-widest text-slate-400 active:scale-95 transition-all disabled:opacity-50"
+              >
+                {isLoadingMore ? 'Loading...' : 'Load more'}
+              </button>
+            )}
           </div>
         )}
       </main>
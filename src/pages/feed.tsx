@@ -265,7 +265,7 @@ const Feed: React.FC = () => {
 
   const fetchFeed = useCallback(async () => {
     setIsLoading(true);
-    const res = await commands.getProofFeed(20, 0);
+    const res = await commands.getProofFeed(20, null, null);
     if (res.status === 'ok') setFeed(res.data.items || []);
     setIsLoading(false);
   }, []);
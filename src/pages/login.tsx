@@ -7,11 +7,13 @@ import { Sparkles, ShieldCheck, Mail, Lock, AlertTriangle, Info } from 'lucide-r
 const UI_MESSAGES: Record<ErrorCode, string> = {
   AUTH_INVALID: "Invalid credentials. Double-check your email and password.",
   USER_EXISTS: "Identity already exists in the database.",
+  USERNAME_TAKEN: "This username is already taken.",
   VALIDATION_ERROR: "Input verification failed. See details below.",
   NOT_FOUND: "System core: Resource not located.",
   DATABASE_ERROR: "Storage failure. Data link interrupted.",
   SERVER_ERROR: "Neural link error. Server is unresponsive.",
   CUSTOM_ERROR: "CUSTOM ERROR",
+  RATE_LIMITED: "Too many attempts. Try again in a moment.",
 };
 
 const Login: React.FC = () => {
@@ -7,11 +7,13 @@ import { Sparkles, UserPlus, Mail, Lock, User, AlertTriangle, Info } from 'lucid
 const UI_MESSAGES: Record<ErrorCode, string> = {
   AUTH_INVALID: "Invalid credentials. Please try again.",
   USER_EXISTS: "This username or email is already taken.",
+  USERNAME_TAKEN: "This username is already taken.",
   VALIDATION_ERROR: "Registration data is invalid. Check details below.",
   NOT_FOUND: "System core: Resource not located.",
   DATABASE_ERROR: "Storage failure. Data link interrupted.",
   SERVER_ERROR: "Neural link error. Server is unresponsive.",
   CUSTOM_ERROR: "CUSTOM ERROR",
+  RATE_LIMITED: "Too many attempts. Try again in a moment.",
 };
 
 const Register: React.FC = () => {
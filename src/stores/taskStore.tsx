@@ -1,6 +1,6 @@
 import { create } from 'zustand'
-import { commands } from '../bindings'
-import type { UserQuestStatusResponse, SubmitProofRequest, SubmitProofResponse } from '../bindings'
+import { commands, events } from '../bindings'
+import type { UserQuestStatusResponse, SubmitProofRequest, ProofDraft } from '../bindings'
 
 interface TaskState {
   tasks: UserQuestStatusResponse[]
@@ -13,7 +13,85 @@ interface TaskState {
     payload: SubmitProofRequest,
     images?: Uint8Array[],
     audios?: Uint8Array[]
-  ) => Promise<SubmitProofResponse | null>
+  ) => Promise<ProofDraft | null>
+
+  getTaskById: (id: string) => UserQuestStatusResponse | undefined
+}
+
+export const useTaskStore = create<TaskState>((set, get) => ({
+  tasks: [],
+  isLoading: false,
+  error: null,
+
+  fetchTasks: async () => {
+    set({ isLoading: true, error: null })
+    try {
+      const result = await commands.getDailyQuests()
+
+      if (result.status === "ok") {
+        set({ tasks: result.data, isLoading: false })
+      } else {
+        set({ error: result.error.message, isLoading: false })
+      }
+    } catch (err) {
+      set({ error: "Failed to connect to backend", isLoading: false })
+      console.error(err)
+    }
+  },
+
+  // Media goes into a local draft first, then the background upload queue takes over
+  submitProof: async (questUlid, payload, images, audios) => {
+    set({ isLoading: true, error: null })
+    try {
+      const saved = await commands.saveProofDraft(
+        null,
+        questUlid,
+        payload.proof_text,
+        payload.visibility ?? "public"
+      )
+      if (saved.status !== "ok") {
+        set({ error: saved.error.message, isLoading: false })
+        return null
+      }
+
+      let draft = saved.data
+      const media = [
+        ...(images ?? []).map(data => ({ kind: "photo" as const, data })),
+        ...(audios ?? []).map(data => ({ kind: "voice" as const, data })),
+      ]
+      for (const item of media) {
+        const added = await commands.addDraftMedia(draft.draft_id, item.kind, Array.from(item.data), null)
+        if (added.status !== "ok") {
+          set({ error: added.error.message, isLoading: false })
+          return null
+        }
+        draft = added.data
+      }
+
+      const queued = await commands.resumeProofDraft(draft.draft_id)
+      if (queued.status !== "ok") {
+        set({ error: queued.error.message, isLoading: false })
+        return null
+      }
+
+      const unlisten = await events.proofUploadProgress.listen(({ payload: progress }) => {
+        if (progress.draft_id !== draft.draft_id) return
+        if (progress.stage === "done") {
+          unlisten()
+          get().fetchTasks()
+        } else if (progress.stage === "failed") {
+          unlisten()
+          set({ error: progress.error ?? "Upload failed, the draft was kept" })
+        }
+      })
+
+      set({ isLoading: false })
+      return draft
+    } catch (err) {
+      set({ error: "Submission failed", isLoading: false })
+      return null
+    }
+  },
 
   getTaskById: (id: string) => UserQuestStatusResponse | undefined
 }
@@ -6,13 +6,14 @@ use serde::{Deserialize, Serialize};
 use shared::endpoints::{refresh_token_endpoints::RefreshTokenEndpoints, API};
 use shared::errors::{jwt_errors::JwtError, AppError, AppResult};
 use shared::models::refresh_token_dto::{CreateRefreshTokenRequest, CreateRefreshTokenResponse};
-use shared::models::Pagination;
+use shared::models::user_dto::ProfileResponse;
+use shared::models::PageQuery;
 use shared::utils::jwt::verify_access_token;
 use tauri::AppHandle;
 use tauri_plugin_store::StoreExt;
 use tokio::sync::{Mutex, RwLock};
 
-const API_URL: &str = env!("BACKEND_URL");
+pub const API_URL: &str = env!("BACKEND_URL");
 
 // TODO: DONT FORGET ABOUT SRR MATE, ITS IMPORTANT
 pub struct AuthService {
@@ -51,7 +52,7 @@ impl AuthService {
         &self,
         method: Method,
         body: Option<&V>,
-        pagination: Option<&Pagination>,
+        pagination: Option<&PageQuery>,
         endpoint: impl API,
     ) -> AppResult<T>
     where
@@ -85,12 +86,14 @@ impl AuthService {
         url: &str,
         method: Method,
         body: Option<&V>,
-        pagination: Option<&Pagination>,
+        pagination: Option<&PageQuery>,
     ) -> AppResult<reqwest::Response>
     where
         V: Serialize,
     {
         let session_guard = self.session.read().await;
+        // GET has no body, its payload goes into the query string instead
+        let body_as_query = method == Method::GET;
         let mut rb = self.client.request(method, url);
 
         if let Some(token) = session_guard.as_ref().and_then(|s| s.access_token.as_ref()) {
@@ -98,7 +101,11 @@ impl AuthService {
         }
 
         if let Some(b) = body {
-            rb = rb.json(b);
+            rb = if body_as_query {
+                rb.query(b)
+            } else {
+                rb.json(b)
+            };
         }
 
         if let Some(q) = pagination {
@@ -209,37 +216,32 @@ impl AuthService {
             warn!("Could not create local access token for immediate use");
         }
 
-        let mut session_guard = self.session.write().await;
-        *session_guard = Some(UserSession {
+        let session = UserSession {
             access_token,
             email: email.to_string(),
             user_ulid: ulid.to_string(),
             username: username.to_string(),
             level,
-            avatar_url: avatar_url.clone(),
-        });
-
-        if let Ok(store) = app.store("user.json") {
-            store.set(
-                "user_conf",
-                serde_json::json!({
-                    "email": email,
-                    "user_ulid": ulid,
-                    "username": username,
-                    "level": level,
-                    "avatar_url": avatar_url,
-                }),
-            );
-            if let Err(e) = store.save() {
-                error!("Failed to save user configuration to store: {:?}", e);
-            } else {
-                debug!("User configuration persisted to user.json");
-            }
-        }
+            avatar_url,
+        };
+        persist_user_conf(app, &session);
+        *self.session.write().await = Some(session);
 
         Ok(())
     }
 
+    // Keeps the cached session in line with the profile after it was fetched or edited
+    pub async fn update_session_profile(&self, app: &AppHandle, profile: &ProfileResponse) {
+        let mut session_guard = self.session.write().await;
+        let Some(session) = session_guard.as_mut() else {
+            return;
+        };
+        session.username = profile.username.clone();
+        session.level = profile.level;
+        session.avatar_url = profile.avatar_url.clone();
+        persist_user_conf(app, session);
+    }
+
     pub async fn delete_user_session(&self) {
         info!("Delete user session...");
 
@@ -248,4 +250,26 @@ impl AuthService {
     }
 }
 
+// The avatar URL is presigned and expires within the hour, so it stays in memory only and
+// is filled in again by the next profile fetch
+fn persist_user_conf(app: &AppHandle, session: &UserSession) {
+    let Ok(store) = app.store("user.json") else {
+        return;
+    };
+    store.set(
+        "user_conf",
+        serde_json::json!({
+            "email": session.email,
+            "user_ulid": session.user_ulid,
+            "username": session.username,
+            "level": session.level,
+        }),
+    );
+    if let Err(e) = store.save() {
+        error!("Failed to save user configuration to store: {:?}", e);
+    } else {
+        debug!("User configuration persisted to user.json");
+    }
+}
+
 pub struct AppState(pub AuthService);
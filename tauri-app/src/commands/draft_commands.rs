@@ -0,0 +1,171 @@
+use shared::{
+    errors::{AppError, AppResult, FrontendRepresentation},
+    models::quest_proof_dto::ProofVisibility,
+};
+use tauri::{AppHandle, State};
+
+use log::{info, warn};
+
+use crate::auth::service::AppState;
+use crate::drafts::{
+    draft::{DraftMediaKind, ProofDraft},
+    service::cancel_submission,
+    store::{
+        copy_draft_media, delete_draft, get_draft, list_drafts, remove_draft_media_file,
+        save_draft, write_draft_media,
+    },
+    upload_queue::UploadQueue,
+};
+
+// Drafts are editable until InitSubmission assigned a proof ULID; after that only
+// resume or discard make sense.
+fn ensure_editable(draft: &ProofDraft) -> Result<(), AppError> {
+    if draft.is_submitted() {
+        warn!("Draft {} is already being submitted", draft.draft_id);
+        return Err(AppError::Custom(
+            "Draft is already submitted, resume or discard it".into(),
+        ));
+    }
+    Ok(())
+}
+
+// Drafts outlive sessions on this device, so every lookup checks who is signed in now
+async fn get_own_draft(app: &AppHandle, state: &AppState, draft_id: &str) -> AppResult<ProofDraft> {
+    let user_ulid = state.0.get_current_user_ulid().await?;
+    let draft = get_draft(app, draft_id)?;
+    if draft.user_ulid != user_ulid {
+        warn!("Draft {} belongs to another user", draft_id);
+        return Err(AppError::NotFound);
+    }
+    Ok(draft)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn save_proof_draft(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    draft_id: Option<String>,
+    quest_ulid: String,
+    proof_text: Option<String>,
+    visibility: ProofVisibility,
+) -> FrontendRepresentation<ProofDraft> {
+    let user_ulid = state.0.get_current_user_ulid().await?;
+
+    let mut draft = match draft_id {
+        Some(id) => get_own_draft(&app, &state, &id).await?,
+        None => ProofDraft::new(user_ulid, quest_ulid.clone()),
+    };
+    ensure_editable(&draft)?;
+
+    draft.quest_ulid = quest_ulid;
+    draft.proof_text = proof_text;
+    draft.visibility = visibility;
+    save_draft(&app, &draft)?;
+
+    Ok(draft)
+}
+
+// Either raw bytes (picked in the webview) or a path on disk (e.g. a recording)
+#[tauri::command]
+#[specta::specta]
+pub async fn add_draft_media(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    draft_id: String,
+    kind: DraftMediaKind,
+    data: Option<Vec<u8>>,
+    source_path: Option<String>,
+) -> FrontendRepresentation<ProofDraft> {
+    let mut draft = get_own_draft(&app, &state, &draft_id).await?;
+    ensure_editable(&draft)?;
+
+    let path = match (data, source_path) {
+        (Some(data), _) => write_draft_media(&app, &draft, kind, &data).await?,
+        (None, Some(source)) => copy_draft_media(&app, &draft, kind, &source).await?,
+        (None, None) => return Err(AppError::Custom("No media provided".into()).into()),
+    };
+
+    draft.media_paths_mut(kind).push(path);
+    save_draft(&app, &draft)?;
+
+    Ok(draft)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_draft_media(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    draft_id: String,
+    kind: DraftMediaKind,
+    index: u32,
+) -> FrontendRepresentation<ProofDraft> {
+    let mut draft = get_own_draft(&app, &state, &draft_id).await?;
+    ensure_editable(&draft)?;
+
+    let paths = draft.media_paths_mut(kind);
+    if index as usize >= paths.len() {
+        return Err(AppError::NotFound.into());
+    }
+    let path = paths.remove(index as usize);
+    save_draft(&app, &draft)?;
+
+    remove_draft_media_file(&path).await;
+
+    Ok(draft)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn list_proof_drafts(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> FrontendRepresentation<Vec<ProofDraft>> {
+    let user_ulid = state.0.get_current_user_ulid().await?;
+    Ok(list_drafts(&app, &user_ulid)?)
+}
+
+// Hands the draft to the upload queue; progress arrives as ProofUploadProgress events
+#[tauri::command]
+#[specta::specta]
+pub async fn resume_proof_draft(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    queue: State<'_, UploadQueue>,
+    draft_id: String,
+) -> FrontendRepresentation<()> {
+    let draft = get_own_draft(&app, &state, &draft_id).await?;
+
+    info!(
+        "Resuming draft {} (proof: {:?}, photos {}/{}, voices {}/{})",
+        draft.draft_id,
+        draft.proof_ulid,
+        draft.uploaded_photos,
+        draft.photo_paths.len(),
+        draft.uploaded_voices,
+        draft.voice_paths.len()
+    );
+
+    queue.enqueue(&app, &draft.draft_id)?;
+    Ok(())
+}
+
+// A proof that was already initialized is cancelled on the server first, so it doesn't
+// linger there in the Uploading state
+#[tauri::command]
+#[specta::specta]
+pub async fn discard_proof_draft(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    draft_id: String,
+) -> FrontendRepresentation<()> {
+    let draft = get_own_draft(&app, &state, &draft_id).await?;
+    info!("Discarding draft {}", draft_id);
+
+    if let Some(proof_ulid) = &draft.proof_ulid {
+        cancel_submission(&state.0, proof_ulid).await?;
+    }
+    delete_draft(&app, &draft_id).await?;
+    Ok(())
+}
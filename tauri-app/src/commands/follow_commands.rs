@@ -0,0 +1,125 @@
+use reqwest::Method;
+use shared::{
+    endpoints::{follow_endpoints::FollowEndpoints, UserUlid},
+    errors::FrontendRepresentation,
+    models::{
+        follow_dto::{FollowCountsResponse, FollowUserDto},
+        Page, PageQuery,
+    },
+};
+use tauri::State;
+
+use log::info;
+
+use crate::auth::service::AppState;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn follow_user(
+    state: State<'_, AppState>,
+    user_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Following user {}", user_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::POST,
+            None::<&()>,
+            None,
+            FollowEndpoints::Follow(UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unfollow_user(
+    state: State<'_, AppState>,
+    user_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Unfollowing user {}", user_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            FollowEndpoints::Unfollow(UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_follow_counts(
+    state: State<'_, AppState>,
+    user_ulid: String,
+) -> FrontendRepresentation<FollowCountsResponse> {
+    let response: FollowCountsResponse = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            None,
+            FollowEndpoints::GetCounts(UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_followers(
+    state: State<'_, AppState>,
+    user_ulid: String,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<FollowUserDto>> {
+    let pagination = PageQuery {
+        limit: None,
+        cursor,
+    };
+
+    let response: Page<FollowUserDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            Some(&pagination),
+            FollowEndpoints::GetFollowers(UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_following(
+    state: State<'_, AppState>,
+    user_ulid: String,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<FollowUserDto>> {
+    let pagination = PageQuery {
+        limit: None,
+        cursor,
+    };
+
+    let response: Page<FollowUserDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            Some(&pagination),
+            FollowEndpoints::GetFollowing(UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
@@ -0,0 +1,231 @@
+use reqwest::Method;
+use shared::{
+    endpoints::{lobby_endpoints::LobbyEndpoints, LobbyUlid, UserUlid},
+    errors::FrontendRepresentation,
+    models::lobby_dto::{
+        CreateInviteRequest, CreateJoinRequest, InvitePreview, JoinRequestDto, LobbyDto,
+        LobbyInviteDto, LobbyMemberDto, LobbyVisibility, SetVisibilityRequest,
+    },
+};
+use tauri::State;
+
+use log::info;
+
+use crate::auth::service::AppState;
+use crate::deep_link::PendingInvite;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_lobby_visibility(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    visibility: LobbyVisibility,
+) -> FrontendRepresentation<LobbyDto> {
+    let response: LobbyDto = state
+        .0
+        .perform_request(
+            Method::PUT,
+            Some(&SetVisibilityRequest { visibility }),
+            None,
+            LobbyEndpoints::SetVisibility(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_lobby_invite(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    expires_in_hours: Option<u32>,
+    max_uses: Option<u32>,
+) -> FrontendRepresentation<LobbyInviteDto> {
+    let response: LobbyInviteDto = state
+        .0
+        .perform_request(
+            Method::POST,
+            Some(&CreateInviteRequest {
+                expires_in_hours,
+                max_uses,
+            }),
+            None,
+            LobbyEndpoints::CreateInvite(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lobby_invites(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+) -> FrontendRepresentation<Vec<LobbyInviteDto>> {
+    let response: Vec<LobbyInviteDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            None,
+            LobbyEndpoints::GetInvites(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn revoke_lobby_invite(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    code: String,
+) -> FrontendRepresentation<()> {
+    info!("Revoking invite {} of lobby {}", code, lobby_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            LobbyEndpoints::RevokeInvite(LobbyUlid(lobby_ulid), code),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_invite_preview(
+    state: State<'_, AppState>,
+    code: String,
+) -> FrontendRepresentation<InvitePreview> {
+    let response: InvitePreview = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            None,
+            LobbyEndpoints::GetInvite(code),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn accept_lobby_invite(
+    state: State<'_, AppState>,
+    code: String,
+) -> FrontendRepresentation<LobbyMemberDto> {
+    info!("Accepting invite {}", code);
+
+    let response: LobbyMemberDto = state
+        .0
+        .perform_request(
+            Method::POST,
+            None::<&()>,
+            None,
+            LobbyEndpoints::AcceptInvite(code),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+// The invite the app was opened with, if the frontend hasn't picked it up yet
+#[tauri::command]
+#[specta::specta]
+pub async fn take_opened_invite(
+    pending: State<'_, PendingInvite>,
+) -> FrontendRepresentation<Option<String>> {
+    Ok(pending.take())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn request_to_join_lobby(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message: Option<String>,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::POST,
+            Some(&CreateJoinRequest { message }),
+            None,
+            LobbyEndpoints::RequestToJoin(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_join_requests(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+) -> FrontendRepresentation<Vec<JoinRequestDto>> {
+    let response: Vec<JoinRequestDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            None,
+            LobbyEndpoints::GetJoinRequests(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn approve_join_request(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+) -> FrontendRepresentation<LobbyMemberDto> {
+    info!("Letting {} into lobby {}", user_ulid, lobby_ulid);
+
+    let response: LobbyMemberDto = state
+        .0
+        .perform_request(
+            Method::POST,
+            None::<&()>,
+            None,
+            LobbyEndpoints::ApproveJoinRequest(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+// Rejects someone's request, or cancels the user's own when `user_ulid` is theirs
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_join_request(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            LobbyEndpoints::DeleteJoinRequest(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -1,16 +1,28 @@
 use reqwest::Method;
 use shared::{
-    endpoints::{lobby_endpoints::LobbyEndpoints, LobbyUlid, UserUlid},
+    endpoints::{
+        lobby_endpoints::{DiscoverQuery, LobbyDetailsQuery, LobbyEndpoints, LobbySort},
+        message_endpoints::{
+            EditMessageRequest, MarkReadRequest, MessageEndpoints, SendMessageRequest, SyncQuery,
+        },
+        LobbyUlid, UserUlid, API,
+    },
     errors::FrontendRepresentation,
-    models::lobby_dto::{
-        CreateLobbyRequest, LobbyDetailsResponse, LobbyDto, LobbyFeedResponse, LobbyMemberDto,
+    models::{
+        lobby_dto::{
+            BanMemberRequest, CreateLobbyRequest, DiscoveredLobby, LobbyBanDto,
+            LobbyDetailsResponse, LobbyDto, LobbyFeedResponse, LobbyMemberDto, LobbyVisibility,
+            Role, SetRoleRequest, TopicCount, TransferOwnershipRequest,
+        },
+        message_dto::{LobbySocketTicket, LobbyUserDto, MessageDto},
+        Page, PageQuery,
     },
 };
 use tauri::State;
 
 use log::info;
 
-use crate::auth::service::AppState;
+use crate::auth::service::{AppState, API_URL};
 
 #[tauri::command]
 #[specta::specta]
@@ -18,17 +30,16 @@ pub async fn create_lobby(
     name: String,
     topic: String,
     description: Option<String>,
+    visibility: LobbyVisibility,
     state: State<'_, AppState>,
 ) -> FrontendRepresentation<LobbyDto> {
     let service = &state.0;
 
-    let ulid = service.get_current_user_ulid().await?;
-
     let lobby_create_request = CreateLobbyRequest {
         name,
         description,
         topic,
-        owner_id: ulid,
+        visibility,
     };
 
     let response: LobbyDto = service
@@ -63,11 +74,54 @@ pub async fn get_all_lobbies(
     Ok(lobbies)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn discover_lobbies(
+    state: State<'_, AppState>,
+    q: Option<String>,
+    topic: Option<String>,
+    sort: LobbySort,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<DiscoveredLobby>> {
+    let response: Page<DiscoveredLobby> = state
+        .0
+        .perform_request(
+            Method::GET,
+            Some(&DiscoverQuery {
+                q,
+                topic,
+                sort,
+                limit: None,
+                cursor,
+            }),
+            None,
+            LobbyEndpoints::Discover,
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lobby_topics(
+    state: State<'_, AppState>,
+) -> FrontendRepresentation<Vec<TopicCount>> {
+    let response: Vec<TopicCount> = state
+        .0
+        .perform_request(Method::GET, None::<&()>, None, LobbyEndpoints::GetTopics)
+        .await?;
+
+    Ok(response)
+}
+
+// `invite` opens up the details of a private lobby the user isn't a member of yet
 #[tauri::command]
 #[specta::specta]
 pub async fn get_lobby_detail(
     state: State<'_, AppState>,
     lobby_ulid: String,
+    invite: Option<String>,
 ) -> FrontendRepresentation<LobbyDetailsResponse> {
     let service = &state.0;
 
@@ -76,7 +130,7 @@ pub async fn get_lobby_detail(
     let response: LobbyDetailsResponse = service
         .perform_request(
             Method::GET,
-            None::<&()>,
+            Some(&LobbyDetailsQuery { invite }),
             None,
             LobbyEndpoints::GetDetails(LobbyUlid(lobby_ulid)),
         )
@@ -107,6 +161,142 @@ pub async fn join_lobby(
     Ok(response)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn leave_lobby(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Leaving lobby {}", lobby_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::POST,
+            None::<&()>,
+            None,
+            LobbyEndpoints::Leave(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn kick_lobby_member(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Kicking {} from lobby {}", user_ulid, lobby_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            LobbyEndpoints::KickMember(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_lobby_member_role(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+    role: Role,
+) -> FrontendRepresentation<LobbyMemberDto> {
+    let response: LobbyMemberDto = state
+        .0
+        .perform_request(
+            Method::PUT,
+            Some(&SetRoleRequest { role }),
+            None,
+            LobbyEndpoints::SetRole(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+// Without `duration_hours` the ban has no end
+#[tauri::command]
+#[specta::specta]
+pub async fn ban_lobby_member(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+    duration_hours: Option<u32>,
+    reason: Option<String>,
+) -> FrontendRepresentation<LobbyBanDto> {
+    info!("Banning {} from lobby {}", user_ulid, lobby_ulid);
+
+    let payload = BanMemberRequest {
+        duration_hours,
+        reason,
+    };
+
+    let response: LobbyBanDto = state
+        .0
+        .perform_request(
+            Method::PUT,
+            Some(&payload),
+            None,
+            LobbyEndpoints::Ban(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unban_lobby_member(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            LobbyEndpoints::Unban(LobbyUlid(lobby_ulid), UserUlid(user_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn transfer_lobby_ownership(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    user_ulid: String,
+) -> FrontendRepresentation<LobbyDto> {
+    info!("Transferring lobby {} to {}", lobby_ulid, user_ulid);
+
+    let response: LobbyDto = state
+        .0
+        .perform_request(
+            Method::POST,
+            Some(&TransferOwnershipRequest { user_id: user_ulid }),
+            None,
+            LobbyEndpoints::TransferOwnership(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn get_lobby_memebers_count(
@@ -126,3 +316,223 @@ pub async fn get_lobby_memebers_count(
 
     Ok(response)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_online_members(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+) -> FrontendRepresentation<Vec<LobbyUserDto>> {
+    let response: Vec<LobbyUserDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            None,
+            LobbyEndpoints::GetOnlineMembers(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lobby_messages(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    limit: Option<u32>,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<MessageDto>> {
+    let pagination = PageQuery { limit, cursor };
+
+    let response: Page<MessageDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            Some(&pagination),
+            MessageEndpoints::GetLobbyMessages(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_lobby_messages(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    since: String,
+) -> FrontendRepresentation<Page<MessageDto>> {
+    let response: Page<MessageDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            Some(&SyncQuery { since, limit: None }),
+            None,
+            MessageEndpoints::SyncMessages(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn send_lobby_message(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    content: String,
+    reply_to: Option<String>,
+) -> FrontendRepresentation<MessageDto> {
+    let response: MessageDto = state
+        .0
+        .perform_request(
+            Method::POST,
+            Some(&SendMessageRequest { content, reply_to }),
+            None,
+            MessageEndpoints::SendMessage(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn edit_lobby_message(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message_ulid: String,
+    content: String,
+) -> FrontendRepresentation<MessageDto> {
+    let response: MessageDto = state
+        .0
+        .perform_request(
+            Method::PATCH,
+            Some(&EditMessageRequest { content }),
+            None,
+            MessageEndpoints::EditMessage(LobbyUlid(lobby_ulid), message_ulid),
+        )
+        .await?;
+
+    Ok(response)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_lobby_message(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Deleting message {} in lobby {}", message_ulid, lobby_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            MessageEndpoints::DeleteMessage(LobbyUlid(lobby_ulid), message_ulid),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn mark_lobby_read(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message_ulid: String,
+    silent: bool,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::POST,
+            Some(&MarkReadRequest {
+                message_id: message_ulid,
+                silent,
+            }),
+            None,
+            MessageEndpoints::MarkRead(LobbyUlid(lobby_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_message_reaction(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message_ulid: String,
+    emoji: String,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::PUT,
+            None::<&()>,
+            None,
+            MessageEndpoints::AddReaction(LobbyUlid(lobby_ulid), message_ulid, emoji),
+        )
+        .await?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_message_reaction(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    message_ulid: String,
+    emoji: String,
+) -> FrontendRepresentation<()> {
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            MessageEndpoints::RemoveReaction(LobbyUlid(lobby_ulid), message_ulid, emoji),
+        )
+        .await?;
+
+    Ok(())
+}
+
+// Must match the server's `SOCKET_AUTH_PROTOCOL`
+const SOCKET_AUTH_PROTOCOL: &str = "bearer";
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_lobby_socket_ticket(
+    state: State<'_, AppState>,
+    lobby_ulid: String,
+    since: Option<String>,
+) -> FrontendRepresentation<LobbySocketTicket> {
+    // The socket can't refresh the token by itself, so hand out one that is still valid
+    let token = state.0.refresh_access_token().await?;
+
+    // http -> ws, https -> wss
+    let mut url = MessageEndpoints::LobbySocket(LobbyUlid(lobby_ulid))
+        .format_with_api_url(API_URL)
+        .replacen("http", "ws", 1);
+    // After a reconnect the server first replays what was missed since this message
+    if let Some(since) = since {
+        url.push_str(&format!("?since={since}"));
+    }
+
+    Ok(LobbySocketTicket {
+        url,
+        protocols: vec![SOCKET_AUTH_PROTOCOL.to_owned(), token],
+    })
+}
@@ -1,3 +1,10 @@
 pub mod auth_commands;
+pub mod draft_commands;
+pub mod follow_commands;
+pub mod lobby_access_commands;
 pub mod lobby_commands;
+pub mod profile_commands;
 pub mod quest_commands;
+pub mod report_commands;
+pub mod search_commands;
+pub mod share_commands;
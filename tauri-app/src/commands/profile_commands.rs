@@ -0,0 +1,117 @@
+use reqwest::Method;
+use shared::{
+    endpoints::user_endpoints::UserEndpoints,
+    errors::{AppError, FrontendRepresentation},
+    models::{
+        user_dto::{ProfileResponse, UpdateProfileRequest},
+        PresignedUpload,
+    },
+};
+use tauri::{AppHandle, State};
+
+use log::{error, info};
+
+use crate::auth::service::AppState;
+use crate::drafts::service::{post_file, UploadError};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_my_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> FrontendRepresentation<ProfileResponse> {
+    let service = &state.0;
+
+    let profile: ProfileResponse = service
+        .perform_request(Method::GET, None::<&()>, None, UserEndpoints::GetMyProfile)
+        .await?;
+
+    // Signed avatar URLs expire, refresh the cached one every time the profile is loaded
+    service.update_session_profile(&app, &profile).await;
+
+    Ok(profile)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_my_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    payload: UpdateProfileRequest,
+) -> FrontendRepresentation<ProfileResponse> {
+    info!("Updating profile");
+    let service = &state.0;
+
+    let profile: ProfileResponse = service
+        .perform_request(
+            Method::PATCH,
+            Some(&payload),
+            None,
+            UserEndpoints::UpdateMyProfile,
+        )
+        .await?;
+
+    service.update_session_profile(&app, &profile).await;
+
+    Ok(profile)
+}
+
+// Takes a path on disk so the picked image never crosses the IPC bridge as a byte array
+#[tauri::command]
+#[specta::specta]
+pub async fn upload_avatar(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    source_path: String,
+) -> FrontendRepresentation<ProfileResponse> {
+    info!("Uploading new avatar from {}", source_path);
+    let service = &state.0;
+
+    let upload: PresignedUpload = service
+        .perform_request(
+            Method::POST,
+            None::<&()>,
+            None,
+            UserEndpoints::GetAvatarUploadUrl,
+        )
+        .await?;
+
+    let read_error = |e: std::io::Error| {
+        error!("Failed to read avatar {}: {}", source_path, e);
+        AppError::Custom(format!("Failed to read avatar file: {}", e))
+    };
+
+    // Checked before reading, so an oversized pick isn't loaded into memory
+    let size = tokio::fs::metadata(&source_path)
+        .await
+        .map_err(read_error)?
+        .len();
+    if size > upload.max_bytes {
+        return Err(AppError::Custom(format!(
+            "Avatar is too large, the limit is {} MB",
+            upload.max_bytes / (1024 * 1024)
+        ))
+        .into());
+    }
+    let data = tokio::fs::read(&source_path).await.map_err(read_error)?;
+
+    post_file(service, &upload, data)
+        .await
+        .map_err(|e| match e {
+            UploadError::Expired => AppError::Custom("Avatar upload form expired".into()),
+            UploadError::Failed(e) => e,
+        })?;
+
+    let profile: ProfileResponse = service
+        .perform_request(
+            Method::POST,
+            None::<&()>,
+            None,
+            UserEndpoints::ConfirmAvatarUpload,
+        )
+        .await?;
+
+    service.update_session_profile(&app, &profile).await;
+
+    Ok(profile)
+}
@@ -2,19 +2,24 @@ use reqwest::Method;
 use shared::{
     endpoints::{
         quest_proof_endpoints::QuestProofEndpoints,
-        user_quest_status_endpoints::UserQuestEndpoints, QuestProofUlid, QuestUlid, UserUlid,
+        user_quest_status_endpoints::UserQuestEndpoints, QuestProofUlid, UserUlid,
     },
     errors::{AppError, FrontendRepresentation},
     models::{
-        quest_proof_dto::{ProofDetailsResponse, SubmitProofRequest, SubmitProofResponse},
-        Pagination,
+        quest_proof_dto::{FeedMode, ProofDetailsResponse, ProofVisibility},
+        Page, PageQuery,
     },
 };
-use tauri::State;
+use tauri::{AppHandle, State};
 
 use log::{error, info};
 
 use crate::auth::service::AppState;
+use crate::drafts::{
+    draft::{DraftMediaKind, ProofDraft},
+    store::{copy_draft_media, save_draft},
+    upload_queue::UploadQueue,
+};
 
 #[tauri::command]
 #[specta::specta]
@@ -36,74 +41,42 @@ pub async fn get_daily_quests(
     Ok(response.quests)
 }
 
+// Media is read from disk by the upload queue instead of crossing IPC as bytes; the
+// returned draft is what ProofUploadProgress events refer to.
 #[tauri::command]
 #[specta::specta]
+#[expect(clippy::too_many_arguments)]
 pub async fn submit_quest_proof(
+    app: AppHandle,
     state: State<'_, AppState>,
+    queue: State<'_, UploadQueue>,
     quest_ulid: String,
-    payload: SubmitProofRequest,
-    image_list: Option<Vec<Vec<u8>>>,
-    audio_list: Option<Vec<Vec<u8>>>,
-) -> FrontendRepresentation<SubmitProofResponse> {
+    proof_text: Option<String>,
+    visibility: ProofVisibility,
+    photo_paths: Vec<String>,
+    voice_paths: Vec<String>,
+) -> FrontendRepresentation<ProofDraft> {
     let service = &state.0;
     let ulid = service.get_current_user_ulid().await?;
 
-    let response: SubmitProofResponse = service
-        .perform_request(
-            Method::POST,
-            Some(&payload),
-            None,
-            QuestProofEndpoints::InitSubmission(UserUlid(ulid.clone()), QuestUlid(quest_ulid)),
-        )
-        .await?;
+    let mut draft = ProofDraft::new(ulid, quest_ulid);
+    draft.proof_text = proof_text;
+    draft.visibility = visibility;
 
-    dbg!(&response);
-
-    let client = reqwest::Client::new();
-
-    if let (Some(images), urls) = (image_list, &response.photo_upload_urls) {
-        for (data, url) in images.into_iter().zip(urls) {
-            let res = client
-                .put(url)
-                .header("Content-Type", "image/jpeg")
-                .body(data)
-                .send()
-                .await
-                .map_err(|e| AppError::Custom(format!("S3 Network Error: {}", e)))?;
-
-            if !res.status().is_success() {
-                let err_body = res.text().await.unwrap_or_default();
-                error!("S3 Photo Upload Failed: {}", err_body);
-                return Err(AppError::Custom("Failed to upload photo to S3".into()).into());
-            }
-            info!("Photo uploaded to S3 successfully");
-        }
+    for source in photo_paths {
+        let path = copy_draft_media(&app, &draft, DraftMediaKind::Photo, &source).await?;
+        draft.photo_paths.push(path);
     }
-
-    if let (Some(audios), urls) = (audio_list, &response.voice_upload_urls) {
-        for (data, url) in audios.into_iter().zip(urls) {
-            client
-                .put(url)
-                .header("Content-Type", "audio/ogg")
-                .body(data)
-                .send()
-                .await
-                .map_err(|e| AppError::Custom(format!("S3 Audio Upload Error: {}", e)))?;
-        }
+    for source in voice_paths {
+        let path = copy_draft_media(&app, &draft, DraftMediaKind::Voice, &source).await?;
+        draft.voice_paths.push(path);
     }
+    save_draft(&app, &draft)?;
 
-    let _: () = service
-        .perform_request(
-            Method::POST,
-            None::<&()>,
-            None,
-            QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(response.proof_ulid.clone())),
-        )
-        .await?;
-
-    info!("Proof {} confirmed successfully", response.proof_ulid);
+    info!("Queued proof submission as draft {}", draft.draft_id);
+    queue.enqueue(&app, &draft.draft_id)?;
 
-    Ok(response)
+    Ok(draft)
 }
 
 #[tauri::command]
@@ -133,29 +106,32 @@ pub async fn get_proof_details(
 pub async fn get_proof_feed(
     state: State<'_, AppState>,
     limit: Option<u32>,
-    offset: Option<u32>,
-) -> FrontendRepresentation<shared::models::quest_proof_dto::ProofFeedResponse> {
+    cursor: Option<String>,
+    mode: Option<FeedMode>,
+) -> FrontendRepresentation<Page<ProofDetailsResponse>> {
     let service = &state.0;
 
     let ulid = service.get_current_user_ulid().await?;
+    let mode = mode.unwrap_or_default();
 
     info!(
-        "Fetching proof feed for user: {} (limit: {:?}, offset: {:?})",
-        ulid, limit, offset
+        "Fetching {:?} proof feed for user: {} (limit: {:?}, cursor: {:?})",
+        mode, ulid, limit, cursor
     );
 
-    let pagination = Pagination {
-        limit: limit.unwrap_or(10),
-        offset: offset.unwrap_or(0),
+    let endpoint = match mode {
+        FeedMode::Chronological => QuestProofEndpoints::GetFeed(UserUlid(ulid)),
+        FeedMode::ForYou => QuestProofEndpoints::GetRankedFeed(UserUlid(ulid)),
+        FeedMode::Following => QuestProofEndpoints::GetFollowingFeed(UserUlid(ulid)),
     };
 
-    let response: shared::models::quest_proof_dto::ProofFeedResponse = service
-        .perform_request(
-            Method::GET,
-            None::<&()>,
-            Some(&pagination),
-            QuestProofEndpoints::GetFeed(UserUlid(ulid)),
-        )
+    let pagination = PageQuery {
+        limit: Some(limit.unwrap_or(10)),
+        cursor,
+    };
+
+    let response: Page<ProofDetailsResponse> = service
+        .perform_request(Method::GET, None::<&()>, Some(&pagination), endpoint)
         .await?;
 
     Ok(response)
@@ -198,14 +174,20 @@ pub async fn toggle_proof_belief(
 async fn fetch_journal_by_id(
     service: &crate::auth::service::AuthService,
     target_user_ulid: String,
-) -> FrontendRepresentation<Vec<ProofDetailsResponse>> {
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<ProofDetailsResponse>> {
     info!("Executing journal fetch for: {}", target_user_ulid);
 
-    let response: Vec<ProofDetailsResponse> = service
+    let pagination = PageQuery {
+        limit: None,
+        cursor,
+    };
+
+    let response: Page<ProofDetailsResponse> = service
         .perform_request(
             Method::GET,
             None::<&()>,
-            None,
+            Some(&pagination),
             QuestProofEndpoints::GetUserJournal(UserUlid(target_user_ulid)),
         )
         .await?;
@@ -217,11 +199,12 @@ async fn fetch_journal_by_id(
 #[specta::specta]
 pub async fn get_my_journal(
     state: State<'_, AppState>,
-) -> FrontendRepresentation<Vec<ProofDetailsResponse>> {
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<ProofDetailsResponse>> {
     let service = &state.0;
     let ulid = service.get_current_user_ulid().await?;
 
-    fetch_journal_by_id(service, ulid).await
+    fetch_journal_by_id(service, ulid, cursor).await
 }
 
 #[tauri::command]
@@ -229,6 +212,7 @@ pub async fn get_my_journal(
 pub async fn get_someone_journal(
     state: State<'_, AppState>,
     user_ulid: String,
-) -> FrontendRepresentation<Vec<ProofDetailsResponse>> {
-    fetch_journal_by_id(&state.0, user_ulid).await
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<ProofDetailsResponse>> {
+    fetch_journal_by_id(&state.0, user_ulid, cursor).await
 }
@@ -0,0 +1,68 @@
+use reqwest::Method;
+use shared::{
+    endpoints::report_endpoints::ReportEndpoints,
+    errors::FrontendRepresentation,
+    models::{
+        report_dto::{
+            CreateReportRequest, PhotoMatchDto, ReportDto, ReportReason, ReportTargetType,
+        },
+        Page, PageQuery,
+    },
+};
+use tauri::State;
+
+use log::info;
+
+use crate::auth::service::AppState;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn report_content(
+    state: State<'_, AppState>,
+    target_type: ReportTargetType,
+    target_id: String,
+    reason: ReportReason,
+    details: Option<String>,
+) -> FrontendRepresentation<ReportDto> {
+    let service = &state.0;
+
+    info!("Reporting {:?} {} for {:?}", target_type, target_id, reason);
+
+    let payload = CreateReportRequest {
+        target_type,
+        target_id,
+        reason,
+        details,
+    };
+
+    let response: ReportDto = service
+        .perform_request(Method::POST, Some(&payload), None, ReportEndpoints::Create)
+        .await?;
+
+    Ok(response)
+}
+
+// Moderators only; near-duplicate proof photos, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_photo_matches(
+    state: State<'_, AppState>,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<PhotoMatchDto>> {
+    let pagination = PageQuery {
+        limit: None,
+        cursor,
+    };
+
+    let response: Page<PhotoMatchDto> = state
+        .0
+        .perform_request(
+            Method::GET,
+            None::<&()>,
+            Some(&pagination),
+            ReportEndpoints::GetPhotoMatches,
+        )
+        .await?;
+
+    Ok(response)
+}
@@ -0,0 +1,90 @@
+use reqwest::Method;
+use shared::{
+    endpoints::search_endpoints::{SearchEndpoints, SearchQuery},
+    errors::FrontendRepresentation,
+    models::{search_dto::SearchHit, Page},
+};
+use tauri::State;
+
+use crate::auth::service::AppState;
+
+async fn search(
+    state: State<'_, AppState>,
+    endpoint: SearchEndpoints,
+    query: SearchQuery,
+) -> FrontendRepresentation<Page<SearchHit>> {
+    // GET bodies are sent as the query string
+    let response: Page<SearchHit> = state
+        .0
+        .perform_request(Method::GET, Some(&query), None, endpoint)
+        .await?;
+
+    Ok(response)
+}
+
+// All of the user's lobbies, or only `lobby_ulid`
+#[tauri::command]
+#[specta::specta]
+pub async fn search_messages(
+    state: State<'_, AppState>,
+    q: String,
+    lobby_ulid: Option<String>,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<SearchHit>> {
+    let query = SearchQuery {
+        q,
+        lobby_id: lobby_ulid,
+        limit: None,
+        cursor,
+    };
+
+    search(state, SearchEndpoints::Messages, query).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_proofs(
+    state: State<'_, AppState>,
+    q: String,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<SearchHit>> {
+    let query = SearchQuery {
+        q,
+        cursor,
+        ..Default::default()
+    };
+
+    search(state, SearchEndpoints::Proofs, query).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_lobbies(
+    state: State<'_, AppState>,
+    q: String,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<SearchHit>> {
+    let query = SearchQuery {
+        q,
+        cursor,
+        ..Default::default()
+    };
+
+    search(state, SearchEndpoints::Lobbies, query).await
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn search_quests(
+    state: State<'_, AppState>,
+    q: String,
+    cursor: Option<String>,
+) -> FrontendRepresentation<Page<SearchHit>> {
+    let query = SearchQuery {
+        q,
+        cursor,
+        ..Default::default()
+    };
+
+    search(state, SearchEndpoints::Quests, query).await
+}
@@ -0,0 +1,54 @@
+use reqwest::Method;
+use shared::{
+    endpoints::{share_endpoints::ShareEndpoints, QuestProofUlid, API},
+    errors::FrontendRepresentation,
+    models::quest_proof_dto::ProofShareResponse,
+};
+use tauri::State;
+
+use log::info;
+
+use crate::auth::service::{AppState, API_URL};
+
+// Returns the public link of the proof page, ready to be copied or handed to the share sheet
+#[tauri::command]
+#[specta::specta]
+pub async fn share_proof(
+    state: State<'_, AppState>,
+    proof_ulid: String,
+) -> FrontendRepresentation<String> {
+    info!("Sharing proof {}", proof_ulid);
+
+    let share: ProofShareResponse = state
+        .0
+        .perform_request(
+            Method::POST,
+            None::<&()>,
+            None,
+            ShareEndpoints::CreateShare(QuestProofUlid(proof_ulid)),
+        )
+        .await?;
+
+    Ok(ShareEndpoints::PublicPage(share.token).format_with_api_url(API_URL))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn revoke_proof_share(
+    state: State<'_, AppState>,
+    proof_ulid: String,
+) -> FrontendRepresentation<()> {
+    info!("Revoking share link of proof {}", proof_ulid);
+
+    state
+        .0
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            ShareEndpoints::RevokeShare(QuestProofUlid(proof_ulid)),
+        )
+        .await?;
+
+    Ok(())
+}
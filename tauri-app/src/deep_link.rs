@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_specta::Event;
+
+// Must match `plugins.deep-link` in tauri.conf.json
+const SCHEME: &str = "speakplease";
+
+// Emitted when an invite link is opened while the app is running
+#[derive(Serialize, Deserialize, Clone, Type, Debug, Event)]
+pub struct InviteLinkOpened {
+    pub code: String,
+}
+
+// The last invite link, kept for a frontend that wasn't listening yet when the app was
+// launched by it
+#[derive(Default)]
+pub struct PendingInvite(Mutex<Option<String>>);
+
+impl PendingInvite {
+    pub fn take(&self) -> Option<String> {
+        self.0.lock().ok().and_then(|mut code| code.take())
+    }
+}
+
+// speakplease://invite/{code}
+fn invite_code(url: &Url) -> Option<String> {
+    if url.scheme() != SCHEME || url.host_str() != Some("invite") {
+        return None;
+    }
+
+    let code = url.path().trim_matches('/');
+    let valid = !code.is_empty() && code.chars().all(|c| c.is_ascii_alphanumeric());
+    valid.then(|| code.to_owned())
+}
+
+fn open_urls(app: &AppHandle, urls: Vec<Url>) {
+    for url in urls {
+        let Some(code) = invite_code(&url) else {
+            warn!("Ignoring unknown deep link: {}", url);
+            continue;
+        };
+        info!("Opened invite link {}", code);
+
+        if let Ok(mut pending) = app.state::<PendingInvite>().0.lock() {
+            *pending = Some(code.clone());
+        }
+        if let Err(e) = (InviteLinkOpened { code }).emit(app) {
+            warn!("Failed to emit invite link: {}", e);
+        }
+    }
+}
+
+pub fn init(app: &AppHandle) {
+    app.manage(PendingInvite::default());
+
+    // Installed bundles register the scheme themselves, this covers dev builds
+    #[cfg(any(windows, target_os = "linux"))]
+    if let Err(e) = app.deep_link().register_all() {
+        warn!("Failed to register deep link schemes: {}", e);
+    }
+
+    let handle = app.clone();
+    app.deep_link()
+        .on_open_url(move |event| open_urls(&handle, event.urls()));
+
+    match app.deep_link().get_current() {
+        Ok(Some(urls)) => open_urls(app, urls),
+        Ok(None) => {}
+        Err(e) => warn!("Failed to read the launch deep link: {}", e),
+    }
+}
@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+use shared::models::{quest_proof_dto::ProofVisibility, PresignedUpload};
+use specta::Type;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DraftMediaKind {
+    Photo,
+    Voice,
+}
+
+// Everything needed to pick up an interrupted submission. Media lives as files under
+// the app data dir, so only paths are kept here.
+#[derive(Serialize, Deserialize, Clone, Type, Debug)]
+pub struct ProofDraft {
+    pub draft_id: String,
+    pub user_ulid: String,
+    pub quest_ulid: String,
+    pub proof_text: Option<String>,
+    pub visibility: ProofVisibility,
+    pub photo_paths: Vec<String>,
+    pub voice_paths: Vec<String>,
+
+    // Set once InitSubmission succeeded; the text and media counts are frozen from then on
+    pub proof_ulid: Option<String>,
+    #[serde(default)]
+    pub photo_uploads: Vec<PresignedUpload>,
+    #[serde(default)]
+    pub voice_uploads: Vec<PresignedUpload>,
+    pub uploaded_photos: u32,
+    pub uploaded_voices: u32,
+
+    // Waiting in or being processed by the upload queue; re-enqueued on app start
+    #[serde(default)]
+    pub queued: bool,
+}
+
+impl ProofDraft {
+    pub fn new(user_ulid: String, quest_ulid: String) -> Self {
+        Self {
+            draft_id: ulid::Ulid::new().to_string(),
+            user_ulid,
+            quest_ulid,
+            proof_text: None,
+            visibility: ProofVisibility::default(),
+            photo_paths: Vec::new(),
+            voice_paths: Vec::new(),
+            proof_ulid: None,
+            photo_uploads: Vec::new(),
+            voice_uploads: Vec::new(),
+            uploaded_photos: 0,
+            uploaded_voices: 0,
+            queued: false,
+        }
+    }
+
+    pub fn is_submitted(&self) -> bool {
+        self.proof_ulid.is_some()
+    }
+
+    pub fn total_files(&self) -> u32 {
+        (self.photo_paths.len() + self.voice_paths.len()) as u32
+    }
+
+    pub fn uploaded_files(&self) -> u32 {
+        self.uploaded_photos + self.uploaded_voices
+    }
+
+    pub fn media_paths(&self, kind: DraftMediaKind) -> &Vec<String> {
+        match kind {
+            DraftMediaKind::Photo => &self.photo_paths,
+            DraftMediaKind::Voice => &self.voice_paths,
+        }
+    }
+
+    pub fn uploaded(&self, kind: DraftMediaKind) -> usize {
+        match kind {
+            DraftMediaKind::Photo => self.uploaded_photos as usize,
+            DraftMediaKind::Voice => self.uploaded_voices as usize,
+        }
+    }
+
+    pub fn media_paths_mut(&mut self, kind: DraftMediaKind) -> &mut Vec<String> {
+        match kind {
+            DraftMediaKind::Photo => &mut self.photo_paths,
+            DraftMediaKind::Voice => &mut self.voice_paths,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri_specta::Event;
+
+#[derive(Serialize, Deserialize, Clone, Copy, Type, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadStage {
+    Queued,
+    Initializing,
+    Uploading,
+    Confirming,
+    Done,
+    Failed,
+}
+
+// Emitted by the upload queue on every step of a draft submission
+#[derive(Serialize, Deserialize, Clone, Type, Debug, Event)]
+pub struct ProofUploadProgress {
+    pub draft_id: String,
+    pub proof_ulid: Option<String>,
+    pub stage: UploadStage,
+    pub uploaded_files: u32,
+    pub total_files: u32,
+    pub error: Option<String>,
+}
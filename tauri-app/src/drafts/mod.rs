@@ -0,0 +1,5 @@
+pub mod draft;
+pub mod events;
+pub mod service;
+pub mod store;
+pub mod upload_queue;
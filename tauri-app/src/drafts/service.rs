@@ -0,0 +1,354 @@
+use std::future::Future;
+use std::time::Duration;
+
+use log::{error, info, warn};
+use reqwest::{
+    multipart::{Form, Part},
+    Method, StatusCode,
+};
+use shared::{
+    endpoints::{quest_proof_endpoints::QuestProofEndpoints, QuestProofUlid, QuestUlid, UserUlid},
+    errors::{AppError, AppResult},
+    models::{
+        quest_proof_dto::{SubmitProofRequest, SubmitProofResponse},
+        PresignedUpload,
+    },
+};
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::auth::service::AuthService;
+use crate::drafts::{
+    draft::{DraftMediaKind, ProofDraft},
+    events::{ProofUploadProgress, UploadStage},
+    store::{delete_draft, read_draft_media, save_draft},
+};
+
+const MAX_ATTEMPTS: u32 = 5;
+const BASE_BACKOFF_MS: u64 = 1000;
+
+pub(crate) enum UploadError {
+    // The presigned policy is past its expiry, new forms fix that
+    Expired,
+    Failed(AppError),
+}
+
+pub fn emit_progress(
+    app: &AppHandle,
+    draft: &ProofDraft,
+    stage: UploadStage,
+    error: Option<String>,
+) {
+    let event = ProofUploadProgress {
+        draft_id: draft.draft_id.clone(),
+        proof_ulid: draft.proof_ulid.clone(),
+        stage,
+        uploaded_files: draft.uploaded_files(),
+        total_files: draft.total_files(),
+        error,
+    };
+
+    if let Err(e) = event.emit(app) {
+        warn!("Failed to emit upload progress: {}", e);
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_millis(BASE_BACKOFF_MS * 2u64.pow(attempt))
+}
+
+// Network failures and 5xx answers are worth another try, anything the server rejected is not
+fn is_retryable(err: &AppError) -> bool {
+    matches!(
+        err,
+        AppError::Api(_) | AppError::Server(_) | AppError::Database(_)
+    )
+}
+
+async fn with_retry<T, F, Fut>(mut op: F) -> AppResult<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = AppResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if is_retryable(&e) && attempt + 1 < MAX_ATTEMPTS => {
+                warn!(
+                    "Request failed (attempt {}): {:?}, retrying",
+                    attempt + 1,
+                    e
+                );
+                tokio::time::sleep(backoff(attempt)).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// Runs init -> uploads -> confirm, persisting the draft after every step so a failure
+// at any point can be resumed later without re-sending what already made it.
+// Returns the ULID of the confirmed proof.
+pub async fn submit_draft(
+    app: &AppHandle,
+    service: &AuthService,
+    mut draft: ProofDraft,
+) -> AppResult<String> {
+    if draft.proof_ulid.is_none() {
+        emit_progress(app, &draft, UploadStage::Initializing, None);
+
+        let payload = &SubmitProofRequest {
+            proof_text: draft.proof_text.clone(),
+            photo_count: draft.photo_paths.len() as u32,
+            voice_count: draft.voice_paths.len() as u32,
+            visibility: draft.visibility,
+        };
+
+        // Not retried: every POST creates a new proof, and a lost response would leave the
+        // previous one behind. A failure keeps the draft unsubmitted, so resuming starts over.
+        let response: SubmitProofResponse = service
+            .perform_request(
+                Method::POST,
+                Some(payload),
+                None,
+                QuestProofEndpoints::InitSubmission(
+                    UserUlid(draft.user_ulid.clone()),
+                    QuestUlid(draft.quest_ulid.clone()),
+                ),
+            )
+            .await?;
+
+        info!(
+            "Draft {} assigned proof {}",
+            draft.draft_id, response.proof_ulid
+        );
+
+        draft.proof_ulid = Some(response.proof_ulid);
+        draft.photo_uploads = response.photo_uploads;
+        draft.voice_uploads = response.voice_uploads;
+        save_draft(app, &draft)?;
+    }
+
+    for kind in [DraftMediaKind::Photo, DraftMediaKind::Voice] {
+        while draft.uploaded(kind) < draft.media_paths(kind).len() {
+            emit_progress(app, &draft, UploadStage::Uploading, None);
+
+            upload_next(app, service, &mut draft, kind).await?;
+
+            match kind {
+                DraftMediaKind::Photo => draft.uploaded_photos += 1,
+                DraftMediaKind::Voice => draft.uploaded_voices += 1,
+            }
+            save_draft(app, &draft)?;
+        }
+    }
+
+    emit_progress(app, &draft, UploadStage::Confirming, None);
+
+    let proof_ulid = draft.proof_ulid.clone().unwrap_or_default();
+    let confirm_ulid = proof_ulid.as_str();
+
+    let confirmed = with_retry(move || async move {
+        service
+            .perform_request::<_, ()>(
+                Method::POST,
+                None::<&()>,
+                None,
+                QuestProofEndpoints::ConfirmSubmission(QuestProofUlid(confirm_ulid.to_owned())),
+            )
+            .await
+    })
+    .await;
+
+    // The server only confirms an Uploading proof once; when an earlier attempt got through
+    // but its answer was lost, the retry is told the proof is already confirmed.
+    match confirmed {
+        Err(AppError::Custom(reason)) => {
+            warn!("Proof {} was already confirmed: {}", proof_ulid, reason)
+        }
+        other => other?,
+    }
+
+    info!("Proof {} confirmed successfully", proof_ulid);
+
+    emit_progress(app, &draft, UploadStage::Done, None);
+    delete_draft(app, &draft.draft_id).await?;
+
+    Ok(proof_ulid)
+}
+
+// Drops the Uploading proof a discarded draft had already initialized. A proof that is
+// gone or was confirmed in the meantime needs nothing more.
+pub async fn cancel_submission(service: &AuthService, proof_ulid: &str) -> AppResult<()> {
+    let result = service
+        .perform_request::<_, ()>(
+            Method::DELETE,
+            None::<&()>,
+            None,
+            QuestProofEndpoints::CancelSubmission(QuestProofUlid(proof_ulid.to_owned())),
+        )
+        .await;
+
+    match result {
+        Err(e @ (AppError::NotFound | AppError::Custom(_))) => {
+            warn!("Proof {} was not cancelled: {:?}", proof_ulid, e);
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+// Uploads the first not yet uploaded file of `kind`, re-requesting the upload forms
+// from the server when they are missing or S3 reports them as expired.
+async fn upload_next(
+    app: &AppHandle,
+    service: &AuthService,
+    draft: &mut ProofDraft,
+    kind: DraftMediaKind,
+) -> AppResult<()> {
+    let index = draft.uploaded(kind);
+    let path = draft.media_paths(kind)[index].clone();
+    let data = read_draft_media(&path).await?;
+
+    let mut last_error = AppError::Custom("No upload form available for media".into());
+    for attempt in 0..MAX_ATTEMPTS {
+        let upload = match kind {
+            DraftMediaKind::Photo => draft.photo_uploads.get(index),
+            DraftMediaKind::Voice => draft.voice_uploads.get(index),
+        }
+        .cloned();
+
+        let Some(upload) = upload else {
+            refresh_upload_urls(app, service, draft).await?;
+            continue;
+        };
+
+        // The storage would refuse it anyway, no point in sending the bytes
+        if data.len() as u64 > upload.max_bytes {
+            return Err(AppError::Custom(format!(
+                "File is too large, the limit is {} MB",
+                upload.max_bytes / (1024 * 1024)
+            )));
+        }
+
+        match post_file(service, &upload, data.clone()).await {
+            Ok(()) => {
+                info!("Uploaded {} to S3 successfully", path);
+                return Ok(());
+            }
+            Err(UploadError::Expired) => {
+                warn!("Upload form for {} expired, requesting new ones", path);
+                refresh_upload_urls(app, service, draft).await?;
+                continue;
+            }
+            Err(UploadError::Failed(e)) if !is_retryable(&e) => return Err(e),
+            Err(UploadError::Failed(e)) => {
+                warn!(
+                    "Upload of {} failed (attempt {}): {:?}",
+                    path,
+                    attempt + 1,
+                    e
+                );
+                last_error = e;
+            }
+        }
+
+        tokio::time::sleep(backoff(attempt)).await;
+    }
+
+    error!("Giving up on {} after {} attempts", path, MAX_ATTEMPTS);
+    Err(last_error)
+}
+
+// Presigned POST: the policy fields first, the file last
+pub(crate) async fn post_file(
+    service: &AuthService,
+    upload: &PresignedUpload,
+    data: Vec<u8>,
+) -> Result<(), UploadError> {
+    let mut form = Form::new();
+    for (name, value) in &upload.fields {
+        form = form.text(name.clone(), value.clone());
+    }
+
+    let content_type = upload
+        .fields
+        .get("Content-Type")
+        .cloned()
+        .unwrap_or_default();
+    let file = Part::bytes(data)
+        .file_name("upload")
+        .mime_str(&content_type)
+        .map_err(|e| UploadError::Failed(AppError::Custom(e.to_string())))?;
+    form = form.part("file", file);
+
+    let res = service
+        .client
+        .post(&upload.url)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| UploadError::Failed(AppError::Api(format!("S3 Network Error: {}", e))))?;
+
+    let status = res.status();
+    if status.is_success() {
+        return Ok(());
+    }
+
+    let err_body = res.text().await.unwrap_or_default();
+    if status == StatusCode::FORBIDDEN && is_policy_expired(&err_body) {
+        return Err(UploadError::Expired);
+    }
+    error!("S3 Upload Failed ({}): {}", status, err_body);
+
+    // 4xx means the policy rejected the file (size, type), retrying won't change that
+    let message = format!("S3 upload failed with status {}", status);
+    Err(UploadError::Failed(if status.is_client_error() {
+        AppError::Custom(message)
+    } else {
+        AppError::Api(message)
+    }))
+}
+
+// S3 answers 403 AccessDenied for any policy violation, only the expiry one goes away
+// with fresh forms:
+// <Error><Code>AccessDenied</Code><Message>Invalid according to Policy: Policy expired.</Message>
+fn is_policy_expired(body: &str) -> bool {
+    let tag = |name: &str| {
+        let open = format!("<{}>", name);
+        let close = format!("</{}>", name);
+        let start = body.find(&open)? + open.len();
+        let end = start + body[start..].find(&close)?;
+        Some(body[start..end].trim().to_owned())
+    };
+
+    tag("Code").as_deref() == Some("AccessDenied")
+        && tag("Message").is_some_and(|message| message.contains("Policy expired"))
+}
+
+async fn refresh_upload_urls(
+    app: &AppHandle,
+    service: &AuthService,
+    draft: &mut ProofDraft,
+) -> AppResult<()> {
+    let proof_ulid = draft.proof_ulid.clone().ok_or(AppError::NotFound)?;
+    let refresh_ulid = proof_ulid.as_str();
+
+    let response: SubmitProofResponse = with_retry(move || async move {
+        service
+            .perform_request(
+                Method::POST,
+                None::<&()>,
+                None,
+                QuestProofEndpoints::RefreshUploadUrls(QuestProofUlid(refresh_ulid.to_owned())),
+            )
+            .await
+    })
+    .await?;
+
+    draft.photo_uploads = response.photo_uploads;
+    draft.voice_uploads = response.voice_uploads;
+    save_draft(app, draft)
+}
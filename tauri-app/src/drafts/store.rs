@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+
+use log::{debug, error, warn};
+use shared::errors::{AppError, AppResult};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_store::StoreExt;
+
+use crate::drafts::draft::{DraftMediaKind, ProofDraft};
+
+const DRAFTS_STORE: &str = "drafts.json";
+const DRAFTS_DIR: &str = "drafts";
+
+fn draft_dir(app: &AppHandle, draft_id: &str) -> AppResult<PathBuf> {
+    let base = app.path().app_data_dir().map_err(|e| {
+        error!("Could not resolve app data dir: {}", e);
+        AppError::Custom(e.to_string())
+    })?;
+    Ok(base.join(DRAFTS_DIR).join(draft_id))
+}
+
+pub fn save_draft(app: &AppHandle, draft: &ProofDraft) -> AppResult<()> {
+    let store = app
+        .store(DRAFTS_STORE)
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let value = serde_json::to_value(draft).map_err(|e| AppError::Custom(e.to_string()))?;
+    store.set(draft.draft_id.clone(), value);
+
+    store.save().map_err(|e| {
+        error!("Failed to persist draft {}: {:?}", draft.draft_id, e);
+        AppError::Custom(e.to_string())
+    })?;
+
+    debug!("Draft {} persisted", draft.draft_id);
+    Ok(())
+}
+
+pub fn get_draft(app: &AppHandle, draft_id: &str) -> AppResult<ProofDraft> {
+    let store = app
+        .store(DRAFTS_STORE)
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let value = store.get(draft_id).ok_or(AppError::NotFound)?;
+    serde_json::from_value(value).map_err(|e| AppError::Custom(e.to_string()))
+}
+
+// Newest first; ULID draft ids sort by creation time
+pub fn list_drafts(app: &AppHandle, user_ulid: &str) -> AppResult<Vec<ProofDraft>> {
+    let store = app
+        .store(DRAFTS_STORE)
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let mut drafts: Vec<ProofDraft> = store
+        .values()
+        .into_iter()
+        .filter_map(|value| match serde_json::from_value::<ProofDraft>(value) {
+            Ok(draft) => Some(draft),
+            Err(e) => {
+                warn!("Skipping unreadable draft entry: {}", e);
+                None
+            }
+        })
+        .filter(|draft| draft.user_ulid == user_ulid)
+        .collect();
+
+    drafts.sort_by(|a, b| b.draft_id.cmp(&a.draft_id));
+    Ok(drafts)
+}
+
+pub async fn delete_draft(app: &AppHandle, draft_id: &str) -> AppResult<()> {
+    let store = app
+        .store(DRAFTS_STORE)
+        .map_err(|e| AppError::Custom(e.to_string()))?;
+
+    store.delete(draft_id);
+    store.save().map_err(|e| AppError::Custom(e.to_string()))?;
+
+    let dir = draft_dir(app, draft_id)?;
+    if let Err(e) = tokio::fs::remove_dir_all(&dir).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to remove media of draft {}: {}", draft_id, e);
+        }
+    }
+
+    debug!("Draft {} deleted", draft_id);
+    Ok(())
+}
+
+// Media is copied into the draft's own folder so temp files (e.g. fresh recordings)
+// being cleaned up by the OS can't break a later resume.
+pub async fn write_draft_media(
+    app: &AppHandle,
+    draft: &ProofDraft,
+    kind: DraftMediaKind,
+    data: &[u8],
+) -> AppResult<String> {
+    let dir = draft_dir(app, &draft.draft_id)?;
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|e| AppError::Custom(format!("Failed to create draft dir: {}", e)))?;
+
+    let (prefix, extension) = match kind {
+        DraftMediaKind::Photo => ("photo", "jpg"),
+        DraftMediaKind::Voice => ("voice", "ogg"),
+    };
+    let file_name = format!("{}_{}.{}", prefix, ulid::Ulid::new(), extension);
+    let path = dir.join(file_name);
+
+    tokio::fs::write(&path, data)
+        .await
+        .map_err(|e| AppError::Custom(format!("Failed to write draft media: {}", e)))?;
+
+    Ok(path.to_string_lossy().into_owned())
+}
+
+pub async fn copy_draft_media(
+    app: &AppHandle,
+    draft: &ProofDraft,
+    kind: DraftMediaKind,
+    source_path: &str,
+) -> AppResult<String> {
+    let data = read_draft_media(source_path).await?;
+    write_draft_media(app, draft, kind, &data).await
+}
+
+pub async fn read_draft_media(path: &str) -> AppResult<Vec<u8>> {
+    tokio::fs::read(path).await.map_err(|e| {
+        error!("Failed to read draft media {}: {}", path, e);
+        AppError::Custom(format!("Failed to read media file: {}", e))
+    })
+}
+
+pub async fn remove_draft_media_file(path: &str) {
+    if let Err(e) = tokio::fs::remove_file(path).await {
+        warn!("Failed to remove draft media {}: {}", path, e);
+    }
+}
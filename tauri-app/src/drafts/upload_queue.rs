@@ -0,0 +1,86 @@
+use log::{error, info, warn};
+use shared::errors::{AppError, AppResult};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use crate::auth::service::AppState;
+use crate::drafts::{
+    events::UploadStage,
+    service::{emit_progress, submit_draft},
+    store::{get_draft, list_drafts, save_draft},
+};
+
+// Submits drafts one at a time in the background. Callers only enqueue a draft id and
+// follow the outcome through `ProofUploadProgress` events.
+pub struct UploadQueue {
+    sender: UnboundedSender<String>,
+}
+
+impl UploadQueue {
+    pub fn spawn(app: AppHandle) -> Self {
+        let (sender, mut receiver) = unbounded_channel::<String>();
+
+        tauri::async_runtime::spawn(async move {
+            while let Some(draft_id) = receiver.recv().await {
+                process(&app, &draft_id).await;
+            }
+        });
+
+        Self { sender }
+    }
+
+    pub fn enqueue(&self, app: &AppHandle, draft_id: &str) -> AppResult<()> {
+        let mut draft = get_draft(app, draft_id)?;
+        draft.queued = true;
+        save_draft(app, &draft)?;
+
+        emit_progress(app, &draft, UploadStage::Queued, None);
+
+        self.sender
+            .send(draft.draft_id)
+            .map_err(|_| AppError::Custom("Upload queue is not running".into()))
+    }
+
+    // Picks up drafts that were still queued when the app was closed
+    pub fn restore(&self, app: &AppHandle, user_ulid: &str) {
+        let drafts = match list_drafts(app, user_ulid) {
+            Ok(drafts) => drafts,
+            Err(e) => {
+                warn!("Could not restore upload queue: {:?}", e);
+                return;
+            }
+        };
+
+        for draft in drafts.into_iter().rev().filter(|d| d.queued) {
+            info!("Restoring queued draft {}", draft.draft_id);
+            if let Err(e) = self.enqueue(app, &draft.draft_id) {
+                warn!("Failed to restore draft {}: {:?}", draft.draft_id, e);
+            }
+        }
+    }
+}
+
+async fn process(app: &AppHandle, draft_id: &str) {
+    // Already submitted or discarded while waiting in the queue
+    let Ok(draft) = get_draft(app, draft_id) else {
+        return;
+    };
+
+    let state = app.state::<AppState>();
+
+    if let Err(e) = submit_draft(app, &state.0, draft).await {
+        error!("Upload of draft {} failed: {:?}", draft_id, e);
+
+        // Keep what was uploaded so far; the user can resume the draft later
+        if let Ok(mut draft) = get_draft(app, draft_id) {
+            draft.queued = false;
+            if let Err(save_err) = save_draft(app, &draft) {
+                warn!(
+                    "Failed to persist failed draft {}: {:?}",
+                    draft_id, save_err
+                );
+            }
+            emit_progress(app, &draft, UploadStage::Failed, Some(e.to_string()));
+        }
+    }
+}
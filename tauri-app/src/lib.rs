@@ -1,14 +1,18 @@
 use tauri::Manager;
 use tauri_plugin_store::StoreExt;
-use tauri_specta::{collect_commands, Builder};
+use tauri_specta::{collect_commands, collect_events, Builder};
 
 use log::{debug, info};
 
 use crate::auth::service::AuthService;
 use crate::auth::{service::AppState, session::UserSession};
+use crate::deep_link::InviteLinkOpened;
+use crate::drafts::{events::ProofUploadProgress, upload_queue::UploadQueue};
 
 pub mod auth;
 pub mod commands;
+pub mod deep_link;
+pub mod drafts;
 
 use commands::auth_commands::{
     check_access_token, delete_refresh_token, get_current_session, login, logout, register,
@@ -20,31 +24,111 @@ use commands::quest_commands::{
 };
 
 use commands::lobby_commands::{
-    create_lobby, get_all_lobbies, get_lobby_detail, get_lobby_memebers_count, join_lobby,
+    add_message_reaction, ban_lobby_member, create_lobby, delete_lobby_message, discover_lobbies,
+    edit_lobby_message, get_all_lobbies, get_lobby_detail, get_lobby_memebers_count,
+    get_lobby_messages, get_lobby_socket_ticket, get_lobby_topics, get_online_members, join_lobby,
+    kick_lobby_member, leave_lobby, mark_lobby_read, remove_message_reaction, send_lobby_message,
+    set_lobby_member_role, sync_lobby_messages, transfer_lobby_ownership, unban_lobby_member,
+};
+
+use commands::lobby_access_commands::{
+    accept_lobby_invite, approve_join_request, create_lobby_invite, delete_join_request,
+    get_invite_preview, get_join_requests, get_lobby_invites, request_to_join_lobby,
+    revoke_lobby_invite, set_lobby_visibility, take_opened_invite,
+};
+
+use commands::draft_commands::{
+    add_draft_media, discard_proof_draft, list_proof_drafts, remove_draft_media,
+    resume_proof_draft, save_proof_draft,
+};
+
+use commands::report_commands::{get_photo_matches, report_content};
+
+use commands::profile_commands::{get_my_profile, update_my_profile, upload_avatar};
+
+use commands::search_commands::{search_lobbies, search_messages, search_proofs, search_quests};
+
+use commands::share_commands::{revoke_proof_share, share_proof};
+
+use commands::follow_commands::{
+    follow_user, get_follow_counts, get_followers, get_following, unfollow_user,
 };
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    let specta_builder = Builder::<tauri::Wry>::new().commands(collect_commands![
-        register,
-        login,
-        logout,
-        get_current_session,
-        check_access_token,
-        delete_refresh_token,
-        get_daily_quests,
-        submit_quest_proof,
-        get_proof_feed,
-        get_proof_details,
-        toggle_proof_belief,
-        get_someone_journal,
-        get_my_journal,
-        create_lobby,
-        get_all_lobbies,
-        get_lobby_detail,
-        get_lobby_memebers_count,
-        join_lobby
-    ]);
+    let specta_builder = Builder::<tauri::Wry>::new()
+        .commands(collect_commands![
+            register,
+            login,
+            logout,
+            get_current_session,
+            check_access_token,
+            delete_refresh_token,
+            get_daily_quests,
+            submit_quest_proof,
+            save_proof_draft,
+            add_draft_media,
+            remove_draft_media,
+            list_proof_drafts,
+            resume_proof_draft,
+            discard_proof_draft,
+            get_proof_feed,
+            get_proof_details,
+            toggle_proof_belief,
+            get_someone_journal,
+            get_my_journal,
+            create_lobby,
+            get_all_lobbies,
+            discover_lobbies,
+            get_lobby_topics,
+            get_lobby_detail,
+            get_lobby_memebers_count,
+            join_lobby,
+            leave_lobby,
+            kick_lobby_member,
+            set_lobby_member_role,
+            ban_lobby_member,
+            unban_lobby_member,
+            transfer_lobby_ownership,
+            set_lobby_visibility,
+            create_lobby_invite,
+            get_lobby_invites,
+            revoke_lobby_invite,
+            get_invite_preview,
+            accept_lobby_invite,
+            take_opened_invite,
+            request_to_join_lobby,
+            get_join_requests,
+            approve_join_request,
+            delete_join_request,
+            get_online_members,
+            get_lobby_messages,
+            send_lobby_message,
+            edit_lobby_message,
+            delete_lobby_message,
+            add_message_reaction,
+            remove_message_reaction,
+            mark_lobby_read,
+            get_lobby_socket_ticket,
+            sync_lobby_messages,
+            report_content,
+            get_photo_matches,
+            follow_user,
+            unfollow_user,
+            get_follow_counts,
+            get_followers,
+            get_following,
+            share_proof,
+            revoke_proof_share,
+            get_my_profile,
+            update_my_profile,
+            upload_avatar,
+            search_messages,
+            search_proofs,
+            search_lobbies,
+            search_quests
+        ])
+        .events(collect_events![ProofUploadProgress, InviteLinkOpened]);
 
     #[cfg(all(debug_assertions, not(mobile)))]
     specta_builder
@@ -54,9 +138,25 @@ pub fn run() {
         )
         .expect("Failed to export bindings");
 
+    let invoke_handler = specta_builder.invoke_handler();
+
     info!("Starting Speak Please application...");
 
-    tauri::Builder::default()
+    let mut builder = tauri::Builder::default();
+
+    // Has to be the first plugin. On Windows and Linux an opened invite link starts a second
+    // process, this hands its URL over to the running app and lets the second one exit.
+    #[cfg(desktop)]
+    {
+        builder = builder.plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.unminimize();
+                let _ = window.set_focus();
+            }
+        }));
+    }
+
+    builder
         .plugin(tauri_plugin_fs::init())
         .plugin(
             tauri_plugin_log::Builder::new()
@@ -70,7 +170,10 @@ pub fn run() {
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_mic_recorder::init())
-        .setup(|app| {
+        .plugin(tauri_plugin_deep_link::init())
+        .setup(move |app| {
+            specta_builder.mount_events(app);
+
             #[cfg(target_os = "android")]
             {
                 debug!("Configuring Android keyring...");
@@ -89,14 +192,23 @@ pub fn run() {
                         email: val["email"].as_str().unwrap_or_default().to_string(),
                         username: val["username"].as_str().unwrap_or_default().to_string(),
                         level: val["level"].as_u64().unwrap_or(0) as u32,
-                        avatar_url: val["avatar_url"].as_str().map(|s| s.to_string()),
+                        // Not persisted, `get_my_profile` brings a fresh signed URL
+                        avatar_url: None,
                     }
                 });
 
+            let restore_for = initial_session.as_ref().map(|s| s.user_ulid.clone());
+
             app.manage(AppState(AuthService::new(initial_session)));
+            app.manage(UploadQueue::spawn(app.handle().clone()));
+            deep_link::init(app.handle());
+
+            if let Some(user_ulid) = restore_for {
+                app.state::<UploadQueue>().restore(app.handle(), &user_ulid);
+            }
             Ok(())
         })
-        .invoke_handler(specta_builder.invoke_handler())
+        .invoke_handler(invoke_handler)
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }